@@ -0,0 +1,168 @@
+use std::{env, io, process, time::Duration};
+
+use snake_game::{
+    core::{take_store_errors, ArenaMode, Board, Boards},
+    net::{ClientMessage, Host, Session, DEFAULT_PORT},
+};
+use tokio::time::sleep;
+
+/// How often the lobby looks for players who left without a message.
+const LOBBY_POLL: Duration = Duration::from_millis(100);
+
+const USAGE: &str = "Usage: snake-server [--port PORT] [--board NAME] [--mode free-for-all|versus|coop|coop-pass-through] [--rounds N] [--players N]";
+
+struct Options {
+    port: u16,
+    board: Option<String>,
+    mode: ArenaMode,
+    rounds: u8,
+    players: usize,
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Self {
+            port: DEFAULT_PORT,
+            board: None,
+            mode: ArenaMode::FreeForAll,
+            rounds: 3,
+            players: 2,
+        };
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--port" => options.port = value()?.parse().map_err(|e| format!("{e}"))?,
+                "--board" => options.board = Some(value()?),
+                "--mode" => {
+                    options.mode = match value()?.as_str() {
+                        "free-for-all" => ArenaMode::FreeForAll,
+                        "versus" => ArenaMode::Versus,
+                        "coop" => ArenaMode::Coop {
+                            friendly_fire: true,
+                        },
+                        "coop-pass-through" => ArenaMode::Coop {
+                            friendly_fire: false,
+                        },
+                        mode => return Err(format!("unknown mode '{mode}'")),
+                    }
+                }
+                "--rounds" => options.rounds = value()?.parse().map_err(|e| format!("{e}"))?,
+                "--players" => options.players = value()?.parse().map_err(|e| format!("{e}"))?,
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        if options.players == 0 || options.rounds == 0 {
+            return Err("--players and --rounds must be at least 1".to_string());
+        }
+        Ok(options)
+    }
+
+    /// The named board, else the first saved one, else the built-in default.
+    fn board(&self) -> Result<Board, String> {
+        let boards = Boards::new();
+        match &self.board {
+            Some(name) => boards
+                .get_names()
+                .iter()
+                .position(|board| board == name)
+                .and_then(|index| boards.get(index))
+                .cloned()
+                .ok_or(format!("there is no board named '{name}'")),
+            None => Ok(boards.get(0).cloned().unwrap_or_default()),
+        }
+    }
+}
+
+/// Hosts matches back to back, so nobody's terminal has to stay open for a game to run.
+async fn serve(options: Options) -> io::Result<()> {
+    let board = options.board().map_err(io::Error::other)?;
+    for error in take_store_errors() {
+        eprintln!("{}", error.get_message());
+    }
+
+    loop {
+        let mut host = Host::bind(options.port, board.clone(), 0, options.players).await?;
+        println!(
+            "Waiting for {} players on {} ({})",
+            options.players,
+            host.get_address(),
+            board.get_name()
+        );
+
+        // Players who leave free their snake again, so the lobby counts the taken snakes
+        // rather than the joins.
+        let mut connected = 0;
+        while host.get_player_count() < options.players {
+            tokio::select! {
+                Some((player, message)) = host.recv() => {
+                    if let ClientMessage::Join { .. } = message {
+                        println!("Player {} joined", player + 1);
+                    }
+                }
+                _ = sleep(LOBBY_POLL) => {}
+            }
+            let count = host.get_player_count();
+            if count < connected {
+                println!("{count} of {} players connected", options.players);
+            }
+            connected = count;
+        }
+
+        println!("Starting a {} match", options.mode.get_name());
+        let Some(mut session) = Session::start(
+            &host,
+            board.clone(),
+            options.mode,
+            options.rounds,
+            options.players,
+        ) else {
+            return Err(io::Error::other(format!(
+                "{} has no room for {} snakes",
+                board.get_name(),
+                options.players
+            )));
+        };
+        let mut ticks = Session::interval();
+        loop {
+            tokio::select! {
+                _ = ticks.tick() => {
+                    if !session.tick(&mut host) {
+                        break;
+                    }
+                }
+                Some((player, message)) = host.recv() => session.handle(player, message),
+            }
+        }
+
+        let scores = session
+            .get_arena()
+            .get_players()
+            .iter()
+            .enumerate()
+            .map(|(index, player)| format!("Player {} {}", index + 1, player.get_score()))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        println!("Match over: {scores}");
+
+        host.close().await;
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = serve(options).await {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
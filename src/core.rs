@@ -1,12 +1,78 @@
+mod adaptive_speed;
+mod appearance;
+mod arena;
+mod autopilot;
+mod backups;
 mod board;
+mod board_check;
+mod board_rules;
 mod boards;
+mod campaign;
+mod cell;
+mod difficulty;
 mod direction;
+mod error;
+mod food_zone;
 mod game;
+mod gamepad;
+mod gate;
+mod messages;
+mod mode;
+mod objective;
+mod objective_records;
+mod png;
 mod point;
+mod prefab;
+mod prefabs;
+mod puzzle;
+mod puzzle_records;
+mod recent_boards;
+mod replay;
+mod saved_game;
 mod scoreboard;
+mod settings;
+mod stats_log;
+mod step;
+mod store;
+mod tutorial;
+mod versus;
+mod wall_style;
 
-pub use board::{Board, Wall};
-pub use boards::Boards;
+pub use adaptive_speed::AdaptiveSpeed;
+pub use appearance::Appearance;
+pub use arena::{Arena, ArenaDelta, ArenaMode, ArenaSnapshot};
+pub use autopilot::steer;
+pub use backups::{list_backups, Backup, BACKUPS_KEPT};
+pub use board::{Board, Wall, MAX_RATING};
+pub use board_check::{BoardCheck, SpawnStatus, SPAWN_RUNWAY};
+pub use board_rules::BoardRules;
+pub use boards::{Boards, Collision};
+pub use campaign::{CampaignLevel, CampaignProgress, DEFAULT_CLEAR_SCORE};
+pub use cell::{Cell, CellKind};
+pub use difficulty::{BoardFeatures, Difficulty, MAX_DIFFICULTY, NORMAL_SPEED, SPEED_STEP};
 pub use direction::Direction;
-pub use game::Game;
-pub use scoreboard::Scoreboard;
+pub use error::Error;
+pub use food_zone::FoodZone;
+pub use game::{DeathCause, Game, GameEvent, GameSnapshot, CHECKPOINT_LIVES, FOOD_SCORE};
+pub use gamepad::{GamepadAction, GamepadMapping};
+pub use gate::Gate;
+pub use messages::{Messages, DEFAULT_LANGUAGE};
+pub use mode::Mode;
+pub use objective::Objective;
+pub use objective_records::{ObjectiveRecord, ObjectiveRecords};
+pub use point::Point;
+pub use prefab::Prefab;
+pub use prefabs::Prefabs;
+pub use puzzle::Puzzle;
+pub use puzzle_records::PuzzleRecords;
+pub use recent_boards::RecentBoards;
+pub use replay::{Replay, ReplayInput, Rules, TimedInput, REPLAY_VERSION};
+pub use saved_game::SavedGame;
+pub use scoreboard::{ScoreCategory, Scoreboard, Streak, SCOREBOARD_VERSION, STREAK_SCORE};
+pub use settings::{Background, BoardBorder, BorderSet, Settings, Theme, MAX_SNAKE_NAME_LEN};
+pub use stats_log::{append_game, death_heatmap, read_games, GameRecord, STATS_LOG_PATH};
+pub use step::{step, GameState};
+pub use store::{check_files, take_store_errors, StoreError};
+pub use tutorial::{Tutorial, TutorialStep};
+pub use versus::Versus;
+pub use wall_style::{WallColor, WallMaterial, WallRegion, WallStyle};
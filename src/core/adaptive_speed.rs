@@ -0,0 +1,154 @@
+use super::{Game, GameEvent, NORMAL_SPEED};
+
+/// Apples eaten within this many ticks of the last one are quick catches.
+const QUICK_CATCH_TICKS: u32 = 40;
+/// Quick catches in a row that speed the snake up.
+const STREAK: u8 = 3;
+const SPEED_UP: u16 = 5;
+/// Eased off after a death that cost a life.
+const DEATH_EASE: u16 = 15;
+/// Eased off after the snake turned away from a wall or body at the last moment.
+const NEAR_MISS_EASE: u16 = 5;
+const MIN_SPEED: u16 = 70;
+const MAX_SPEED: u16 = 150;
+
+/// Follows how the player is doing and nudges the speed, in percent of the board's: a bit
+/// faster after a streak of quick catches, slower after deaths and near-misses.
+#[derive(Debug, Clone)]
+pub struct AdaptiveSpeed {
+    speed: u16,
+    ticks_since_catch: u32,
+    quick_catches: u8,
+    /// Whether the cell ahead of the head was deadly on the last tick.
+    danger: bool,
+}
+
+impl AdaptiveSpeed {
+    pub fn new() -> Self {
+        Self {
+            speed: NORMAL_SPEED,
+            ticks_since_catch: 0,
+            quick_catches: 0,
+            danger: false,
+        }
+    }
+
+    pub fn get_speed(&self) -> u16 {
+        self.speed
+    }
+
+    /// Checks the tick that just happened.
+    pub fn update(&mut self, game: &Game, events: &[GameEvent]) {
+        self.record(events, game.is_danger_ahead());
+    }
+
+    fn record(&mut self, events: &[GameEvent], danger_ahead: bool) {
+        self.ticks_since_catch += 1;
+        for event in events {
+            match event {
+                GameEvent::Ate { .. } => {
+                    if self.ticks_since_catch <= QUICK_CATCH_TICKS {
+                        self.quick_catches += 1;
+                    } else {
+                        self.quick_catches = 0;
+                    }
+                    self.ticks_since_catch = 0;
+                    if self.quick_catches >= STREAK {
+                        self.quick_catches = 0;
+                        self.speed = (self.speed + SPEED_UP).min(MAX_SPEED);
+                    }
+                }
+                GameEvent::Respawned => {
+                    self.quick_catches = 0;
+                    self.ease(DEATH_EASE);
+                }
+                GameEvent::Bumped | GameEvent::Checkpoint { .. } | GameEvent::GateOpened { .. } => {
+                }
+            }
+        }
+
+        let died = events.contains(&GameEvent::Respawned);
+        if self.danger && !danger_ahead && !died {
+            self.ease(NEAR_MISS_EASE);
+        }
+        self.danger = danger_ahead;
+    }
+
+    fn ease(&mut self, by: u16) {
+        self.speed = self.speed.saturating_sub(by).max(MIN_SPEED);
+    }
+}
+
+impl Default for AdaptiveSpeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_adaptive_speed {
+    use super::{AdaptiveSpeed, MAX_SPEED, MIN_SPEED, QUICK_CATCH_TICKS};
+    use crate::core::{Board, Direction, Game, GameEvent, Point, NORMAL_SPEED};
+
+    fn ate() -> GameEvent {
+        GameEvent::Ate {
+            at: Point::new(0, 0),
+            points: 1,
+        }
+    }
+
+    #[test]
+    fn quick_catches_speed_up() {
+        let mut adaptive = AdaptiveSpeed::new();
+        adaptive.record(&[ate()], false);
+        adaptive.record(&[ate()], false);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED);
+        adaptive.record(&[ate()], false);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED + 5);
+
+        // A slow catch breaks the streak.
+        adaptive.record(&[ate()], false);
+        adaptive.record(&[ate()], false);
+        for _ in 0..QUICK_CATCH_TICKS {
+            adaptive.record(&[], false);
+        }
+        adaptive.record(&[ate()], false);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED + 5);
+
+        for _ in 0..100 {
+            adaptive.record(&[ate()], false);
+        }
+        assert_eq!(adaptive.get_speed(), MAX_SPEED);
+    }
+
+    #[test]
+    fn deaths_and_near_misses_ease_off() {
+        let mut adaptive = AdaptiveSpeed::new();
+        adaptive.record(&[GameEvent::Respawned], false);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED - 15);
+
+        adaptive.record(&[], true);
+        adaptive.record(&[], true);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED - 15);
+        adaptive.record(&[], false);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED - 20);
+
+        // Running into the danger is a death, not a near miss; the speed stops at the minimum.
+        adaptive.record(&[], true);
+        adaptive.record(&[GameEvent::Respawned], false);
+        assert_eq!(adaptive.get_speed(), MIN_SPEED);
+    }
+
+    #[test]
+    fn danger_is_read_from_the_game() {
+        let board = Board::new("edge".to_string(), 10, vec![Point::new(4, 6)]);
+        let mut game = Game::new(&board, 3);
+        let mut adaptive = AdaptiveSpeed::new();
+        adaptive.update(&game, &[]);
+        assert!(game.is_danger_ahead());
+        game.rotation(Direction::Up);
+        assert!(!game.is_danger_ahead());
+        adaptive.update(&game, &[]);
+        assert_eq!(adaptive.get_speed(), NORMAL_SPEED - 5);
+    }
+}
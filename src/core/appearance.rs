@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the themes are drawn for a dark or a light terminal background.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Appearance {
+    /// Follows the terminal's background, dark when it cannot be told.
+    #[default]
+    Auto,
+    Dark,
+    Light,
+}
+
+impl Appearance {
+    pub fn next(&self) -> Self {
+        match self {
+            Appearance::Auto => Appearance::Dark,
+            Appearance::Dark => Appearance::Light,
+            Appearance::Light => Appearance::Auto,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Appearance::Auto => "auto",
+            Appearance::Dark => "dark",
+            Appearance::Light => "light",
+        }
+    }
+
+    /// The appearance to draw with, using `detected` for `Auto`.
+    pub fn resolve(&self, detected: Option<Appearance>) -> Appearance {
+        match self {
+            Appearance::Auto => match detected {
+                Some(Appearance::Light) => Appearance::Light,
+                _ => Appearance::Dark,
+            },
+            appearance => *appearance,
+        }
+    }
+
+    /// The background from a terminal's answer to the OSC 11 query,
+    /// like `ESC ] 11 ; rgb:ffff/ffff/dddd BEL`.
+    pub fn from_osc_reply(reply: &str) -> Option<Appearance> {
+        let rgb = reply.split("rgb:").nth(1)?;
+        let rgb = rgb.split(['\x07', '\x1b']).next()?;
+        let channels: Vec<f64> = rgb
+            .split('/')
+            .map(|channel| {
+                let digits = channel.len().clamp(1, 4) as u32;
+                u32::from_str_radix(channel, 16)
+                    .ok()
+                    .map(|value| value as f64 / (16f64.powi(digits as i32) - 1.0))
+            })
+            .collect::<Option<_>>()?;
+        let [red, green, blue] = channels[..] else {
+            return None;
+        };
+        Some(Self::from_luminance(
+            0.2126 * red + 0.7152 * green + 0.0722 * blue,
+        ))
+    }
+
+    /// The background from the `COLORFGBG` variable some terminals set, like `15;0`.
+    pub fn from_colorfgbg(value: &str) -> Option<Appearance> {
+        let background: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        Some(match background {
+            7 | 9..=15 => Appearance::Light,
+            _ => Appearance::Dark,
+        })
+    }
+
+    fn from_luminance(luminance: f64) -> Appearance {
+        if luminance > 0.5 {
+            Appearance::Light
+        } else {
+            Appearance::Dark
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_appearance {
+    use super::Appearance;
+
+    #[test]
+    fn reads_the_terminal_background() {
+        let light = "\x1b]11;rgb:ffff/ffff/dddd\x07";
+        let dark = "\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\";
+        assert_eq!(Appearance::from_osc_reply(light), Some(Appearance::Light));
+        assert_eq!(Appearance::from_osc_reply(dark), Some(Appearance::Dark));
+        assert_eq!(
+            Appearance::from_osc_reply("rgb:f/f/f"),
+            Some(Appearance::Light)
+        );
+        assert_eq!(Appearance::from_osc_reply("\x1b[?62c"), None);
+        assert_eq!(Appearance::from_osc_reply("rgb:zz/00/00"), None);
+
+        assert_eq!(Appearance::from_colorfgbg("0;15"), Some(Appearance::Light));
+        assert_eq!(
+            Appearance::from_colorfgbg("15;default;0"),
+            Some(Appearance::Dark)
+        );
+        assert_eq!(Appearance::from_colorfgbg("15;default"), None);
+    }
+
+    #[test]
+    fn manual_choice_wins() {
+        let light = Some(Appearance::Light);
+        assert_eq!(Appearance::Auto.resolve(light), Appearance::Light);
+        assert_eq!(Appearance::Auto.resolve(None), Appearance::Dark);
+        assert_eq!(Appearance::Dark.resolve(light), Appearance::Dark);
+    }
+}
@@ -0,0 +1,672 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    cell::{Cell, CellKind},
+    game::Snake,
+    point::Point,
+    Board, Direction, Game, Wall,
+};
+
+const FOOD_SCORE: u16 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Player {
+    snake: Snake,
+    direction: Direction,
+    score: u16,
+    alive: bool,
+}
+
+impl Player {
+    fn new(snake: Snake, direction: Direction) -> Self {
+        Self {
+            snake,
+            direction,
+            score: 0,
+            alive: true,
+        }
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    fn delta(&self, previous: &Player) -> PlayerDelta {
+        let old: Vec<&Point> = previous.snake.iter().collect();
+        let new: Vec<&Point> = self.snake.iter().collect();
+        let grown = (0..=new.len())
+            .find(|&grown| {
+                let kept = new.len() - grown;
+                kept <= old.len() && new[grown..] == old[..kept]
+            })
+            .unwrap_or(new.len());
+
+        PlayerDelta {
+            grow: new[..grown].iter().map(|point| (*point).clone()).collect(),
+            shrink: old.len() - (new.len() - grown),
+            direction: self.direction,
+            score: self.score,
+            alive: self.alive,
+        }
+    }
+
+    fn apply(&mut self, delta: PlayerDelta) {
+        for _ in 0..delta.shrink {
+            self.snake.pop_back();
+        }
+        for point in delta.grow.into_iter().rev() {
+            self.snake.push_front(point);
+        }
+        self.direction = delta.direction;
+        self.score = delta.score;
+        self.alive = delta.alive;
+    }
+
+    fn head(&self) -> Option<&Point> {
+        self.snake.front()
+    }
+
+    fn heading(&self) -> Direction {
+        match (self.snake.front(), self.snake.iter().nth(1)) {
+            (Some(head), Some(neck)) => neck.direction_of_neighbor(head),
+            _ => self.direction,
+        }
+    }
+}
+
+/// Rules for how a multiplayer game ends and what a death leaves behind.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ArenaMode {
+    /// Played until every snake is dead, the highest score wins.
+    FreeForAll,
+    /// Dead snakes turn into walls; the round ends when one snake is left.
+    Versus,
+    /// Everyone feeds one shared score and the run ends when any snake dies.
+    Coop { friendly_fire: bool },
+}
+
+impl ArenaMode {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::FreeForAll => Self::Versus,
+            Self::Versus => Self::Coop {
+                friendly_fire: true,
+            },
+            Self::Coop { .. } => Self::FreeForAll,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Self::FreeForAll => "Free for all",
+            Self::Versus => "Versus",
+            Self::Coop { .. } => "Co-op",
+        }
+    }
+}
+
+/// Everything that changes tick to tick; the board itself is only sent once.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArenaSnapshot {
+    mode: ArenaMode,
+    players: Vec<Player>,
+    food: Point,
+    debris: Vec<Wall>,
+}
+
+/// How one snake changed: cells added at the head and how many were dropped from the tail.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlayerDelta {
+    grow: Vec<Point>,
+    shrink: usize,
+    direction: Direction,
+    score: u16,
+    alive: bool,
+}
+
+/// Changes since the previous snapshot, a lot smaller than sending the whole arena every tick.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArenaDelta {
+    players: Vec<PlayerDelta>,
+    food: Option<Point>,
+    debris: Vec<Wall>,
+}
+
+/// A board shared by several snakes that move simultaneously, used for multiplayer games.
+pub struct Arena {
+    board: Board,
+    mode: ArenaMode,
+    /// Where the snakes start every round.
+    spawn: Vec<Player>,
+    players: Vec<Player>,
+    food: Point,
+    debris: Vec<Wall>,
+    /// No free cell was left for the food, which ends the round.
+    filled: bool,
+}
+
+impl Arena {
+    /// `None` when the board has no room for every snake and the food.
+    pub fn new(board: Board, mode: ArenaMode, players: usize, length: u16) -> Option<Self> {
+        let spawn = Self::spawn_players(&board, players, length)?;
+        let food = Self::find_lunch_point(&spawn, &[], &board)?;
+
+        Some(Self {
+            board,
+            mode,
+            players: spawn.clone(),
+            spawn,
+            food,
+            debris: Vec::new(),
+            filled: false,
+        })
+    }
+
+    /// Rebuilds an arena from a snapshot received over the network, for rendering.
+    pub fn from_snapshot(board: Board, snapshot: ArenaSnapshot) -> Self {
+        Self {
+            board,
+            mode: snapshot.mode,
+            spawn: Vec::new(),
+            players: snapshot.players,
+            food: snapshot.food,
+            debris: snapshot.debris,
+            filled: false,
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: ArenaSnapshot) {
+        self.mode = snapshot.mode;
+        self.players = snapshot.players;
+        self.food = snapshot.food;
+        self.debris = snapshot.debris;
+    }
+
+    pub fn snapshot(&self) -> ArenaSnapshot {
+        ArenaSnapshot {
+            mode: self.mode,
+            players: self.players.clone(),
+            food: self.food.clone(),
+            debris: self.debris.clone(),
+        }
+    }
+
+    /// What changed since `previous`, which must be a snapshot of this arena in the same round.
+    pub fn delta(&self, previous: &ArenaSnapshot) -> ArenaDelta {
+        ArenaDelta {
+            players: self
+                .players
+                .iter()
+                .zip(&previous.players)
+                .map(|(player, previous)| player.delta(previous))
+                .collect(),
+            food: (self.food != previous.food).then(|| self.food.clone()),
+            debris: self.debris[previous.debris.len().min(self.debris.len())..].to_vec(),
+        }
+    }
+
+    pub fn apply(&mut self, delta: ArenaDelta) {
+        for (player, delta) in self.players.iter_mut().zip(delta.players) {
+            player.apply(delta);
+        }
+        if let Some(food) = delta.food {
+            self.food = food;
+        }
+        self.debris.extend(delta.debris);
+    }
+
+    /// Respawns every snake on a clean board for the next round.
+    pub fn reset(&mut self) {
+        self.players = self.spawn.clone();
+        self.debris.clear();
+        self.filled = false;
+        self.place_food();
+    }
+
+    pub fn get_players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn rotation(&mut self, player: usize, direction: Direction) {
+        if let Some(player) = self.players.get_mut(player) {
+            let heading = player.heading();
+            if !heading.is_opposite(&direction) {
+                player.direction = direction;
+            }
+        }
+    }
+
+    /// Moves every living snake one cell; returns whether the game goes on.
+    pub fn walk(&mut self) -> bool {
+        let size = self.board.get_size();
+        let new_heads: Vec<Option<Point>> = self
+            .players
+            .iter()
+            .map(|player| {
+                player
+                    .alive
+                    .then(|| {
+                        player
+                            .head()
+                            .map(|head| head.get_neighbor(&player.direction, size))
+                    })
+                    .flatten()
+            })
+            .collect();
+
+        let eaten: Vec<bool> = new_heads
+            .iter()
+            .map(|head| head.as_ref() == Some(&self.food))
+            .collect();
+
+        let dead: Vec<bool> = new_heads
+            .iter()
+            .enumerate()
+            .map(|(index, head)| match head {
+                Some(head) => self.collides(index, head, &new_heads, &eaten),
+                None => false,
+            })
+            .collect();
+
+        for (index, player) in self.players.iter_mut().enumerate() {
+            match &new_heads[index] {
+                Some(_) if dead[index] => {
+                    player.alive = false;
+                    if self.mode == ArenaMode::Versus {
+                        self.debris.extend(player.snake.iter().cloned());
+                    }
+                    player.snake.clear();
+                }
+                Some(head) => {
+                    player.snake.push_front(head.clone());
+                    if eaten[index] {
+                        player.score += FOOD_SCORE;
+                    } else {
+                        player.snake.pop_back();
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if eaten
+            .iter()
+            .zip(&dead)
+            .any(|(eaten, dead)| *eaten && !*dead)
+        {
+            self.place_food();
+        }
+
+        !self.is_over()
+    }
+
+    /// Puts the food on a free cell, or ends the round when there is none.
+    fn place_food(&mut self) {
+        match Self::find_lunch_point(&self.players, &self.debris, &self.board) {
+            Some(food) => self.food = food,
+            None => self.filled = true,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        if self.filled {
+            return true;
+        }
+        let alive = self.players.iter().filter(|player| player.alive).count();
+        match self.mode {
+            ArenaMode::FreeForAll => alive == 0,
+            ArenaMode::Versus => alive <= 1,
+            ArenaMode::Coop { .. } => alive < self.players.len(),
+        }
+    }
+
+    pub fn get_mode(&self) -> ArenaMode {
+        self.mode
+    }
+
+    /// What the team has eaten together, used as the score in co-op.
+    pub fn get_shared_score(&self) -> u16 {
+        self.players.iter().map(|player| player.score).sum()
+    }
+
+    /// The only snake still alive, if exactly one is.
+    pub fn get_survivor(&self) -> Option<usize> {
+        let mut alive = self.players.iter().enumerate().filter(|(_, p)| p.alive);
+        match (alive.next(), alive.next()) {
+            (Some((index, _)), None) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Cells as seen by `viewer`, whose snake is drawn as the player's and the rest as rivals.
+    pub fn get_cells(&self, viewer: Option<usize>) -> Vec<Vec<Cell>> {
+        let mut result = self.board.get_cells();
+
+        self.debris.iter().for_each(|p| {
+            result[(p.get_x() + 1) as usize][(p.get_y() + 1) as usize] =
+                Cell::new(CellKind::Wall, "▓")
+        });
+        Game::put_food(&mut result, &self.food);
+        for (index, player) in self.players.iter().enumerate() {
+            let (head, body) = if Some(index) == viewer {
+                (CellKind::SnakeHead, CellKind::Snake)
+            } else {
+                (CellKind::RivalHead, CellKind::RivalSnake)
+            };
+            Game::put_snake(&mut result, &player.snake, head, body);
+        }
+
+        result
+    }
+}
+
+impl Arena {
+    /// The first snake starts on the board's spawn when it has one; the others, and all of
+    /// them otherwise, spread over the rows. `None` when one of them finds no room.
+    fn spawn_players(board: &Board, players: usize, length: u16) -> Option<Vec<Player>> {
+        let size = board.get_size() as i16;
+        let mut spawned: Vec<Player> = Vec::with_capacity(players);
+        for index in 0..players {
+            let player = match board.get_spawn() {
+                Some((head, direction)) if index == 0 => Player::new(
+                    Game::spawn_snake(board, head, *direction, length),
+                    *direction,
+                ),
+                _ => {
+                    let row = (index as i16 + 1) * size / (players as i16 + 1);
+                    Player::new(
+                        Self::create_snake(board, row, length, &spawned)?,
+                        Direction::Right,
+                    )
+                }
+            };
+            spawned.push(player);
+        }
+        Some(spawned)
+    }
+
+    /// A snake heading right along the first row from `row` on with `length` free cells in a
+    /// line and a free cell ahead of them, clear of walls and of the snakes in `taken`.
+    fn create_snake(board: &Board, row: i16, length: u16, taken: &[Player]) -> Option<Snake> {
+        let size = board.get_size() as i16;
+        let length = length as i16;
+        let free = |point: &Point| {
+            !board.is_wall(point) && !taken.iter().any(|player| player.snake.contains(point))
+        };
+        (0..size)
+            .map(|offset| (row + offset) % size)
+            .flat_map(|x| (0..size - length).map(move |y| (x, y)))
+            .find(|&(x, y)| (y..=y + length).all(|y| free(&Point::new(x, y))))
+            .map(|(x, y)| (y..y + length).rev().map(|y| Point::new(x, y)).collect())
+    }
+
+    /// Whether a move from `from` to `to` went across an edge on a board where edges kill.
+    fn leaves_board(&self, from: &Point, to: &Point) -> bool {
+        !self.board.get_rules().is_wrap()
+            && (from.get_x().abs_diff(to.get_x()) > 1 || from.get_y().abs_diff(to.get_y()) > 1)
+    }
+
+    /// A head dies on walls, on any body that stays this tick, when meeting another head,
+    /// and off the edge of a board that does not wrap; co-op without friendly fire only
+    /// counts its own body.
+    fn collides(
+        &self,
+        index: usize,
+        head: &Point,
+        new_heads: &[Option<Point>],
+        eaten: &[bool],
+    ) -> bool {
+        let friendly_fire = !matches!(
+            self.mode,
+            ArenaMode::Coop {
+                friendly_fire: false
+            }
+        );
+        let counts = |other: usize| friendly_fire || other == index;
+
+        let hits_head = new_heads.iter().enumerate().any(|(other, other_head)| {
+            other != index && counts(other) && other_head.as_ref() == Some(head)
+        });
+
+        let hits_body = self.players.iter().enumerate().any(|(other, player)| {
+            let moving_tail = player.alive && !eaten[other];
+            let body_len = player.snake.len() - usize::from(moving_tail);
+            counts(other)
+                && player
+                    .snake
+                    .iter()
+                    .take(body_len)
+                    .any(|point| point == head)
+        });
+
+        let off_board = self.players[index]
+            .head()
+            .is_some_and(|from| self.leaves_board(from, head));
+
+        off_board
+            || self.board.is_wall(head)
+            || self.debris.contains(head)
+            || hits_head
+            || hits_body
+    }
+
+    /// A free cell one of the living snakes can get to, so the food never sits in a pocket
+    /// closed off by walls or debris; `None` once there is no such cell.
+    fn find_lunch_point(players: &[Player], debris: &[Wall], board: &Board) -> Option<Point> {
+        let heads: Vec<Point> = players
+            .iter()
+            .filter(|player| player.alive)
+            .filter_map(|player| player.head().cloned())
+            .collect();
+        let reachable = board.get_reachable(&heads, debris);
+        let table_size = board.get_size() as i16;
+        let free: Vec<Point> = (0..table_size)
+            .flat_map(|x| (0..table_size).map(move |y| Point::new(x, y)))
+            .filter(|food| {
+                let on_snake = players.iter().any(|player| player.snake.contains(food));
+                reachable[food.get_x() as usize][food.get_y() as usize]
+                    && !on_snake
+                    && !debris.contains(food)
+            })
+            .collect();
+
+        board.pick_food_cell(&free, &mut rand::thread_rng())
+    }
+}
+
+#[cfg(test)]
+mod test_arena {
+    use std::collections::LinkedList;
+
+    use super::{Arena, ArenaMode};
+    use crate::core::{point::Point, Board, BoardRules, Direction};
+
+    #[test]
+    fn spawn_rows() {
+        let arena = Arena::new(
+            Board::new("test".to_string(), 9, Vec::new()),
+            ArenaMode::FreeForAll,
+            2,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(
+            arena.players[0].snake,
+            LinkedList::from([Point::new(3, 2), Point::new(3, 1), Point::new(3, 0)])
+        );
+        assert_eq!(
+            arena.players[1].snake,
+            LinkedList::from([Point::new(6, 2), Point::new(6, 1), Point::new(6, 0)])
+        );
+    }
+
+    #[test]
+    fn spawns_clear_of_walls() {
+        // Row 4 is walled off up to column 6, so the second snake moves down a row.
+        let walls = (0..7).map(|y| Point::new(4, y)).collect();
+        let mut board = Board::new("test".to_string(), 9, walls);
+        board.set_spawn(Point::new(7, 4), Direction::Up).unwrap();
+        let arena = Arena::new(board, ArenaMode::FreeForAll, 3, 3).unwrap();
+
+        assert_eq!(
+            arena.players[0].snake,
+            LinkedList::from([Point::new(7, 4), Point::new(8, 4), Point::new(0, 4)])
+        );
+        assert_eq!(arena.players[0].direction, Direction::Up);
+        assert_eq!(
+            arena.players[1].snake,
+            LinkedList::from([Point::new(5, 2), Point::new(5, 1), Point::new(5, 0)])
+        );
+        assert_eq!(arena.players[2].snake.front(), Some(&Point::new(6, 2)));
+
+        let walls = (0..5).flat_map(|x| (0..5).map(move |y| Point::new(x, y)));
+        let board = Board::new("full".to_string(), 5, walls.skip(3).collect());
+        assert!(Arena::new(board, ArenaMode::FreeForAll, 1, 3).is_none());
+    }
+
+    #[test]
+    fn edges_kill_unless_the_board_wraps() {
+        for wrap in [true, false] {
+            let mut board = Board::new("test".to_string(), 9, Vec::new());
+            board.set_rules(BoardRules::new(Some(wrap), None, None, None));
+            let mut arena = Arena::new(board, ArenaMode::FreeForAll, 1, 3).unwrap();
+            arena.food = Point::new(8, 8);
+            arena.rotation(0, Direction::Up);
+
+            for _ in 0..4 {
+                assert!(arena.walk());
+            }
+            assert_eq!(arena.walk(), wrap);
+        }
+    }
+
+    #[test]
+    fn a_full_board_ends_the_round() {
+        // Only the snake's row is open, and eating the last apple leaves no cell free.
+        let walls = (0..4)
+            .flat_map(|x| (0..4).map(move |y| Point::new(x, y)))
+            .filter(|point| point.get_x() != 2)
+            .collect();
+        let board = Board::new("test".to_string(), 4, walls);
+        let mut arena = Arena::new(board, ArenaMode::FreeForAll, 1, 3).unwrap();
+        assert_eq!(arena.food, Point::new(2, 3));
+
+        assert!(!arena.walk());
+        assert!(arena.is_over());
+        assert!(arena.players[0].alive);
+    }
+
+    #[test]
+    fn walk_and_eat() {
+        let mut arena = Arena::new(
+            Board::new("test".to_string(), 9, Vec::new()),
+            ArenaMode::FreeForAll,
+            2,
+            3,
+        )
+        .unwrap();
+        arena.food = Point::new(3, 3);
+
+        assert!(arena.walk());
+        assert_eq!(arena.players[0].score, 1);
+        assert_eq!(arena.players[0].snake.len(), 4);
+        assert_eq!(arena.players[1].snake.front(), Some(&Point::new(6, 3)));
+        assert_ne!(arena.food, Point::new(3, 3));
+    }
+
+    #[test]
+    fn head_on_collision_kills_both() {
+        let mut arena = Arena::new(
+            Board::new("test".to_string(), 9, Vec::new()),
+            ArenaMode::FreeForAll,
+            2,
+            3,
+        )
+        .unwrap();
+        arena.food = Point::new(0, 8);
+
+        arena.rotation(0, Direction::Down);
+        arena.rotation(1, Direction::Up);
+        assert!(arena.walk());
+        assert!(!arena.walk());
+        assert!(!arena.players[0].alive);
+        assert!(!arena.players[1].alive);
+    }
+
+    #[test]
+    fn following_a_tail_is_safe() {
+        let mut arena = Arena::new(
+            Board::new("test".to_string(), 9, Vec::new()),
+            ArenaMode::FreeForAll,
+            1,
+            4,
+        )
+        .unwrap();
+        arena.food = Point::new(8, 8);
+        arena.players[0].snake = LinkedList::from([
+            Point::new(1, 1),
+            Point::new(1, 2),
+            Point::new(2, 2),
+            Point::new(2, 1),
+        ]);
+        arena.players[0].direction = Direction::Down;
+
+        assert!(arena.walk());
+        assert!(arena.players[0].alive);
+    }
+
+    #[test]
+    fn versus_death_leaves_walls() {
+        let board = Board::new("test".to_string(), 9, vec![Point::new(3, 4)]);
+        let mut arena = Arena::new(board, ArenaMode::Versus, 2, 3).unwrap();
+        arena.food = Point::new(8, 8);
+
+        assert!(arena.walk());
+        assert!(!arena.walk());
+        assert_eq!(arena.get_survivor(), Some(1));
+        assert_eq!(arena.debris.len(), 3);
+        assert!(arena.debris.contains(&Point::new(3, 1)));
+
+        arena.reset();
+        assert!(arena.debris.is_empty());
+        assert!(arena.players.iter().all(|player| player.alive));
+    }
+
+    #[test]
+    fn coop_friendly_collisions() {
+        for friendly_fire in [true, false] {
+            let board = Board::new("test".to_string(), 9, Vec::new());
+            let mut arena = Arena::new(board, ArenaMode::Coop { friendly_fire }, 2, 3).unwrap();
+            arena.food = Point::new(8, 8);
+            arena.players[1].snake =
+                LinkedList::from([Point::new(4, 2), Point::new(4, 1), Point::new(4, 0)]);
+            arena.rotation(0, Direction::Down);
+
+            assert_eq!(arena.walk(), !friendly_fire);
+            assert_eq!(arena.players[0].alive, !friendly_fire);
+        }
+    }
+
+    #[test]
+    fn delta_replays_ticks() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let mut arena = Arena::new(board.clone(), ArenaMode::Versus, 2, 3).unwrap();
+        let mut mirror = Arena::from_snapshot(board, arena.snapshot());
+        arena.food = Point::new(3, 3);
+
+        for direction in [Direction::Right, Direction::Down, Direction::Down] {
+            let previous = arena.snapshot();
+            arena.rotation(0, direction);
+            arena.rotation(1, Direction::Up);
+            arena.walk();
+
+            mirror.apply(arena.delta(&previous));
+            assert_eq!(mirror.snapshot(), arena.snapshot());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use super::{CellKind, Direction, Game, Point};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+];
+
+/// Steers along the shortest path to the nearest apple, or to any free neighbour when no
+/// apple can be reached. Used for demo games.
+pub fn steer(game: &Game) -> Option<Direction> {
+    let cells = game.get_cells();
+    let size = cells.len() as u16 - 2;
+    let kind = |point: &Point| {
+        cells[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize].get_kind()
+    };
+    let is_free = |point: &Point| {
+        matches!(
+            kind(point),
+            CellKind::Empty | CellKind::Food | CellKind::Checkpoint
+        )
+    };
+
+    let head = (0..size as i16)
+        .flat_map(|x| (0..size as i16).map(move |y| Point::new(x, y)))
+        .find(|point| kind(point) == CellKind::SnakeHead)?;
+
+    let mut visited = vec![head.clone()];
+    let mut queue = VecDeque::new();
+    for direction in DIRECTIONS {
+        let next = head.get_neighbor(&direction, size);
+        if is_free(&next) {
+            visited.push(next.clone());
+            queue.push_back((next, direction));
+        }
+    }
+    let fallback = queue.front().map(|(_, direction)| *direction);
+
+    while let Some((point, first)) = queue.pop_front() {
+        if kind(&point) == CellKind::Food {
+            return Some(first);
+        }
+        for direction in DIRECTIONS {
+            let next = point.get_neighbor(&direction, size);
+            if is_free(&next) && !visited.contains(&next) {
+                visited.push(next.clone());
+                queue.push_back((next, first));
+            }
+        }
+    }
+
+    fallback
+}
+
+#[cfg(test)]
+mod test_autopilot {
+    use super::steer;
+    use crate::core::{Board, Direction, Game, Point};
+
+    #[test]
+    fn finds_the_way_around_walls() {
+        // The snake heads right along row 3 of a 7×7 board; a wall blocks the row.
+        let walls = (2..=4).map(|x| Point::new(x, 5)).collect();
+        let board = Board::new("test".to_string(), 7, walls);
+        let mut game = Game::with_seed(&board, 3, 7);
+
+        for _ in 0..100 {
+            if game.get_score() == 3 {
+                break;
+            }
+            if let Some(direction) = steer(&game) {
+                game.rotation(direction);
+            }
+            assert!(game.walk());
+        }
+        assert_eq!(game.get_score(), 3);
+    }
+
+    #[test]
+    fn turns_toward_food() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 1);
+        game.place_food(Point::new(0, 4)).unwrap();
+
+        assert_eq!(steer(&game), Some(Direction::Up));
+    }
+}
@@ -0,0 +1,152 @@
+//! Keeps the last few versions of the boards and scoreboard files, so a save that went wrong
+//! can be undone.
+
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{boards, scoreboard, Error};
+
+const BACKUP_DIR: &str = "./src/backups";
+/// How many backups of each file are kept; older ones are deleted.
+pub const BACKUPS_KEPT: usize = 5;
+
+const BACKED_UP: [&str; 2] = [boards::JSON_FILE_PATH, scoreboard::JSON_FILE_PATH];
+
+/// An earlier version of a saved file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    /// The file this is a backup of.
+    file: String,
+    path: String,
+    /// When it was taken, in milliseconds since the Unix epoch.
+    at: u64,
+}
+
+impl Backup {
+    /// When the backup was taken, in seconds since the Unix epoch.
+    pub fn get_at(&self) -> u64 {
+        self.at / 1000
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Puts the backup in place of the file it was taken from.
+    pub fn restore(&self) -> Result<(), Error> {
+        fs::copy(&self.path, &self.file)
+            .map(|_| ())
+            .map_err(|e| Error::Io {
+                path: self.file.clone(),
+                source: e,
+            })
+    }
+}
+
+/// The backups of `file`, newest first.
+pub fn list_backups(file: &str) -> Vec<Backup> {
+    list_in(BACKUP_DIR, file)
+}
+
+/// Copies `file` into the backups before `json` is written over it, unless nothing changed.
+/// A failed backup does not stop the save.
+pub(crate) fn rotate(file: &str, json: &str) {
+    if BACKED_UP.contains(&file) {
+        let _ = rotate_in(BACKUP_DIR, file, json, BACKUPS_KEPT);
+    }
+}
+
+fn rotate_in(dir: &str, file: &str, json: &str, kept: usize) -> std::io::Result<()> {
+    let Ok(old) = fs::read_to_string(file) else {
+        return Ok(());
+    };
+    if old == json {
+        return Ok(());
+    }
+
+    fs::create_dir_all(dir)?;
+    let at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    // Never overwrite a backup taken in the same millisecond.
+    let at = list_in(dir, file)
+        .first()
+        .map_or(at, |newest| at.max(newest.at + 1));
+    fs::write(
+        Path::new(dir).join(format!("{}-{at}.json", stem(file))),
+        old,
+    )?;
+
+    for backup in list_in(dir, file).into_iter().skip(kept) {
+        fs::remove_file(backup.path)?;
+    }
+    Ok(())
+}
+
+fn list_in(dir: &str, file: &str) -> Vec<Backup> {
+    let prefix = format!("{}-", stem(file));
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let at = name.strip_prefix(&prefix)?.strip_suffix(".json")?;
+            Some(Backup {
+                file: file.to_string(),
+                at: at.parse().ok()?,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.at));
+    backups
+}
+
+fn stem(file: &str) -> &str {
+    Path::new(file)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(file)
+}
+
+#[cfg(test)]
+mod test_backups {
+    use std::{env, fs};
+
+    use super::{list_in, rotate_in};
+
+    #[test]
+    fn keeps_the_last_versions() {
+        let dir = env::temp_dir().join(format!("snake-backups-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("boards.json").to_string_lossy().to_string();
+        let backups = dir.join("backups").to_string_lossy().to_string();
+
+        rotate_in(&backups, &file, "[]", 2).unwrap();
+        assert!(list_in(&backups, &file).is_empty());
+
+        for version in 1..=4 {
+            let json = format!("[{version}]");
+            rotate_in(&backups, &file, &json, 2).unwrap();
+            fs::write(&file, &json).unwrap();
+        }
+        rotate_in(&backups, &file, "[4]", 2).unwrap();
+
+        let kept = list_in(&backups, &file);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(fs::read_to_string(kept[0].get_path()).unwrap(), "[3]");
+        assert_eq!(fs::read_to_string(kept[1].get_path()).unwrap(), "[2]");
+
+        kept[1].restore().unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "[2]");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,15 +1,59 @@
-use super::point::Point;
+use std::{collections::VecDeque, fs};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    cell::{Cell, CellKind},
+    png,
+    point::Point,
+    BoardRules, CampaignLevel, Direction, Error, FoodZone, Gate, Objective, Prefab, Puzzle,
+    WallColor, WallRegion, WallStyle,
+};
 
 pub type Wall = Point;
 type Walls = Vec<Wall>;
 
+const DEFAULT_PUZZLE_MOVES: u16 = 50;
+
 use serde::{Deserialize, Serialize};
 
+/// The most stars a board can be rated.
+pub const MAX_RATING: u8 = 5;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Board {
     name: String,
     table_size: u16,
     walls: Walls,
+    #[serde(default)]
+    food_zones: Vec<FoodZone>,
+    /// Where the snake's head starts and which way it heads, instead of the board's middle.
+    #[serde(default)]
+    spawn: Option<(Point, Direction)>,
+    #[serde(default)]
+    puzzle: Option<Puzzle>,
+    #[serde(default)]
+    checkpoints: Vec<Point>,
+    #[serde(default, skip_serializing_if = "BoardRules::is_empty")]
+    rules: BoardRules,
+    /// How the walls are drawn.
+    #[serde(default, skip_serializing_if = "WallStyle::is_default")]
+    wall_style: WallStyle,
+    /// Walls drawn in a color of their own, over the style's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wall_regions: Vec<WallRegion>,
+    /// How many stars out of `MAX_RATING` the player gave the board, `None` when unrated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rating: Option<u8>,
+    /// The board's place in the unlock order of the pack it was installed from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    campaign: Option<Box<CampaignLevel>>,
+    /// The apples to eat before the timer runs out, instead of an endless game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    objective: Option<Objective>,
+    /// The gates still closed, in the order the stages open them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    gates: Vec<Gate>,
 }
 
 impl Board {
@@ -29,6 +73,17 @@ impl Board {
             name,
             table_size,
             walls,
+            food_zones: Vec::new(),
+            spawn: None,
+            puzzle: None,
+            checkpoints: Vec::new(),
+            rules: BoardRules::default(),
+            wall_style: WallStyle::default(),
+            wall_regions: Vec::new(),
+            rating: None,
+            campaign: None,
+            objective: None,
+            gates: Vec::new(),
         }
     }
 
@@ -37,14 +92,17 @@ impl Board {
             name,
             table_size: self.table_size,
             walls: self.walls.clone(),
-        }
-    }
-
-    pub fn default() -> Self {
-        Self {
-            name: "test board".to_string(),
-            table_size: 10,
-            walls: Vec::from([Wall::new(5, 5)]),
+            food_zones: self.food_zones.clone(),
+            spawn: self.spawn.clone(),
+            puzzle: self.puzzle.clone(),
+            checkpoints: self.checkpoints.clone(),
+            rules: self.rules,
+            wall_style: self.wall_style.clone(),
+            wall_regions: self.wall_regions.clone(),
+            rating: self.rating,
+            campaign: self.campaign.clone(),
+            objective: self.objective,
+            gates: self.gates.clone(),
         }
     }
 
@@ -52,30 +110,383 @@ impl Board {
         self.table_size
     }
 
+    /// The share of the playing field taken by walls, from 0 to 1.
+    pub fn get_wall_density(&self) -> f64 {
+        let cells = self.table_size as f64 * self.table_size as f64;
+        if cells == 0.0 {
+            return 0.0;
+        }
+        self.walls.len() as f64 / cells
+    }
+
+    /// Closed gates block the way like walls.
     pub fn is_wall(&self, point: &Wall) -> bool {
-        self.walls.contains(point)
+        self.walls.contains(point) || self.gates.iter().any(|gate| gate.contains(point))
+    }
+
+    pub fn get_walls(&self) -> &[Wall] {
+        &self.walls
     }
 
-    pub fn add_wall(&mut self, point: Wall) -> Result<(), String> {
+    /// A wall placed on the spawn cell clears the spawn, and one on a puzzle apple or a
+    /// checkpoint removes it.
+    pub fn add_wall(&mut self, point: Wall) -> Result<(), Error> {
         let valid_range = 0..self.table_size as i16;
 
         if valid_range.contains(&point.get_x()) && valid_range.contains(&point.get_y()) {
+            if self
+                .spawn
+                .as_ref()
+                .is_some_and(|(spawn, _)| *spawn == point)
+            {
+                self.spawn = None;
+            }
+            if self
+                .get_puzzle()
+                .is_some_and(|p| p.get_food().contains(&point))
+            {
+                self.toggle_puzzle_food(point.clone())?;
+            }
+            self.checkpoints.retain(|checkpoint| *checkpoint != point);
             self.walls.push(point);
             Ok(())
         } else {
-            Err("the point out of range".to_string())
+            Err(Error::OutOfRange(point))
+        }
+    }
+
+    /// Adds the prefab's walls with its top-left corner at `at`; cells off the board are skipped.
+    pub fn stamp(&mut self, prefab: &Prefab, at: &Point) {
+        for cell in prefab.get_cells() {
+            let wall = Wall::new(at.get_x() + cell.get_x(), at.get_y() + cell.get_y());
+            if !self.is_wall(&wall) {
+                let _ = self.add_wall(wall);
+            }
+        }
+    }
+
+    /// Adds the walls of a text grid with its top-left corner at `at`, returning how many were
+    /// new. `#`, `X` and the wall glyphs are walls and anything else is open; cells off the
+    /// board are skipped.
+    pub fn paste(&mut self, grid: &str, at: &Point) -> usize {
+        let mut added = 0;
+        for (x, row) in grid.lines().enumerate() {
+            for (y, c) in row.chars().enumerate() {
+                let wall = Wall::new(at.get_x() + x as i16, at.get_y() + y as i16);
+                if is_wall_glyph(c) && !self.is_wall(&wall) && self.add_wall(wall).is_ok() {
+                    added += 1;
+                }
+            }
+        }
+        added
+    }
+
+    /// Walls inside the rectangle spanned by two corners.
+    pub fn get_walls_between(&self, from: &Point, to: &Point) -> Vec<Wall> {
+        let rows = from.get_x().min(to.get_x())..=from.get_x().max(to.get_x());
+        let columns = from.get_y().min(to.get_y())..=from.get_y().max(to.get_y());
+        self.walls
+            .iter()
+            .filter(|w| rows.contains(&w.get_x()) && columns.contains(&w.get_y()))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_spawn(&self) -> Option<&(Point, Direction)> {
+        self.spawn.as_ref()
+    }
+
+    /// Starts the snake on `point` heading `direction`; both it and the cell ahead must be free.
+    pub fn set_spawn(&mut self, point: Point, direction: Direction) -> Result<(), Error> {
+        let valid_range = 0..self.table_size as i16;
+        if !valid_range.contains(&point.get_x()) || !valid_range.contains(&point.get_y()) {
+            return Err(Error::OutOfRange(point));
+        }
+        if direction == Direction::None {
+            return Err(Error::Invalid("the spawn needs a heading"));
+        }
+        if self.is_wall(&point) || self.is_wall(&point.get_neighbor(&direction, self.table_size)) {
+            return Err(Error::Invalid("the spawn is blocked by a wall"));
+        }
+
+        self.spawn = Some((point, direction));
+        Ok(())
+    }
+
+    pub fn clear_spawn(&mut self) {
+        self.spawn = None;
+    }
+
+    pub fn get_puzzle(&self) -> Option<&Puzzle> {
+        self.puzzle.as_ref()
+    }
+
+    /// Adds or removes a puzzle apple; the first one turns the board into a puzzle and
+    /// removing the last one turns it back.
+    pub fn toggle_puzzle_food(&mut self, point: Point) -> Result<(), Error> {
+        let valid_range = 0..self.table_size as i16;
+        if !valid_range.contains(&point.get_x()) || !valid_range.contains(&point.get_y()) {
+            return Err(Error::OutOfRange(point));
+        }
+        if self.is_wall(&point) {
+            return Err(Error::Invalid("apples cannot go on walls"));
+        }
+
+        let (mut food, max_moves) = match self.puzzle.take() {
+            Some(puzzle) => (puzzle.get_food().to_vec(), puzzle.get_max_moves()),
+            None => (Vec::new(), DEFAULT_PUZZLE_MOVES),
+        };
+        match food.iter().position(|p| *p == point) {
+            Some(index) => {
+                food.remove(index);
+            }
+            None => food.push(point),
+        }
+
+        if !food.is_empty() {
+            self.puzzle = Some(Puzzle::new(food, max_moves));
         }
+        Ok(())
+    }
+
+    pub fn set_puzzle_moves(&mut self, max_moves: u16) {
+        if let Some(puzzle) = &self.puzzle {
+            self.puzzle = Some(Puzzle::new(puzzle.get_food().to_vec(), max_moves.max(1)));
+        }
+    }
+
+    pub fn get_gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Adds a gate that opens after the ones already there; its cells must be on the board
+    /// and free of walls.
+    pub fn add_gate(&mut self, gate: Gate) -> Result<(), Error> {
+        let valid_range = 0..self.table_size as i16;
+        for point in gate.get_cells() {
+            if !valid_range.contains(&point.get_x()) || !valid_range.contains(&point.get_y()) {
+                return Err(Error::OutOfRange(point.clone()));
+            }
+            if self.is_wall(point) {
+                return Err(Error::Invalid("gates cannot go on walls"));
+            }
+        }
+        self.gates.push(gate);
+        Ok(())
+    }
+
+    /// Opens the first gate still closed, returning it.
+    pub fn open_gate(&mut self) -> Option<Gate> {
+        (!self.gates.is_empty()).then(|| self.gates.remove(0))
+    }
+
+    pub fn get_objective(&self) -> Option<&Objective> {
+        self.objective.as_ref()
+    }
+
+    pub fn set_objective(&mut self, objective: Option<Objective>) {
+        self.objective = objective;
+    }
+
+    pub fn get_rules(&self) -> &BoardRules {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: BoardRules) {
+        self.rules = rules;
+    }
+
+    pub fn get_wall_style(&self) -> &WallStyle {
+        &self.wall_style
+    }
+
+    pub fn set_wall_style(&mut self, wall_style: WallStyle) {
+        self.wall_style = wall_style;
+    }
+
+    pub fn get_checkpoints(&self) -> &[Point] {
+        &self.checkpoints
+    }
+
+    /// Adds or removes a checkpoint, a cell that becomes the respawn point once touched.
+    pub fn toggle_checkpoint(&mut self, point: Point) -> Result<(), Error> {
+        let valid_range = 0..self.table_size as i16;
+        if !valid_range.contains(&point.get_x()) || !valid_range.contains(&point.get_y()) {
+            return Err(Error::OutOfRange(point));
+        }
+        if self.is_wall(&point) {
+            return Err(Error::Invalid("checkpoints cannot go on walls"));
+        }
+
+        match self.checkpoints.iter().position(|p| *p == point) {
+            Some(index) => {
+                self.checkpoints.remove(index);
+            }
+            None => self.checkpoints.push(point),
+        }
+        Ok(())
+    }
+
+    pub fn add_food_zone(&mut self, zone: FoodZone) {
+        self.food_zones.push(zone);
+    }
+
+    pub fn clear_food_zones(&mut self) {
+        self.food_zones.clear();
+    }
+
+    pub fn get_food_zones(&self) -> &[FoodZone] {
+        &self.food_zones
+    }
+
+    /// How likely food is to appear on `point`: the summed weights of the zones covering it,
+    /// or 1 everywhere on a board without zones.
+    pub fn get_food_weight(&self, point: &Point) -> u32 {
+        if self.food_zones.is_empty() {
+            return 1;
+        }
+        self.food_zones
+            .iter()
+            .filter(|zone| zone.contains(point))
+            .map(|zone| zone.get_weight() as u32)
+            .sum()
+    }
+
+    /// The cells that can be reached from any of `starts` without going through a wall or
+    /// one of `blocked`, indexed `[x][y]`. The edges are only crossed when the board wraps.
+    pub fn get_reachable(&self, starts: &[Point], blocked: &[Point]) -> Vec<Vec<bool>> {
+        let size = self.table_size;
+        let mut seen = vec![vec![false; size as usize]; size as usize];
+        let mut queue = VecDeque::new();
+        for start in starts {
+            seen[start.get_x() as usize][start.get_y() as usize] = true;
+            queue.push_back(start.clone());
+        }
+        while let Some(point) = queue.pop_front() {
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                let next = point.get_neighbor(&direction, size);
+                let (x, y) = (next.get_x() as usize, next.get_y() as usize);
+                let wrapped = point.get_x().abs_diff(next.get_x()) > 1
+                    || point.get_y().abs_diff(next.get_y()) > 1;
+                if seen[x][y]
+                    || self.is_wall(&next)
+                    || blocked.contains(&next)
+                    || (wrapped && !self.rules.is_wrap())
+                {
+                    continue;
+                }
+                seen[x][y] = true;
+                queue.push_back(next);
+            }
+        }
+        seen
+    }
+
+    /// Picks one of the `free` cells by food weight, falling back to any of them when no zone
+    /// cell is free.
+    pub fn pick_food_cell<R: Rng>(&self, free: &[Point], rng: &mut R) -> Option<Point> {
+        free.choose_weighted(rng, |point| self.get_food_weight(point))
+            .or_else(|_| free.choose(rng).ok_or(()))
+            .ok()
+            .cloned()
     }
 
     pub fn remove_wall(&mut self, point: &Wall) {
         self.walls.retain(|p| p != point);
+        self.paint_wall(point, None);
+    }
+
+    pub fn get_wall_regions(&self) -> &[WallRegion] {
+        &self.wall_regions
+    }
+
+    pub fn get_rating(&self) -> Option<u8> {
+        self.rating
+    }
+
+    /// Rates the board 1 to `MAX_RATING` stars, or clears the rating with `None`.
+    pub fn set_rating(&mut self, rating: Option<u8>) -> Result<(), Error> {
+        if rating.is_some_and(|stars| !(1..=MAX_RATING).contains(&stars)) {
+            return Err(Error::Invalid("a rating is 1 to 5 stars"));
+        }
+        self.rating = rating;
+        Ok(())
+    }
+
+    pub fn get_campaign(&self) -> Option<&CampaignLevel> {
+        self.campaign.as_deref()
+    }
+
+    pub fn set_campaign(&mut self, campaign: Option<CampaignLevel>) {
+        self.campaign = campaign.map(Box::new);
+    }
+
+    pub fn get_wall_color(&self, wall: &Wall) -> Option<WallColor> {
+        self.wall_regions
+            .iter()
+            .find(|region| region.contains(wall))
+            .map(WallRegion::get_color)
+    }
+
+    /// Paints the walls inside the rectangle spanned by two corners, or clears their color.
+    pub fn paint_walls(&mut self, from: &Point, to: &Point, color: Option<WallColor>) {
+        for wall in self.get_walls_between(from, to) {
+            self.paint_wall(&wall, color);
+        }
+    }
+
+    fn paint_wall(&mut self, wall: &Wall, color: Option<WallColor>) {
+        for region in &mut self.wall_regions {
+            region.remove(wall);
+        }
+        self.wall_regions.retain(|region| !region.is_empty());
+        let Some(color) = color else {
+            return;
+        };
+        match self
+            .wall_regions
+            .iter_mut()
+            .find(|region| region.get_color() == color)
+        {
+            Some(region) => region.add(wall.clone()),
+            None => self
+                .wall_regions
+                .push(WallRegion::new(color, vec![wall.clone()])),
+        }
     }
 
     pub fn get_table(&self) -> Vec<Vec<String>> {
+        to_table(&self.get_cells())
+    }
+
+    pub fn get_cells(&self) -> Vec<Vec<Cell>> {
         let len = (self.get_size() + 2) as usize;
-        let mut result = vec![vec![" ".to_string(); len]; len];
+        let mut result = vec![vec![Cell::empty(); len]; len];
 
-        Self::put_walls(&mut result, &self.walls);
+        Self::put_walls(&mut result, &self.walls, &self.wall_style);
+        for region in &self.wall_regions {
+            for wall in region.get_walls() {
+                let cell = &mut result[(wall.get_x() + 1) as usize][(wall.get_y() + 1) as usize];
+                if cell.get_kind() == CellKind::Wall {
+                    *cell = cell.clone().with_tint(Some(region.get_color()));
+                }
+            }
+        }
+        for gate in &self.gates {
+            for point in gate.get_cells() {
+                result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] =
+                    Cell::new(CellKind::Gate, "▦");
+            }
+        }
+        for checkpoint in &self.checkpoints {
+            result[(checkpoint.get_x() + 1) as usize][(checkpoint.get_y() + 1) as usize] =
+                Cell::new(CellKind::Checkpoint, "⚑");
+        }
         Self::put_boarder(&mut result, len);
 
         result
@@ -84,32 +495,88 @@ impl Board {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Writes the board to `path` as JSON, to share it outside the saved boards.
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let json = serde_json::to_string(self).unwrap();
+        fs::write(path, json).map_err(|e| Error::Io {
+            path: path.to_string(),
+            source: e,
+        })
+    }
+
+    /// Writes the board to `path` as a PNG picture, to show it outside the game.
+    pub fn save_png(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, png::board_png(self)).map_err(|e| Error::Io {
+            path: path.to_string(),
+            source: e,
+        })
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self {
+            name: "test board".to_string(),
+            table_size: 10,
+            walls: Vec::from([Wall::new(5, 5)]),
+            food_zones: Vec::new(),
+            spawn: None,
+            puzzle: None,
+            checkpoints: Vec::new(),
+            rules: BoardRules::default(),
+            wall_style: WallStyle::default(),
+            wall_regions: Vec::new(),
+            rating: None,
+            campaign: None,
+            objective: None,
+            gates: Vec::new(),
+        }
+    }
 }
 
 impl Board {
-    fn put_walls(result: &mut [Vec<String>], walls: &Walls) {
+    fn put_walls(result: &mut [Vec<Cell>], walls: &Walls, style: &WallStyle) {
+        let wall = Cell::new(CellKind::Wall, style.get_glyph()).with_tint(style.get_color());
         walls.iter().for_each(|p| {
-            result[(p.get_x() + 1) as usize][(p.get_y() + 1) as usize] = "█".to_string()
+            result[(p.get_x() + 1) as usize][(p.get_y() + 1) as usize] = wall.clone()
         });
     }
 
-    fn put_boarder(result: &mut [Vec<String>], len: usize) {
+    fn put_boarder(result: &mut [Vec<Cell>], len: usize) {
+        let border = |glyph| Cell::new(CellKind::Border, glyph);
         let last_index = len - 1;
-        result[0].fill("─".to_string());
-        result[0][0] = "┌".to_string();
-        result[0][last_index] = "┐".to_string();
+        result[0].fill(border("─"));
+        result[0][0] = border("┌");
+        result[0][last_index] = border("┐");
 
-        result[last_index].fill("─".to_string());
-        result[last_index][0] = "└".to_string();
-        result[last_index][last_index] = "┘".to_string();
+        result[last_index].fill(border("─"));
+        result[last_index][0] = border("└");
+        result[last_index][last_index] = border("┘");
 
         for row in &mut result[1..=(len - 2)] {
-            row[0] = "│".to_string();
-            row[last_index] = "│".to_string();
+            row[0] = border("│");
+            row[last_index] = border("│");
         }
     }
 }
 
+pub fn to_table(cells: &[Vec<Cell>]) -> Vec<Vec<String>> {
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.get_glyph().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// The characters a pasted grid draws walls with, the ones of the wall materials included.
+fn is_wall_glyph(c: char) -> bool {
+    matches!(c, '#' | 'X' | 'x' | '█' | '▓' | '▒' | '░' | '♣')
+}
+
 impl<'a> IntoIterator for &'a Board {
     type Item = &'a Wall;
     type IntoIter = std::slice::Iter<'a, Wall>;
@@ -131,6 +598,22 @@ impl<'a> IntoIterator for &'a mut Board {
 #[cfg(test)]
 mod test_board {
     use super::{Board, Wall};
+    use crate::core::{BoardRules, CellKind, Direction, FoodZone, Prefab, WallColor};
+
+    #[test]
+    fn reachable_cells_stop_at_walls() {
+        // A wall across row 2 cuts the board in two unless it wraps around.
+        let walls = (0..5).map(|y| Wall::new(2, y)).collect();
+        let mut board = Board::new("test".to_string(), 5, walls);
+        let reachable = board.get_reachable(&[Wall::new(0, 0)], &[]);
+        assert!(reachable[1][4] && reachable[4][4]);
+        assert!(!reachable[2][0]);
+
+        board.set_rules(BoardRules::new(Some(false), None, None, None));
+        let reachable = board.get_reachable(&[Wall::new(0, 0)], &[Wall::new(1, 0)]);
+        assert!(reachable[0][4] && reachable[1][1]);
+        assert!(!reachable[1][0] && !reachable[3][0] && !reachable[4][4]);
+    }
 
     #[test]
     fn is_wall() {
@@ -146,6 +629,16 @@ mod test_board {
         assert!(!board.is_wall(&Wall::new(5, 4)));
     }
 
+    #[test]
+    fn wall_density() {
+        let board = Board::new("test".to_string(), 10, vec![Wall::new(1, 1)]);
+        assert_eq!(board.get_wall_density(), 0.01);
+        assert_eq!(
+            Board::new("empty".to_string(), 0, vec![]).get_wall_density(),
+            0.0
+        );
+    }
+
     #[test]
     fn check_create() {
         let board = Board::new(
@@ -156,4 +649,129 @@ mod test_board {
 
         assert_eq!(board.walls, Vec::from([Wall::new(3, 3), Wall::new(3, 0)]));
     }
+
+    #[test]
+    fn stamp_clips_to_the_board() {
+        let mut board = Board::new("test".to_string(), 4, vec![Wall::new(3, 3)]);
+        let prefab = Prefab::new("square".to_string(), vec![Wall::new(0, 0), Wall::new(1, 1)]);
+
+        board.stamp(&prefab, &Wall::new(2, 2));
+        board.stamp(&prefab, &Wall::new(2, 2));
+
+        assert_eq!(board.walls, vec![Wall::new(3, 3), Wall::new(2, 2)]);
+        assert_eq!(
+            board.get_walls_between(&Wall::new(3, 3), &Wall::new(0, 0)),
+            board.walls
+        );
+    }
+
+    #[test]
+    fn paste_a_grid() {
+        let mut board = Board::new("test".to_string(), 4, vec![Wall::new(1, 1)]);
+
+        let added = board.paste("#.\r\n.##\n   █\n", &Wall::new(1, 1));
+
+        assert_eq!(added, 2);
+        assert_eq!(
+            board.walls,
+            vec![Wall::new(1, 1), Wall::new(2, 2), Wall::new(2, 3)]
+        );
+        assert_eq!(board.paste("...\n. .", &Wall::new(0, 0)), 0);
+    }
+
+    #[test]
+    fn food_zones_weight_cells() {
+        let mut board = Board::new("test".to_string(), 5, Vec::new());
+        assert_eq!(board.get_food_weight(&Wall::new(2, 2)), 1);
+
+        board.add_food_zone(FoodZone::new(&Wall::new(1, 3), &Wall::new(0, 0), 2));
+        board.add_food_zone(FoodZone::new(&Wall::new(0, 0), &Wall::new(0, 4), 1));
+        assert_eq!(board.get_food_weight(&Wall::new(0, 1)), 3);
+        assert_eq!(board.get_food_weight(&Wall::new(1, 0)), 2);
+        assert_eq!(board.get_food_weight(&Wall::new(2, 2)), 0);
+
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..20 {
+            let cell = board
+                .pick_food_cell(&[Wall::new(4, 4), Wall::new(0, 4)], &mut rng)
+                .unwrap();
+            assert_eq!(cell, Wall::new(0, 4));
+        }
+        assert_eq!(
+            board.pick_food_cell(&[Wall::new(4, 4)], &mut rng),
+            Some(Wall::new(4, 4))
+        );
+        assert_eq!(board.pick_food_cell(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn spawn_avoids_walls() {
+        let mut board = Board::new("test".to_string(), 5, vec![Wall::new(1, 1)]);
+
+        assert!(board.set_spawn(Wall::new(1, 1), Direction::Up).is_err());
+        assert!(board.set_spawn(Wall::new(1, 0), Direction::Right).is_err());
+        assert!(board.set_spawn(Wall::new(5, 0), Direction::Right).is_err());
+        assert!(board.set_spawn(Wall::new(2, 2), Direction::None).is_err());
+
+        assert!(board.set_spawn(Wall::new(1, 0), Direction::Down).is_ok());
+        assert_eq!(board.get_spawn(), Some(&(Wall::new(1, 0), Direction::Down)));
+
+        board.add_wall(Wall::new(1, 0)).unwrap();
+        assert_eq!(board.get_spawn(), None);
+    }
+
+    #[test]
+    fn checkpoints() {
+        let mut board = Board::new("test".to_string(), 5, vec![Wall::new(1, 1)]);
+        assert!(board.toggle_checkpoint(Wall::new(1, 1)).is_err());
+        assert!(board.toggle_checkpoint(Wall::new(5, 0)).is_err());
+
+        board.toggle_checkpoint(Wall::new(2, 2)).unwrap();
+        board.toggle_checkpoint(Wall::new(3, 3)).unwrap();
+        board.toggle_checkpoint(Wall::new(2, 2)).unwrap();
+        assert_eq!(board.get_checkpoints(), [Wall::new(3, 3)]);
+        assert_eq!(board.get_cells()[4][4].get_kind(), CellKind::Checkpoint);
+
+        board.add_wall(Wall::new(3, 3)).unwrap();
+        assert!(board.get_checkpoints().is_empty());
+    }
+
+    #[test]
+    fn puzzle_food() {
+        let mut board = Board::new("test".to_string(), 5, vec![Wall::new(1, 1)]);
+        assert!(board.toggle_puzzle_food(Wall::new(1, 1)).is_err());
+
+        board.toggle_puzzle_food(Wall::new(0, 0)).unwrap();
+        board.toggle_puzzle_food(Wall::new(0, 1)).unwrap();
+        board.set_puzzle_moves(12);
+        let puzzle = board.get_puzzle().unwrap();
+        assert_eq!(puzzle.get_food(), &[Wall::new(0, 0), Wall::new(0, 1)]);
+        assert_eq!(puzzle.get_max_moves(), 12);
+
+        board.add_wall(Wall::new(0, 1)).unwrap();
+        board.toggle_puzzle_food(Wall::new(0, 0)).unwrap();
+        assert!(board.get_puzzle().is_none());
+    }
+
+    #[test]
+    fn painted_walls() {
+        let walls = vec![Wall::new(0, 0), Wall::new(0, 1), Wall::new(3, 3)];
+        let mut board = Board::new("test".to_string(), 5, walls);
+
+        board.paint_walls(&Wall::new(0, 0), &Wall::new(1, 1), Some(WallColor::Red));
+        board.paint_walls(&Wall::new(0, 1), &Wall::new(3, 3), Some(WallColor::Blue));
+        assert_eq!(board.get_wall_color(&Wall::new(0, 0)), Some(WallColor::Red));
+        assert_eq!(
+            board.get_wall_color(&Wall::new(0, 1)),
+            Some(WallColor::Blue)
+        );
+        assert_eq!(board.get_cells()[4][4].get_tint(), Some(WallColor::Blue));
+        assert_eq!(board.get_wall_regions().len(), 2);
+
+        board.remove_wall(&Wall::new(0, 0));
+        board.paint_walls(&Wall::new(0, 0), &Wall::new(4, 4), None);
+        assert!(board.get_wall_regions().is_empty());
+        assert_eq!(board.get_cells()[4][4].get_tint(), None);
+    }
 }
@@ -0,0 +1,200 @@
+use std::{cmp::Reverse, collections::VecDeque};
+
+use super::{Board, Direction, Point};
+
+/// Free cells straight ahead of the start that leave the player time to react.
+pub const SPAWN_RUNWAY: u16 = 3;
+
+/// How the cells around where the snake starts look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnStatus {
+    Clear,
+    /// A wall on the start cell or the one ahead of it.
+    Blocked,
+    /// Fewer than `SPAWN_RUNWAY` free cells ahead, with that many before the first wall.
+    Cramped(u16),
+}
+
+/// What could be wrong with a board, measured as it is edited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardCheck {
+    walls: usize,
+    open_cells: usize,
+    /// The open areas the snake cannot get to from where it starts, largest first.
+    unreachable: Vec<Vec<Point>>,
+    spawn: SpawnStatus,
+}
+
+impl BoardCheck {
+    pub fn new(board: &Board) -> Self {
+        let size = board.get_size();
+        let (start, direction) = start(board);
+
+        // Gates open as the game goes on, so the chambers behind them count as reachable.
+        let mut opened = board.clone();
+        while opened.open_gate().is_some() {}
+        let mut unreachable = regions(&opened);
+        unreachable.retain(|region| !region.contains(&start));
+        unreachable.sort_by_key(|region| Reverse(region.len()));
+
+        let ahead = start.get_neighbor(&direction, size);
+        let spawn = if board.is_wall(&start) || board.is_wall(&ahead) {
+            SpawnStatus::Blocked
+        } else {
+            let mut cell = start.clone();
+            let mut free = 0;
+            while free < SPAWN_RUNWAY {
+                let next = cell.get_neighbor(&direction, size);
+                if board.is_wall(&next) || leaves_board(&cell, &next, board) {
+                    break;
+                }
+                free += 1;
+                cell = next;
+            }
+            match free {
+                SPAWN_RUNWAY => SpawnStatus::Clear,
+                free => SpawnStatus::Cramped(free),
+            }
+        };
+
+        let cells = size as usize * size as usize;
+        Self {
+            walls: board.get_walls().len(),
+            open_cells: cells - board.get_walls().len(),
+            unreachable,
+            spawn,
+        }
+    }
+
+    pub fn get_walls(&self) -> usize {
+        self.walls
+    }
+
+    pub fn get_open_cells(&self) -> usize {
+        self.open_cells
+    }
+
+    pub fn get_unreachable(&self) -> &[Vec<Point>] {
+        &self.unreachable
+    }
+
+    /// The open cells in all the areas the snake cannot get to.
+    pub fn get_unreachable_cells(&self) -> usize {
+        self.unreachable.iter().map(Vec::len).sum()
+    }
+
+    pub fn get_spawn(&self) -> SpawnStatus {
+        self.spawn
+    }
+
+    /// Whether nothing was found that would spoil a game.
+    pub fn is_ok(&self) -> bool {
+        self.unreachable.is_empty() && self.spawn == SpawnStatus::Clear
+    }
+}
+
+/// Where the snake's head starts and which way it heads: the board's spawn, or the middle
+/// heading right.
+fn start(board: &Board) -> (Point, Direction) {
+    match board.get_spawn() {
+        Some((head, direction)) => (head.clone(), *direction),
+        None => {
+            let half = (board.get_size() as i16 - 1) / 2;
+            (Point::new(half, half), Direction::Right)
+        }
+    }
+}
+
+/// The board's open cells, split into the areas that connect.
+fn regions(board: &Board) -> Vec<Vec<Point>> {
+    let size = board.get_size();
+    let mut seen = vec![vec![false; size as usize]; size as usize];
+    let mut regions = Vec::new();
+    for x in 0..size as i16 {
+        for y in 0..size as i16 {
+            let first = Point::new(x, y);
+            if seen[x as usize][y as usize] || board.is_wall(&first) {
+                continue;
+            }
+            seen[x as usize][y as usize] = true;
+            let mut region = vec![first.clone()];
+            let mut queue = VecDeque::from([first]);
+            while let Some(point) = queue.pop_front() {
+                for direction in [
+                    Direction::Up,
+                    Direction::Down,
+                    Direction::Left,
+                    Direction::Right,
+                ] {
+                    let next = point.get_neighbor(&direction, size);
+                    let (x, y) = (next.get_x() as usize, next.get_y() as usize);
+                    if seen[x][y] || board.is_wall(&next) || leaves_board(&point, &next, board) {
+                        continue;
+                    }
+                    seen[x][y] = true;
+                    region.push(next.clone());
+                    queue.push_back(next);
+                }
+            }
+            regions.push(region);
+        }
+    }
+    regions
+}
+
+/// Whether stepping from `from` to its neighbor `to` goes over an edge the board does not
+/// wrap around.
+fn leaves_board(from: &Point, to: &Point, board: &Board) -> bool {
+    let wrapped = from.get_x().abs_diff(to.get_x()) > 1 || from.get_y().abs_diff(to.get_y()) > 1;
+    wrapped && !board.get_rules().is_wrap()
+}
+
+#[cfg(test)]
+mod test_board_check {
+    use super::{BoardCheck, SpawnStatus};
+    use crate::core::{Board, BoardRules, Direction, Gate, Point, Wall};
+
+    #[test]
+    fn finds_closed_areas_and_a_blocked_spawn() {
+        let open = BoardCheck::new(&Board::new("open".to_string(), 10, vec![]));
+        assert_eq!(open.get_walls(), 0);
+        assert_eq!(open.get_open_cells(), 100);
+        assert!(open.is_ok());
+
+        // Walls on every side of the corner cell (0, 0), across the edges as the board wraps.
+        let ring = [(0, 1), (1, 0), (9, 0), (0, 9)];
+        let walls = ring.map(|(x, y)| Wall::new(x, y)).to_vec();
+        let mut board = Board::new("closed".to_string(), 10, walls);
+        let check = BoardCheck::new(&board);
+        assert_eq!(check.get_open_cells(), 96);
+        assert_eq!(check.get_unreachable(), [vec![Point::new(0, 0)]]);
+        assert_eq!(check.get_unreachable_cells(), 1);
+
+        board.add_wall(Wall::new(5, 1)).unwrap();
+        board.set_spawn(Point::new(5, 3), Direction::Left).unwrap();
+        let check = BoardCheck::new(&board);
+        assert_eq!(check.get_spawn(), SpawnStatus::Cramped(1));
+
+        board.add_wall(Wall::new(4, 4)).unwrap();
+        board.add_wall(Wall::new(4, 5)).unwrap();
+        board.clear_spawn();
+        let check = BoardCheck::new(&board);
+        assert_eq!(check.get_spawn(), SpawnStatus::Blocked);
+        assert!(!check.is_ok());
+    }
+
+    #[test]
+    fn chambers_behind_gates_count_as_reachable() {
+        // A column of walls splits the board, with a gate as the only way through.
+        let walls = (0..10)
+            .filter(|x| *x != 2)
+            .map(|x| Wall::new(x, 2))
+            .collect();
+        let mut board = Board::new("chambers".to_string(), 10, walls);
+        board.set_rules(BoardRules::new(Some(false), None, None, None));
+        assert!(board.add_gate(Gate::new(vec![Wall::new(0, 2)], 3)).is_err());
+        board.add_gate(Gate::new(vec![Wall::new(2, 2)], 3)).unwrap();
+        assert!(board.is_wall(&Wall::new(2, 2)));
+        assert!(BoardCheck::new(&board).get_unreachable().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use super::settings::MAX_APPLES;
+
+const MIN_TICK_MILLIS: u64 = 20;
+const MAX_TICK_MILLIS: u64 = 1000;
+
+/// Rules a board brings along, applied over the player's settings whenever it is played.
+/// Each one left out keeps the usual behaviour.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct BoardRules {
+    /// Whether the snake comes back on the other side of an edge; without it, edges kill.
+    wrap: Option<bool>,
+    /// Milliseconds per tick, for a faster or slower snake.
+    tick_millis: Option<u64>,
+    apples: Option<u8>,
+    /// Only a circle around the head is lit, as with the spotlight setting.
+    fog: Option<bool>,
+}
+
+impl BoardRules {
+    pub fn new(
+        wrap: Option<bool>,
+        tick_millis: Option<u64>,
+        apples: Option<u8>,
+        fog: Option<bool>,
+    ) -> Self {
+        Self {
+            wrap,
+            tick_millis,
+            apples,
+            fog,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn is_wrap(&self) -> bool {
+        self.wrap.unwrap_or(true)
+    }
+
+    /// The tick length, kept within reason whatever the file says.
+    pub fn get_tick_millis(&self) -> Option<u64> {
+        self.tick_millis
+            .map(|millis| millis.clamp(MIN_TICK_MILLIS, MAX_TICK_MILLIS))
+    }
+
+    pub fn get_apples(&self) -> Option<u8> {
+        self.apples.map(|apples| apples.clamp(1, MAX_APPLES))
+    }
+
+    pub fn is_fog(&self) -> bool {
+        self.fog.unwrap_or(false)
+    }
+
+    /// The rules that differ from the usual, to show with the board.
+    pub fn describe(&self) -> Vec<String> {
+        let mut rules = Vec::new();
+        if let Some(wrap) = self.wrap {
+            rules.push(match wrap {
+                true => "edges wrap".to_string(),
+                false => "edges kill".to_string(),
+            });
+        }
+        if let Some(millis) = self.get_tick_millis() {
+            rules.push(format!("{millis} ms ticks"));
+        }
+        if let Some(apples) = self.get_apples() {
+            rules.push(match apples {
+                1 => "1 apple".to_string(),
+                apples => format!("{apples} apples"),
+            });
+        }
+        if let Some(fog) = self.fog {
+            rules.push(match fog {
+                true => "fog".to_string(),
+                false => "no fog".to_string(),
+            });
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod test_board_rules {
+    use super::BoardRules;
+
+    #[test]
+    fn describes_and_bounds_overrides() {
+        let rules: BoardRules =
+            serde_json::from_str(r#"{ "wrap": false, "tick_millis": 5, "apples": 3 }"#).unwrap();
+        assert!(!rules.is_wrap());
+        assert_eq!(rules.get_tick_millis(), Some(20));
+        assert!(!rules.is_fog());
+        assert_eq!(rules.describe(), ["edges kill", "20 ms ticks", "3 apples"]);
+
+        let rules: BoardRules = serde_json::from_str(r#"{ "apples": 255 }"#).unwrap();
+        assert_eq!(rules.get_apples(), Some(10));
+
+        assert!(BoardRules::default().is_wrap());
+        assert!(BoardRules::default().is_empty());
+        assert!(BoardRules::default().describe().is_empty());
+    }
+}
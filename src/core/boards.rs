@@ -1,11 +1,81 @@
-use std::{
-    fs::File,
-    io::{BufReader, Write},
-};
+use std::{cmp::Reverse, fs, sync::Mutex};
 
-use super::Board;
+use rand::{seq::SliceRandom, Rng};
+use serde_json::{json, Value};
 
-const JSON_FILE_PATH: &str = "./src/boards.json";
+use super::{store, Board, Difficulty, Error};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/boards.json";
+/// Where boards that no longer load are moved, so one broken entry does not cost the rest.
+const QUARANTINE_FILE_PATH: &str = "./src/corrupt/boards.json";
+
+const NAME_ADJECTIVES: [&str; 24] = [
+    "ancient",
+    "bitter",
+    "crooked",
+    "dusty",
+    "endless",
+    "forgotten",
+    "frozen",
+    "gilded",
+    "hidden",
+    "hollow",
+    "jagged",
+    "lonely",
+    "mossy",
+    "narrow",
+    "quiet",
+    "restless",
+    "rusty",
+    "shifting",
+    "silent",
+    "sunken",
+    "tangled",
+    "twisted",
+    "wandering",
+    "winding",
+];
+const NAME_NOUNS: [&str; 24] = [
+    "archive",
+    "burrow",
+    "canyon",
+    "cellar",
+    "citadel",
+    "courtyard",
+    "crypt",
+    "garden",
+    "grove",
+    "harbor",
+    "hollow",
+    "keep",
+    "labyrinth",
+    "maze",
+    "meadow",
+    "orchard",
+    "passage",
+    "quarry",
+    "ruins",
+    "spiral",
+    "temple",
+    "tower",
+    "tunnels",
+    "warren",
+];
+/// Random picks tried before falling back to numbering one.
+const NAME_ATTEMPTS: usize = 20;
+
+/// The names of the boards quarantined since the last call to [`Boards::take_quarantined`].
+static QUARANTINED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// What to do with an imported board whose name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collision {
+    /// Keeps both, saving the imported one under a free name.
+    Rename,
+    Skip,
+    /// Replaces the board of the same name.
+    Overwrite,
+}
 
 #[derive(Debug)]
 pub struct Boards {
@@ -13,24 +83,205 @@ pub struct Boards {
 }
 
 impl Boards {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
     pub fn new() -> Self {
-        let file = File::open(JSON_FILE_PATH).unwrap();
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        Self::from_entries(store::load(path, || None))
+    }
+
+    /// The boards that were moved to the quarantine file since the last call, by name.
+    pub fn take_quarantined() -> Vec<String> {
+        std::mem::take(&mut QUARANTINED.lock().unwrap())
+    }
+
+    pub fn get_quarantine_path() -> &'static str {
+        QUARANTINE_FILE_PATH
+    }
 
-        let reader = BufReader::new(file);
-        let boards: Vec<Board> = serde_json::from_reader(reader).unwrap();
+    /// Loads what it can, quarantining the entries that are not boards any more.
+    fn from_entries(entries: Option<Vec<Value>>) -> Self {
+        let Some(entries) = entries else {
+            return Self {
+                boards: vec![Board::default()],
+            };
+        };
 
+        let (mut boards, broken) = sort_out(entries);
+        if broken.is_empty() {
+            return Self { boards };
+        }
+
+        let names = broken.iter().map(|(name, _)| name.clone()).collect();
+        let records = broken.into_iter().map(|(_, record)| record).collect();
+        if store::quarantine(JSON_FILE_PATH, QUARANTINE_FILE_PATH, records) {
+            QUARANTINED.lock().unwrap().extend::<Vec<_>>(names);
+            if boards.is_empty() {
+                boards.push(Board::default());
+            }
+            // Saved at once so another `Boards` does not quarantine the same entries again.
+            store::save(JSON_FILE_PATH, &boards);
+        }
         Self { boards }
     }
 
-    pub fn add(&mut self, name: String, board: Board) -> Result<(), String> {
-        if self.boards.iter().any(|board| board.get_name() == &name) {
-            return Err(format!("Board '{}' already exists", name));
+    pub fn add(&mut self, name: String, board: Board) -> Result<(), Error> {
+        if self.contains(&name) {
+            return Err(Error::NameTaken {
+                kind: "Board",
+                name,
+            });
         }
 
         self.boards.push(board);
         Ok(())
     }
 
+    /// Puts `board` in place of the one named `old_name`, which may keep or change its name.
+    pub fn replace(&mut self, old_name: &str, board: Board) -> Result<(), Error> {
+        if !same_name(board.get_name(), old_name) && self.contains(board.get_name()) {
+            return Err(Error::NameTaken {
+                kind: "Board",
+                name: board.get_name().to_string(),
+            });
+        }
+
+        match self.boards.iter_mut().find(|b| b.get_name() == old_name) {
+            Some(old) => {
+                *old = board;
+                Ok(())
+            }
+            None => Err(Error::NoSuchBoard(old_name.to_string())),
+        }
+    }
+
+    /// Saves a copy of the board at `index` under the first free "<name> copy" name.
+    pub fn duplicate(&mut self, index: usize) -> Result<String, Error> {
+        let board = self
+            .get(index)
+            .ok_or(Error::Invalid("there is no such board"))?;
+        let name = self.get_free_name(board.get_name());
+
+        let copy = board.copy_with_new_name(name.clone());
+        self.add(name.clone(), copy)?;
+        Ok(name)
+    }
+
+    /// The first of "<name> copy", "<name> copy 2", ... that no board has.
+    pub fn get_free_name(&self, name: &str) -> String {
+        (1..)
+            .map(|n| match n {
+                1 => format!("{name} copy"),
+                n => format!("{name} copy {n}"),
+            })
+            .find(|name| !self.contains(name))
+            .unwrap()
+    }
+
+    /// A two-word name like "crooked-citadel" that no board has yet.
+    pub fn generate_name(&self, rng: &mut impl Rng) -> String {
+        let mut pick = || {
+            let adjective = NAME_ADJECTIVES.choose(rng).unwrap();
+            let noun = NAME_NOUNS.choose(rng).unwrap();
+            format!("{adjective}-{noun}")
+        };
+        (0..NAME_ATTEMPTS)
+            .map(|_| pick())
+            .find(|name| !self.contains(name))
+            .unwrap_or_else(|| {
+                let name = pick();
+                self.get_free_name(&name)
+            })
+    }
+
+    /// Whether a board has `name`, not minding case or surrounding spaces.
+    pub fn contains(&self, name: &str) -> bool {
+        self.boards
+            .iter()
+            .any(|board| same_name(board.get_name(), name))
+    }
+
+    /// Reads another boards file, or a single exported board, to [`merge`](Self::merge).
+    pub fn read_file(path: &str) -> Result<Vec<Board>, Error> {
+        let json = fs::read_to_string(path).map_err(|e| Error::Io {
+            path: path.to_string(),
+            source: e,
+        })?;
+        let value: Value = serde_json::from_str(&json).map_err(|e| Error::Json(e.to_string()))?;
+
+        let boards = if value.is_array() {
+            serde_json::from_value(value)
+        } else {
+            serde_json::from_value(value).map(|board| vec![board])
+        };
+        boards.map_err(|e| Error::Json(e.to_string()))
+    }
+
+    /// Adds `board`, settling a taken name with `collision`. Returns the name it was saved
+    /// under, or `None` when it was skipped.
+    pub fn merge(&mut self, board: Board, collision: Collision) -> Option<String> {
+        let name = board.get_name().to_string();
+        if !self.contains(&name) {
+            self.boards.push(board);
+            return Some(name);
+        }
+
+        match collision {
+            Collision::Skip => None,
+            Collision::Rename => {
+                let name = self.get_free_name(&name);
+                self.boards.push(board.copy_with_new_name(name.clone()));
+                Some(name)
+            }
+            Collision::Overwrite => {
+                let old = self
+                    .boards
+                    .iter_mut()
+                    .find(|b| same_name(b.get_name(), &name))?;
+                *old = board;
+                Some(name)
+            }
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Board> {
+        (index < self.boards.len()).then(|| self.boards.remove(index))
+    }
+
+    /// Rates the board at `index`, see [`Board::set_rating`].
+    pub fn set_rating(&mut self, index: usize, rating: Option<u8>) -> Result<(), Error> {
+        self.boards
+            .get_mut(index)
+            .ok_or(Error::Invalid("there is no such board"))?
+            .set_rating(rating)
+    }
+
+    /// The indices of the boards rated `min_rating` stars or more, every board for 0. With
+    /// `best_first` the best rated boards come first and unrated ones last; boards rated
+    /// alike keep their order.
+    pub fn get_rated(&self, min_rating: u8, best_first: bool) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.boards.len())
+            .filter(|index| self.boards[*index].get_rating().unwrap_or(0) >= min_rating)
+            .collect();
+        if best_first {
+            indices.sort_by_key(|index| Reverse(self.boards[*index].get_rating()));
+        }
+        indices
+    }
+
+    /// Orders the boards at `indices` by their difficulty in stars, easiest first unless
+    /// `hardest_first`. Boards as hard as each other keep their order.
+    pub fn sort_by_difficulty(&self, indices: &mut [usize], hardest_first: bool) {
+        let stars = |index: &usize| self.boards.get(*index).map_or(0, Difficulty::get_stars);
+        if hardest_first {
+            indices.sort_by_cached_key(|index| Reverse(stars(index)));
+        } else {
+            indices.sort_by_cached_key(stars);
+        }
+    }
+
     pub fn get(&self, index: usize) -> Option<&Board> {
         self.boards.get(index)
     }
@@ -43,10 +294,202 @@ impl Boards {
     }
 }
 
+/// Names that would look alike in the board list count as the same name.
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+/// Splits the saved entries into boards and, for the rest, a name and a record for the
+/// quarantine file holding the entry and why it failed.
+fn sort_out(entries: Vec<Value>) -> (Vec<Board>, Vec<(String, Value)>) {
+    let mut boards = Vec::new();
+    let mut broken = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        match serde_json::from_value::<Board>(entry.clone()) {
+            Ok(board) => boards.push(board),
+            Err(error) => {
+                let name = match entry.get("name").and_then(Value::as_str) {
+                    Some(name) => format!("'{name}'"),
+                    None => format!("#{}", index + 1),
+                };
+                broken.push((name, json!({ "error": error.to_string(), "entry": entry })));
+            }
+        }
+    }
+    (boards, broken)
+}
+
+impl Default for Boards {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for Boards {
     fn drop(&mut self) {
-        let json = serde_json::to_string(&self.boards).unwrap();
-        let mut file = File::create(JSON_FILE_PATH).unwrap();
-        file.write_all(json.as_bytes()).unwrap();
+        store::save(JSON_FILE_PATH, &self.boards);
+    }
+}
+
+#[cfg(test)]
+mod test_boards {
+    use std::mem::ManuallyDrop;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+    use serde_json::json;
+
+    use super::{sort_out, Boards, Collision};
+    use crate::core::{store, Board, Wall};
+
+    fn boards() -> ManuallyDrop<Boards> {
+        // `ManuallyDrop` keeps the tests from writing boards.json.
+        ManuallyDrop::new(Boards {
+            boards: vec![
+                Board::new("maze".to_string(), 10, vec![]),
+                Board::new("open".to_string(), 10, vec![]),
+            ],
+        })
+    }
+
+    #[test]
+    fn duplicate_picks_a_free_name() {
+        let mut boards = boards();
+        assert_eq!(boards.duplicate(0), Ok("maze copy".to_string()));
+        assert_eq!(boards.duplicate(0), Ok("maze copy 2".to_string()));
+        assert!(boards.duplicate(9).is_err());
+        assert_eq!(
+            boards.get_names(),
+            ["maze", "open", "maze copy", "maze copy 2"]
+        );
+    }
+
+    #[test]
+    fn broken_entries_are_sorted_out() {
+        let good = serde_json::to_value(Board::new("maze".to_string(), 10, vec![])).unwrap();
+        let entries = vec![
+            json!({ "name": "half", "table_size": "big" }),
+            good,
+            json!(42),
+        ];
+
+        let (boards, broken) = sort_out(entries);
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].get_name(), "maze");
+        let names: Vec<_> = broken.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["'half'", "#3"]);
+        assert_eq!(broken[1].1["entry"], json!(42));
+        assert!(broken[0].1["error"].is_string());
+    }
+
+    #[test]
+    fn merge_settles_collisions() {
+        let mut boards = boards();
+        let imported = |name: &str| Board::new(name.to_string(), 20, vec![]);
+
+        assert_eq!(
+            boards.merge(imported("new"), Collision::Skip),
+            Some("new".to_string())
+        );
+        assert_eq!(boards.merge(imported("maze"), Collision::Skip), None);
+        assert_eq!(boards.get(0).unwrap().get_size(), 10);
+        assert_eq!(
+            boards.merge(imported("maze"), Collision::Rename),
+            Some("maze copy".to_string())
+        );
+        assert_eq!(
+            boards.merge(imported("open"), Collision::Overwrite),
+            Some("open".to_string())
+        );
+        assert_eq!(boards.get(1).unwrap().get_size(), 20);
+        assert_eq!(boards.get_names(), ["maze", "open", "new", "maze copy"]);
+    }
+
+    #[test]
+    fn remove() {
+        let mut boards = boards();
+        assert_eq!(boards.remove(0).unwrap().get_name(), "maze");
+        assert!(boards.remove(1).is_none());
+        assert_eq!(boards.get_names(), ["open"]);
+    }
+
+    #[test]
+    fn replace_keeps_names_unique() {
+        let mut boards = boards();
+        let edited = Board::new("maze".to_string(), 12, vec![]);
+        assert!(boards.replace("maze", edited).is_ok());
+        assert_eq!(boards.get(0).unwrap().get_size(), 12);
+
+        let renamed = Board::new("open".to_string(), 12, vec![]);
+        assert!(boards.replace("maze", renamed).is_err());
+        assert!(boards.replace("gone", Board::default()).is_err());
+
+        let recased = Board::new("Maze".to_string(), 12, vec![]);
+        assert!(boards.replace("maze", recased).is_ok());
+        assert!(boards.add(" OPEN ".to_string(), Board::default()).is_err());
+    }
+
+    #[test]
+    fn ratings_sort_and_filter() {
+        let mut boards = boards();
+        boards
+            .add(
+                "spiral".to_string(),
+                Board::new("spiral".to_string(), 10, vec![]),
+            )
+            .unwrap();
+        assert!(boards.set_rating(0, Some(6)).is_err());
+        boards.set_rating(1, Some(2)).unwrap();
+        boards.set_rating(2, Some(5)).unwrap();
+
+        assert_eq!(boards.get_rated(0, false), [0, 1, 2]);
+        assert_eq!(boards.get_rated(0, true), [2, 1, 0]);
+        assert_eq!(boards.get_rated(3, true), [2]);
+
+        boards.set_rating(2, None).unwrap();
+        assert_eq!(boards.get_rated(1, false), [1]);
+        let json = serde_json::to_value(boards.get(2).unwrap()).unwrap();
+        assert!(json.get("rating").is_none());
+    }
+
+    #[test]
+    fn difficulty_sorts() {
+        let mut boards = boards();
+        let walls = (0..10).flat_map(|y| [Wall::new(3, y), Wall::new(6, y)]);
+        boards
+            .add(
+                "rows".to_string(),
+                Board::new("rows".to_string(), 10, walls.collect()),
+            )
+            .unwrap();
+
+        let mut indices = vec![0, 1, 2];
+        boards.sort_by_difficulty(&mut indices, true);
+        assert_eq!(indices, [2, 0, 1]);
+        boards.sort_by_difficulty(&mut indices, false);
+        assert_eq!(indices, [0, 1, 2]);
+    }
+
+    #[test]
+    fn generated_names_are_free() {
+        let mut boards = boards();
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        for _ in 0..50 {
+            let name = boards.generate_name(&mut rng);
+            assert_eq!(name.split('-').count(), 2, "{name}");
+            assert!(!boards.contains(&name));
+            boards
+                .add(name.clone(), Board::new(name, 10, vec![]))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn a_corrupt_file_falls_back_to_the_default_board() {
+        let path = store::test_file("boards.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing boards.json.
+        let boards = ManuallyDrop::new(Boards::load(&path));
+        assert_eq!(boards.boards.len(), 1);
+        assert!(store::is_unreadable(&path));
     }
 }
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{store, Settings};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/campaign.json";
+/// The score a level needs when its pack does not say.
+pub const DEFAULT_CLEAR_SCORE: u16 = 10;
+
+/// Where a board installed from a pack stands in the pack's unlock order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignLevel {
+    pack: String,
+    /// 1 for the first board, which is never locked.
+    level: u16,
+    /// The score a game on the board needs to clear it.
+    clear_score: u16,
+}
+
+impl CampaignLevel {
+    pub fn new(pack: String, level: u16, clear_score: u16) -> Self {
+        Self {
+            pack,
+            level: level.max(1),
+            clear_score,
+        }
+    }
+
+    pub fn get_pack(&self) -> &str {
+        &self.pack
+    }
+
+    pub fn get_level(&self) -> u16 {
+        self.level
+    }
+
+    pub fn get_clear_score(&self) -> u16 {
+        self.clear_score
+    }
+
+    /// Whether `other` comes right before this level in the same pack.
+    pub fn is_after(&self, other: &CampaignLevel) -> bool {
+        self.pack == other.pack && self.level == other.level + 1
+    }
+}
+
+/// How far each profile got through each pack, as the last level cleared in order.
+#[derive(Debug)]
+pub struct CampaignProgress {
+    /// By profile name, "" for the default settings, then by pack name.
+    cleared: HashMap<String, HashMap<String, u16>>,
+}
+
+impl CampaignProgress {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        let cleared = store::load(path, HashMap::new);
+        Self { cleared }
+    }
+
+    /// The last level of `pack` the current profile cleared, 0 for none.
+    pub fn get_cleared(&self, pack: &str) -> u16 {
+        self.cleared
+            .get(&profile())
+            .and_then(|packs| packs.get(pack))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Whether the current profile cleared the level before `level`.
+    pub fn is_unlocked(&self, level: &CampaignLevel) -> bool {
+        level.level <= self.get_cleared(&level.pack) + 1
+    }
+
+    /// Records a game scoring `score` on an unlocked `level`, returning whether it cleared the
+    /// level for the first time.
+    pub fn record(&mut self, level: &CampaignLevel, score: u16) -> bool {
+        let cleared = self.get_cleared(&level.pack);
+        if score < level.clear_score || level.level != cleared + 1 {
+            return false;
+        }
+        self.cleared
+            .entry(profile())
+            .or_default()
+            .insert(level.pack.clone(), level.level);
+        true
+    }
+}
+
+impl Default for CampaignProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CampaignProgress {
+    fn drop(&mut self) {
+        store::save(JSON_FILE_PATH, &self.cleared);
+    }
+}
+
+fn profile() -> String {
+    Settings::get_profile().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test_campaign {
+    use std::{collections::HashMap, mem::ManuallyDrop};
+
+    use super::{CampaignLevel, CampaignProgress};
+    use crate::core::store;
+
+    #[test]
+    fn levels_unlock_in_order() {
+        // `ManuallyDrop` keeps the test from writing campaign.json.
+        let mut progress = ManuallyDrop::new(CampaignProgress {
+            cleared: HashMap::new(),
+        });
+        let first = CampaignLevel::new("caves".to_string(), 1, 5);
+        let second = CampaignLevel::new("caves".to_string(), 2, 10);
+        let third = CampaignLevel::new("caves".to_string(), 3, 10);
+        assert!(second.is_after(&first));
+        assert!(!third.is_after(&first));
+
+        assert!(progress.is_unlocked(&first));
+        assert!(!progress.is_unlocked(&second));
+        assert!(!progress.record(&first, 4));
+        assert!(!progress.record(&second, 20));
+        assert!(progress.record(&first, 5));
+        assert!(!progress.record(&first, 8));
+
+        assert!(progress.is_unlocked(&second));
+        assert!(!progress.is_unlocked(&third));
+        assert_eq!(progress.get_cleared("caves"), 1);
+        assert_eq!(progress.get_cleared("forest"), 0);
+    }
+
+    #[test]
+    fn a_corrupt_file_starts_empty() {
+        let path = store::test_file("campaign.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing campaign.json.
+        let campaign = ManuallyDrop::new(CampaignProgress::load(&path));
+        assert!(campaign.cleared.is_empty());
+        assert!(store::is_unreadable(&path));
+    }
+}
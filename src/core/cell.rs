@@ -0,0 +1,58 @@
+use super::WallColor;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CellKind {
+    Empty,
+    Border,
+    Wall,
+    Food,
+    Snake,
+    SnakeHead,
+    RivalSnake,
+    RivalHead,
+    /// A wall about to move, or the cell it is moving to.
+    Shifting,
+    /// A cell that becomes the respawn point once the snake touches it.
+    Checkpoint,
+    /// A closed gate, opened by eating enough apples in the stage before it.
+    Gate,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cell {
+    kind: CellKind,
+    glyph: String,
+    /// A color the board asks for, drawn over the theme's.
+    tint: Option<WallColor>,
+}
+
+impl Cell {
+    pub fn new(kind: CellKind, glyph: &str) -> Self {
+        Self {
+            kind,
+            glyph: glyph.to_string(),
+            tint: None,
+        }
+    }
+
+    pub fn with_tint(mut self, tint: Option<WallColor>) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn empty() -> Self {
+        Self::new(CellKind::Empty, " ")
+    }
+
+    pub fn get_kind(&self) -> CellKind {
+        self.kind
+    }
+
+    pub fn get_glyph(&self) -> &str {
+        &self.glyph
+    }
+
+    pub fn get_tint(&self) -> Option<WallColor> {
+        self.tint
+    }
+}
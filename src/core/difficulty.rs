@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Direction, Point};
+
+/// A rough guess at how hard a board plays, from how much of it is walled off and how
+/// little room is left to turn.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+/// Boards smaller than this count as a step harder.
+const CRAMPED_SIZE: u16 = 12;
+/// The speed a board is played at, in percent.
+pub const NORMAL_SPEED: u16 = 100;
+/// How much one press of `+` or `-` changes the speed, in percent.
+pub const SPEED_STEP: u16 = 10;
+/// The most stars a board's difficulty is shown with.
+pub const MAX_DIFFICULTY: u8 = 5;
+/// The score a board needs for each star after the first.
+const STAR_SCORES: [f64; 4] = [0.05, 0.1, 0.15, 0.25];
+
+/// What a board's difficulty is judged from, measured over its free cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardFeatures {
+    wall_density: f64,
+    /// The share of free cells with two free neighbors or fewer, inside a corridor one cell wide.
+    corridors: f64,
+    /// Free cells with a single free neighbor, the ends of a corridor.
+    dead_ends: usize,
+    /// The share of free cells whose eight neighbors are all free.
+    open_area: f64,
+}
+
+impl BoardFeatures {
+    pub fn measure(board: &Board) -> Self {
+        let size = board.get_size();
+        let free: Vec<Point> = (0..size as i16)
+            .flat_map(|x| (0..size as i16).map(move |y| Point::new(x, y)))
+            .filter(|point| !board.is_wall(point))
+            .collect();
+        let is_free = |from: &Point, to: &Point| {
+            let wrapped =
+                from.get_x().abs_diff(to.get_x()) > 1 || from.get_y().abs_diff(to.get_y()) > 1;
+            !board.is_wall(to) && (!wrapped || board.get_rules().is_wrap())
+        };
+
+        let (mut corridors, mut dead_ends, mut open) = (0, 0, 0);
+        for point in &free {
+            let sides = [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .map(|direction| point.get_neighbor(&direction, size));
+            let free_sides = sides.iter().filter(|side| is_free(point, side)).count();
+            corridors += (free_sides <= 2) as usize;
+            dead_ends += (free_sides == 1) as usize;
+
+            let corners = [
+                (&sides[0], Direction::Left),
+                (&sides[0], Direction::Right),
+                (&sides[1], Direction::Left),
+                (&sides[1], Direction::Right),
+            ]
+            .iter()
+            .all(|(side, direction)| {
+                let corner = side.get_neighbor(direction, size);
+                is_free(side, &corner)
+            });
+            open += (free_sides == 4 && corners) as usize;
+        }
+
+        let share = |count: usize| match free.len() {
+            0 => 0.0,
+            len => count as f64 / len as f64,
+        };
+        Self {
+            wall_density: board.get_wall_density(),
+            corridors: share(corridors),
+            dead_ends,
+            open_area: share(open),
+        }
+    }
+
+    pub fn get_wall_density(&self) -> f64 {
+        self.wall_density
+    }
+
+    pub fn get_corridors(&self) -> f64 {
+        self.corridors
+    }
+
+    pub fn get_dead_ends(&self) -> usize {
+        self.dead_ends
+    }
+
+    pub fn get_open_area(&self) -> f64 {
+        self.open_area
+    }
+}
+
+impl Difficulty {
+    pub fn estimate(board: &Board) -> Self {
+        match Self::get_stars(board) {
+            1 => Self::Easy,
+            2 | 3 => Self::Medium,
+            _ => Self::Hard,
+        }
+    }
+
+    /// How hard the board plays, 1 to `MAX_DIFFICULTY` stars. The walls weigh the most;
+    /// narrow corridors, dead ends and little open room add to them, as does a small board.
+    pub fn get_stars(board: &Board) -> u8 {
+        let features = BoardFeatures::measure(board);
+        let free_cells = (board.get_size() as f64).powi(2) * (1.0 - features.wall_density);
+        let dead_ends = match free_cells {
+            0.0 => 0.0,
+            cells => (features.dead_ends as f64 / cells * 20.0).min(1.0),
+        };
+        let cramped = if board.get_size() < CRAMPED_SIZE {
+            0.05
+        } else {
+            0.0
+        };
+
+        let score = features.wall_density
+            + cramped
+            + 0.1 * features.corridors
+            + 0.05 * dead_ends
+            + 0.05 * (1.0 - features.open_area);
+        1 + STAR_SCORES
+            .iter()
+            .filter(|needed| score >= **needed)
+            .count() as u8
+    }
+
+    /// Roaming snakes on boards of this difficulty, when they are turned on.
+    pub fn get_hazards(&self) -> u8 {
+        match self {
+            Self::Easy => 1,
+            Self::Medium => 2,
+            Self::Hard => 3,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Medium => "Medium",
+            Self::Hard => "Hard",
+        }
+    }
+
+    /// The slowest and fastest the speed may be set during a game, in percent of the board's.
+    /// Easy boards leave the most room either way; hard ones stay close to how they are meant
+    /// to play.
+    pub fn get_speed_bounds(&self) -> (u16, u16) {
+        match self {
+            Self::Easy => (50, 200),
+            Self::Medium => (70, 150),
+            Self::Hard => (80, 130),
+        }
+    }
+
+    /// `speed` a step faster or slower, kept within the bounds.
+    pub fn nudge_speed(&self, speed: u16, faster: bool) -> u16 {
+        let (slowest, fastest) = self.get_speed_bounds();
+        let speed = match faster {
+            true => speed.saturating_add(SPEED_STEP),
+            false => speed.saturating_sub(SPEED_STEP),
+        };
+        speed.clamp(slowest, fastest)
+    }
+}
+
+#[cfg(test)]
+mod test_difficulty {
+    use super::{BoardFeatures, Difficulty, NORMAL_SPEED};
+    use crate::core::{Board, Wall};
+
+    #[test]
+    fn more_walls_are_harder() {
+        let open = Board::new("open".to_string(), 20, vec![Wall::new(1, 1)]);
+        assert_eq!(Difficulty::estimate(&open), Difficulty::Easy);
+
+        let walls = (0..20).map(|y| Wall::new(10, y)).collect();
+        let split = Board::new("split".to_string(), 20, walls);
+        assert_eq!(Difficulty::estimate(&split), Difficulty::Medium);
+
+        let walls = (0..20)
+            .flat_map(|y| [Wall::new(5, y), Wall::new(10, y), Wall::new(15, y)])
+            .collect();
+        let maze = Board::new("maze".to_string(), 20, walls);
+        assert_eq!(Difficulty::estimate(&maze), Difficulty::Hard);
+
+        let small = Board::new("small".to_string(), 8, vec![Wall::new(1, 1)]);
+        assert_eq!(Difficulty::estimate(&small), Difficulty::Medium);
+
+        assert_eq!(Difficulty::get_stars(&open), 1);
+        assert!(Difficulty::get_stars(&maze) > Difficulty::get_stars(&split));
+    }
+
+    #[test]
+    fn speed_stays_within_bounds() {
+        let hard = Difficulty::Hard;
+        let mut speed = NORMAL_SPEED;
+        for _ in 0..10 {
+            speed = hard.nudge_speed(speed, true);
+        }
+        assert_eq!(speed, 130);
+        speed = hard.nudge_speed(speed, false);
+        assert_eq!(speed, 120);
+
+        let mut speed = NORMAL_SPEED;
+        for _ in 0..10 {
+            speed = Difficulty::Easy.nudge_speed(speed, false);
+        }
+        assert_eq!(speed, 50);
+    }
+
+    #[test]
+    fn corridors_and_dead_ends_count() {
+        let open = BoardFeatures::measure(&Board::new("open".to_string(), 10, Vec::new()));
+        assert_eq!(open.get_corridors(), 0.0);
+        assert_eq!(open.get_dead_ends(), 0);
+        assert_eq!(open.get_open_area(), 1.0);
+
+        // Walls on every other row leave corridors one cell wide, and a wall across the
+        // first corridor makes both its cells next to it dead ends.
+        let mut walls: Vec<Wall> = (0..10)
+            .step_by(2)
+            .flat_map(|x| (0..10).map(move |y| Wall::new(x, y)))
+            .collect();
+        walls.push(Wall::new(1, 5));
+        let corridors = Board::new("corridors".to_string(), 10, walls.clone());
+        let features = BoardFeatures::measure(&corridors);
+        assert_eq!(features.get_corridors(), 1.0);
+        assert_eq!(features.get_dead_ends(), 2);
+        assert_eq!(features.get_open_area(), 0.0);
+        assert_eq!(Difficulty::get_stars(&corridors), 5);
+
+        walls.truncate(10);
+        let few = Board::new("few".to_string(), 10, walls);
+        assert!(Difficulty::get_stars(&few) < Difficulty::get_stars(&corridors));
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
@@ -17,6 +19,27 @@ impl Direction {
                 | (Direction::Right, Direction::Left)
         )
     }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::None => Direction::None,
+        }
+    }
+
+    /// The next heading turning clockwise.
+    pub fn clockwise(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::None => Direction::None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -56,4 +79,18 @@ mod test_direction {
         assert!(!down.is_opposite(&right));
         assert!(!down.is_opposite(&none));
     }
+
+    #[test]
+    fn opposite_and_clockwise() {
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            assert!(direction.is_opposite(&direction.opposite()));
+            assert_eq!(direction.clockwise().clockwise(), direction.opposite());
+        }
+        assert_eq!(Direction::Up.clockwise(), Direction::Right);
+    }
 }
@@ -0,0 +1,78 @@
+use std::{fmt, io};
+
+use super::Point;
+
+/// Why a core operation failed, for callers that handle some failures differently.
+#[derive(Debug)]
+pub enum Error {
+    /// A file could not be read or written.
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    /// The data is not what it should be, such as a boards file that is not valid JSON.
+    Json(String),
+    UnsupportedReplayVersion(u64),
+    NotAReplay,
+    /// A board or prefab with the name already exists.
+    NameTaken {
+        kind: &'static str,
+        name: String,
+    },
+    NoSuchBoard(String),
+    OutOfRange(Point),
+    /// A move that breaks the rules of the board or the game, such as an apple on a wall.
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{path}: {source}"),
+            Self::Json(message) => write!(f, "invalid data: {message}"),
+            Self::UnsupportedReplayVersion(version) => {
+                write!(f, "unsupported replay version {version}")
+            }
+            Self::NotAReplay => write!(f, "not a replay file"),
+            Self::NameTaken { kind, name } => write!(f, "{kind} '{name}' already exists"),
+            Self::NoSuchBoard(name) => write!(f, "there is no board named '{name}'"),
+            Self::OutOfRange(point) => write!(
+                f,
+                "the point ({}, {}) is out of range",
+                point.get_x(),
+                point.get_y()
+            ),
+            Self::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// File errors are the same when they are of the same kind on the same file.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Io { path, source }, Self::Io { path: p, source: s }) => {
+                path == p && source.kind() == s.kind()
+            }
+            (Self::Json(a), Self::Json(b)) => a == b,
+            (Self::UnsupportedReplayVersion(a), Self::UnsupportedReplayVersion(b)) => a == b,
+            (Self::NotAReplay, Self::NotAReplay) => true,
+            (Self::NameTaken { kind, name }, Self::NameTaken { kind: k, name: n }) => {
+                kind == k && name == n
+            }
+            (Self::NoSuchBoard(a), Self::NoSuchBoard(b)) => a == b,
+            (Self::OutOfRange(a), Self::OutOfRange(b)) => a == b,
+            (Self::Invalid(a), Self::Invalid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
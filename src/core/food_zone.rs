@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use super::Point;
+
+/// A rectangle of the board where food may appear, picked in proportion to its weight.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FoodZone {
+    from: Point,
+    to: Point,
+    weight: u16,
+}
+
+impl FoodZone {
+    /// The zone spanned by two opposite corners.
+    pub fn new(from: &Point, to: &Point, weight: u16) -> Self {
+        Self {
+            from: Point::new(from.get_x().min(to.get_x()), from.get_y().min(to.get_y())),
+            to: Point::new(from.get_x().max(to.get_x()), from.get_y().max(to.get_y())),
+            weight,
+        }
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.from.get_x()..=self.to.get_x()).contains(&point.get_x())
+            && (self.from.get_y()..=self.to.get_y()).contains(&point.get_y())
+    }
+
+    pub fn get_weight(&self) -> u16 {
+        self.weight
+    }
+}
@@ -1,108 +1,752 @@
-use rand::Rng;
-use std::collections::LinkedList;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{LinkedList, VecDeque},
+};
 
-use super::{point::Point, Board, Direction};
+use super::{
+    board::to_table,
+    cell::{Cell, CellKind},
+    point::Point,
+    Board, BoardRules, Difficulty, Direction, Error, Gate, Objective, Replay, ReplayInput, Rules,
+    TimedInput, Wall,
+};
 
-type Snake = LinkedList<Point>;
+pub(super) type Snake = LinkedList<Point>;
 type Food = Point;
 
+pub const FOOD_SCORE: u16 = 1;
+const MAX_PENDING_TURNS: usize = 2;
+const MAX_BOOST_ENERGY: u16 = 40;
+const BOOST_DRAIN_TICKS: u16 = 5;
+const SHIFTING_WALLS: usize = 3;
+const SHIFT_WARNING_TICKS: u16 = 15;
+/// Ticks between two drifts of moving food.
+const FOOD_DRIFT_TICKS: u32 = 6;
+/// One apple in this many brings out a mouse, when mice are on.
+const MOUSE_CHANCE: u32 = 8;
+const MOUSE_SCORE: u16 = 5;
+/// Ticks a mouse stays before it escapes.
+const MOUSE_TICKS: u16 = 60;
+/// A mouse moves every this many ticks, so the snake can catch up.
+const MOUSE_MOVE_TICKS: u16 = 2;
+/// How long a roaming snake grows.
+const HAZARD_LENGTH: usize = 3;
+/// A roaming snake moves every this many ticks.
+const HAZARD_MOVE_TICKS: u32 = 2;
+/// One move in this many a roaming snake turns even when it could go straight on.
+const HAZARD_TURN_CHANCE: u32 = 4;
+/// Roaming snakes start at least this far from the head.
+const HAZARD_SAFE_DISTANCE: u16 = 5;
+/// A maze game grows a wall every time the score passes another multiple of this.
+const MAZE_SCORE_STEP: u16 = 3;
+/// Walls grown in a maze game appear at least this far from the head.
+const MAZE_SAFE_DISTANCE: u16 = 3;
+/// The lives of a game on a board with checkpoints; each respawn costs one.
+pub const CHECKPOINT_LIVES: u16 = 3;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum GameEvent {
+    Ate {
+        at: Point,
+        points: u16,
+    },
+    /// A move into a wall or the body was skipped because the snake cannot die.
+    Bumped,
+    /// The snake touched a checkpoint, which is now where it respawns.
+    Checkpoint {
+        at: Point,
+    },
+    /// The snake died and came back at its last checkpoint, losing a life.
+    Respawned,
+    /// A stage's apples were eaten and its gate opened on these cells.
+    GateOpened {
+        cells: Vec<Point>,
+    },
+}
+
+/// What ended a game.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeathCause {
+    Wall,
+    /// Crossed an edge of a board that does not wrap.
+    Edge,
+    Body,
+    RoamingSnake,
+    /// A puzzle ran out of moves with apples left.
+    OutOfMoves,
+    /// An objective's timer ran out with apples left.
+    OutOfTime,
+    /// The player left before the game ended.
+    GaveUp,
+}
+
+impl DeathCause {
+    pub fn get_name(&self) -> &str {
+        match self {
+            DeathCause::Wall => "wall",
+            DeathCause::Edge => "edge",
+            DeathCause::Body => "own body",
+            DeathCause::RoamingSnake => "roaming snake",
+            DeathCause::OutOfMoves => "out of moves",
+            DeathCause::OutOfTime => "out of time",
+            DeathCause::GaveUp => "gave up",
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Game<'a> {
-    board: &'a Board,
+    /// Borrowed until shifting walls first moves one.
+    board: Cow<'a, Board>,
+    /// The board as the game started, kept for replays.
+    origin: &'a Board,
+    seed: u64,
+    /// What `StdRng` is built on, used directly so its state can be saved.
+    rng: ChaCha12Rng,
+    length: u16,
+    food_count: usize,
+    tick: u32,
+    inputs: Vec<TimedInput>,
+    spawn: Snake,
     snake: Snake,
-    food: Food,
+    food: Vec<Food>,
     score: u16,
     direction: Direction,
+    turned: bool,
+    pending_turns: VecDeque<Direction>,
+    boost: bool,
+    boost_energy: u16,
+    boost_ticks: u16,
+    no_death: bool,
+    /// The relaxed mode, where the snake turns away from whatever it would run into.
+    zen: bool,
+    hardcore: bool,
+    /// Set once the player changed the speed during the game.
+    speed_adjusted: bool,
+    /// Apples drift a cell every `FOOD_DRIFT_TICKS`.
+    moving_food: bool,
+    /// Eating an apple now and then brings out a mouse that runs from the snake.
+    mice: bool,
+    /// The mouse and the ticks left before it escapes.
+    mouse: Option<(Point, u16)>,
+    /// Small snakes wandering the board that kill on contact, with their heading.
+    hazards: Vec<(Snake, Direction)>,
+    lives: u16,
+    /// The last checkpoint touched and the heading the snake had there.
+    checkpoint: Option<(Point, Direction)>,
+    shift_every: u16,
+    apples: u16,
+    /// Walls about to move, as `(from, to)`, and the ticks left before they do.
+    shift: Vec<(Wall, Wall)>,
+    shift_countdown: u16,
+    moves: u16,
+    /// Set on puzzle boards, where the fixed apples must be eaten within this many moves.
+    max_moves: Option<u16>,
+    /// The snake length that ends a race.
+    target_length: Option<u16>,
+    /// The pellets left to clear, set when the game is about clearing them.
+    pellets: Option<Vec<Point>>,
+    /// The walls grown so far, set when the game is a maze growing with the score.
+    grown_walls: Option<u16>,
+    events: Vec<GameEvent>,
+    /// Set once `walk` reports the end of the game.
+    over: bool,
+    /// Set when the snake took up the board and no apple could be put down any more.
+    filled: bool,
+    /// The cells of the board alone, drawn again only when its walls move.
+    background: Vec<Vec<Cell>>,
+    /// What killed the snake last and where, cleared when it respawns.
+    death: Option<(DeathCause, Point)>,
 }
 
 impl<'a> Game<'a> {
     pub fn new(board: &'a Board, length: u16) -> Self {
-        let snake = Self::create_snake(board.get_size(), length);
-        let food = Self::find_lunch_point(&snake, board);
+        Self::with_seed(board, length, rand::random())
+    }
 
-        Self {
+    /// A game whose food and walls move the same way every time for the same `seed`.
+    pub fn with_seed(board: &'a Board, length: u16, seed: u64) -> Self {
+        let rng = ChaCha12Rng::seed_from_u64(seed);
+        let (snake, direction) = match board.get_spawn() {
+            Some((head, direction)) => (
+                Self::spawn_snake(board, head, *direction, length),
+                *direction,
+            ),
+            None => (
+                Self::create_snake(board.get_size(), length),
+                Direction::Right,
+            ),
+        };
+        let (food, max_moves) = match board.get_puzzle() {
+            Some(puzzle) => (puzzle.get_food().to_vec(), Some(puzzle.get_max_moves())),
+            None => (Vec::new(), None),
+        };
+
+        let mut game = Self {
             food,
+            spawn: snake.clone(),
             snake,
             score: 0,
-            direction: Direction::Right,
-            board,
+            direction,
+            turned: false,
+            pending_turns: VecDeque::new(),
+            boost: false,
+            boost_energy: MAX_BOOST_ENERGY,
+            boost_ticks: 0,
+            no_death: false,
+            zen: false,
+            hardcore: false,
+            speed_adjusted: false,
+            lives: match board.get_checkpoints().is_empty() {
+                true => 0,
+                false => CHECKPOINT_LIVES,
+            },
+            checkpoint: None,
+            moving_food: false,
+            mice: false,
+            mouse: None,
+            hazards: Vec::new(),
+            shift_every: 0,
+            apples: 0,
+            shift: Vec::new(),
+            shift_countdown: 0,
+            moves: 0,
+            max_moves,
+            target_length: None,
+            pellets: None,
+            grown_walls: None,
+            board: Cow::Borrowed(board),
+            origin: board,
+            seed,
+            rng,
+            length,
+            food_count: 1,
+            tick: 0,
+            inputs: Vec::new(),
+            events: Vec::new(),
+            over: false,
+            filled: false,
+            background: board.get_cells(),
+            death: None,
+        };
+        if game.max_moves.is_none() {
+            let food = game.find_lunch_point();
+            game.food.extend(food);
         }
+        game
     }
 
+    /// Turns the snake; a second turn in the same tick is queued for the following ticks.
     pub fn rotation(&mut self, direction: Direction) {
-        if !self.direction.is_opposite(&direction) {
-            self.direction = direction;
-        }
+        self.record(ReplayInput::Turn(direction));
+        self.turn(direction);
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Whether the next step as the snake heads now would hit a wall, a snake or the edge.
+    pub fn is_danger_ahead(&self) -> bool {
+        let Some(head) = self.snake.front() else {
+            return false;
+        };
+        let next = head.get_neighbor(&self.direction, self.board.get_size());
+        !Self::is_free(&self.snake, &self.board, &next)
+            || self.is_hazard(&next)
+            || self.leaves_board(head, &next)
     }
 
     pub fn get_score(&self) -> u16 {
         self.score
     }
 
+    /// The last cell of the snake's body.
+    pub fn get_tail(&self) -> Option<&Point> {
+        self.snake.back()
+    }
+
+    pub fn set_boost(&mut self, boost: bool) {
+        if boost != self.boost {
+            self.record(ReplayInput::Boost(boost));
+        }
+        self.boost = boost;
+    }
+
+    /// Steps taken so far.
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Everything needed to play this game again from the start up to now.
+    pub fn get_replay(&self) -> Replay {
+        let rules = Rules {
+            length: self.length,
+            apples: self.food_count as u8,
+            shifting_walls: self.shift_every as u8,
+            target_length: self.target_length,
+            pellets: self.pellets.is_some(),
+            maze: self.grown_walls.is_some(),
+            hardcore: self.hardcore,
+            moving_food: self.moving_food,
+            mice: self.mice,
+            hazards: self.hazards.len() as u8,
+            speed_adjusted: self.speed_adjusted,
+            difficulty: Some(Difficulty::estimate(self.origin)),
+        };
+        Replay::new(
+            self.origin.clone(),
+            self.seed,
+            rules,
+            self.inputs.clone(),
+            self.tick,
+            self.score,
+        )
+    }
+
+    pub fn is_boosting(&self) -> bool {
+        self.boost && self.boost_energy > 0
+    }
+
+    /// Remaining boost energy between 0.0 and 1.0.
+    pub fn get_boost_gauge(&self) -> f64 {
+        self.boost_energy as f64 / MAX_BOOST_ENERGY as f64
+    }
+
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Moves one cell, returning whether the game goes on: false once the snake dies, a
+    /// puzzle is cleared or out of moves, a race reaches its target length, an objective is
+    /// met or the snake has filled the board.
     pub fn walk(&mut self) -> bool {
-        let head = self.snake.front().unwrap();
+        let alive = self.step() || self.respawn();
+        if alive {
+            self.touch_checkpoint();
+            self.eat_pellet();
+            self.grow_walls();
+        }
+        self.update_boost();
+        self.update_shift();
+        self.update_food();
+        // A snake of two cells has none left once it dies, and the mouse runs from the head.
+        if alive {
+            self.update_mouse();
+        }
+        self.update_hazards();
+        self.tick += 1;
 
-        let new_head = head.get_neighbor(&self.direction, self.board.get_size());
+        self.turned = false;
+        if let Some(direction) = self.pending_turns.pop_front() {
+            self.turn(direction);
+        }
 
-        let collides_with_walls = self.board.is_wall(&new_head);
-        let collides_with_body = Self::is_snake(&self.snake, &new_head);
+        if alive && !self.is_puzzle_cleared() && self.get_moves_left() == Some(0) {
+            self.death = Some((DeathCause::OutOfMoves, Self::get_head(&self.snake)));
+        }
+        let going_on = alive
+            && !self.is_puzzle_cleared()
+            && self.get_moves_left() != Some(0)
+            && !self.is_target_reached()
+            && !self.is_pellets_cleared()
+            && !self.is_objective_met()
+            && !self.filled;
+        self.over |= !going_on;
+        going_on
+    }
 
-        if collides_with_body || collides_with_walls {
-            self.snake.pop_back();
-            self.snake.pop_front();
+    /// Whether a step has ended the game, by death or by reaching its goal.
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
 
-            false
-        } else if new_head == self.food {
-            self.snake.push_front(new_head);
-            self.score += 1;
-            self.food = Self::find_lunch_point(&self.snake, self.board);
+    /// Whether the snake ate the last apple there was room for, which wins the game.
+    pub fn is_board_filled(&self) -> bool {
+        self.filled
+    }
 
-            true
-        } else {
-            self.snake.push_front(new_head);
-            self.snake.pop_back();
+    /// What killed the snake and the cell it ran into, once it has died for good.
+    pub fn get_death(&self) -> Option<&(DeathCause, Point)> {
+        self.death.as_ref()
+    }
 
-            true
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_moves(&self) -> u16 {
+        self.moves
+    }
+
+    pub fn get_moves_left(&self) -> Option<u16> {
+        self.max_moves
+            .map(|max_moves| max_moves.saturating_sub(self.moves))
+    }
+
+    pub fn get_food_left(&self) -> usize {
+        self.food.len()
+    }
+
+    pub fn is_puzzle_cleared(&self) -> bool {
+        self.max_moves.is_some() && self.food.is_empty()
+    }
+
+    pub fn get_objective(&self) -> Option<&Objective> {
+        self.board.get_objective()
+    }
+
+    /// The apples eaten so far this game.
+    pub fn get_apples_eaten(&self) -> u16 {
+        self.apples
+    }
+
+    pub fn is_objective_met(&self) -> bool {
+        self.get_objective()
+            .is_some_and(|objective| self.apples >= objective.get_apples())
+    }
+
+    /// Ends a game on an objective board whose timer ran out, unless it is already over.
+    pub fn run_out_of_time(&mut self) {
+        if self.over || self.get_objective().is_none() {
+            return;
         }
+        self.death = Some((DeathCause::OutOfTime, Self::get_head(&self.snake)));
+        self.over = true;
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.snake.len() as u16
+    }
+
+    pub fn get_target_length(&self) -> Option<u16> {
+        self.target_length
+    }
+
+    /// Turns the game into a race that ends once the snake is `target` cells long.
+    pub fn set_target_length(&mut self, target: u16) {
+        self.target_length = Some(target);
+    }
+
+    pub fn is_target_reached(&self) -> bool {
+        self.target_length
+            .is_some_and(|target| self.get_length() >= target)
+    }
+
+    /// Covers every free cell with pellets and takes the apples away; the game ends once the
+    /// head has been over every pellet. Pellets do not make the snake grow.
+    pub fn start_pellets(&mut self) {
+        self.food.clear();
+        self.pellets = Some(Self::free_cells(&self.snake, &self.board, &[]));
+    }
+
+    /// The pellets left to clear, `None` when the game is not about pellets.
+    pub fn get_pellets_left(&self) -> Option<usize> {
+        self.pellets.as_ref().map(Vec::len)
+    }
+
+    pub fn is_pellets_cleared(&self) -> bool {
+        self.pellets.as_ref().is_some_and(Vec::is_empty)
+    }
+
+    /// Turns the board into a maze over the game: every `MAZE_SCORE_STEP` points a wall grows
+    /// on a free cell away from the head, never one that would close off part of the board.
+    pub fn start_maze(&mut self) {
+        self.grown_walls = Some(0);
+    }
+
+    /// The apples eaten in the current stage and the ones that open its gate, `None` once
+    /// every gate is open.
+    pub fn get_gate_progress(&self) -> Option<(u16, u16)> {
+        let gates = self.origin.get_gates();
+        let opened = gates.len() - self.board.get_gates().len();
+        let gate = gates.get(opened)?;
+        let stage_start: u16 = gates[..opened].iter().map(Gate::get_apples).sum();
+        Some((self.apples.saturating_sub(stage_start), gate.get_apples()))
+    }
+
+    /// Opens the current stage's gate once its apples are eaten.
+    fn open_gate(&mut self) {
+        if self
+            .get_gate_progress()
+            .is_none_or(|(eaten, quota)| eaten < quota)
+        {
+            return;
+        }
+        if let Some(gate) = self.board.to_mut().open_gate() {
+            self.background = self.board.get_cells();
+            self.events.push(GameEvent::GateOpened {
+                cells: gate.get_cells().to_vec(),
+            });
+        }
+    }
+
+    /// The walls grown so far, `None` when the game is not a maze.
+    pub fn get_grown_walls(&self) -> Option<u16> {
+        self.grown_walls
+    }
+
+    /// Keeps `count` apples on the board at once; puzzles keep their own apples.
+    pub fn set_food_count(&mut self, count: usize) {
+        self.food_count = count;
+        if self.max_moves.is_some() {
+            return;
+        }
+        self.food.truncate(count.max(1));
+        while self.food.len() < count {
+            let Some(food) = self.find_lunch_point() else {
+                break;
+            };
+            self.food.push(food);
+        }
+    }
+
+    /// Every `every` apples a few walls move elsewhere after a short warning; 0 turns it off.
+    pub fn set_shifting_walls(&mut self, every: u16) {
+        self.shift_every = every;
+    }
+
+    /// Makes the apples drift a cell now and then; puzzle apples stay put.
+    pub fn set_moving_food(&mut self, moving_food: bool) {
+        self.moving_food = moving_food;
+    }
+
+    /// Lets a mouse come out after some apples, worth more if caught before it escapes;
+    /// puzzles never have mice.
+    pub fn set_mice(&mut self, mice: bool) {
+        self.mice = mice;
+    }
+
+    /// Puts `count` roaming snakes on free cells away from the head; they grow to a few cells
+    /// as they move, avoid everything else and kill the snake when it runs into one.
+    pub fn set_hazards(&mut self, count: u8) {
+        let head = Self::get_head(&self.snake);
+        let size = self.board.get_size();
+        self.hazards.clear();
+        for _ in 0..count {
+            let cells: Vec<Point> = Self::free_cells(&self.snake, &self.board, &self.food)
+                .into_iter()
+                .filter(|point| {
+                    Self::distance(point, &head, size) >= HAZARD_SAFE_DISTANCE
+                        && self.is_open(point)
+                })
+                .collect();
+            let Some(at) = cells.choose(&mut self.rng).cloned() else {
+                break;
+            };
+            let direction = *[
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .choose(&mut self.rng)
+            .unwrap();
+            self.hazards.push((Snake::from([at]), direction));
+        }
+    }
+
+    pub fn get_hazard_count(&self) -> usize {
+        self.hazards.len()
+    }
+
+    /// The ticks left to catch the mouse, `None` when there is none.
+    pub fn get_mouse_ticks_left(&self) -> Option<u16> {
+        self.mouse.as_ref().map(|(_, ticks)| *ticks)
+    }
+
+    /// Level-testing cheat: a move into a wall or the body is skipped instead of killing.
+    pub fn set_no_death(&mut self, no_death: bool) {
+        self.no_death = no_death;
+    }
+
+    /// Zen mode: instead of dying, the snake bounces off walls and its body into a free side,
+    /// and waits for a turn when there is none.
+    pub fn set_zen(&mut self, zen: bool) {
+        self.zen = zen;
+    }
+
+    pub fn is_zen(&self) -> bool {
+        self.zen
+    }
+
+    /// Marks the game as hardcore, which is played at top speed without pausing and with a
+    /// single life, whatever checkpoints the board has.
+    pub fn set_hardcore(&mut self, hardcore: bool) {
+        self.hardcore = hardcore;
+        if hardcore {
+            self.lives = 0;
+        }
+    }
+
+    /// The board the game started on, before shifting or grown walls changed it.
+    pub fn get_board(&self) -> &Board {
+        self.origin
+    }
+
+    /// The rules of the board being played.
+    pub fn get_board_rules(&self) -> &BoardRules {
+        self.origin.get_rules()
+    }
+
+    pub fn has_checkpoints(&self) -> bool {
+        !self.board.get_checkpoints().is_empty()
+    }
+
+    /// The respawns left; 0 on boards without checkpoints.
+    pub fn get_lives(&self) -> u16 {
+        self.lives
+    }
+
+    pub fn is_hardcore(&self) -> bool {
+        self.hardcore
+    }
+
+    /// Marks the game as played at a speed the player changed, which keeps its score apart.
+    pub fn set_speed_adjusted(&mut self) {
+        self.speed_adjusted = true;
+    }
+
+    pub fn is_speed_adjusted(&self) -> bool {
+        self.speed_adjusted
+    }
+
+    /// Level-testing cheat: moves the whole snake, keeping its shape, so its head lands on `to`.
+    pub fn teleport(&mut self, to: Point) -> Result<(), Error> {
+        Self::check_in_range(&self.board, &to)?;
+
+        let head = Self::get_head(&self.snake);
+        let (dx, dy) = (to.get_x() - head.get_x(), to.get_y() - head.get_y());
+        let size = self.board.get_size() as i16;
+        let moved: Snake = self
+            .snake
+            .iter()
+            .map(|p| {
+                Point::new(
+                    (p.get_x() + dx).rem_euclid(size),
+                    (p.get_y() + dy).rem_euclid(size),
+                )
+            })
+            .collect();
+
+        if moved.iter().any(|p| self.board.is_wall(p)) {
+            return Err(Error::Invalid("the snake does not fit there"));
+        }
+        self.snake = moved;
+        Ok(())
+    }
+
+    /// Level-testing cheat: moves the oldest apple to `at`.
+    pub fn place_food(&mut self, at: Point) -> Result<(), Error> {
+        Self::check_in_range(&self.board, &at)?;
+
+        if !Self::is_free(&self.snake, &self.board, &at) || self.food.contains(&at) {
+            return Err(Error::Invalid("the cell is not free"));
+        }
+        if !self.food.is_empty() {
+            self.food.remove(0);
+        }
+        self.food.push(at);
+        Ok(())
+    }
+
+    /// Cells where food may appear right now, limited to the board's food zones while
+    /// any of them is free.
+    pub fn get_food_spawn_cells(&self) -> Vec<Point> {
+        let free = self.spawn_cells(&[]);
+        let zoned: Vec<Point> = free
+            .iter()
+            .filter(|point| self.board.get_food_weight(point) > 0)
+            .cloned()
+            .collect();
+
+        if zoned.is_empty() {
+            free
+        } else {
+            zoned
+        }
+    }
+
+    /// Cells the snake started on.
+    pub fn get_snake_spawn_cells(&self) -> Vec<Point> {
+        self.spawn.iter().cloned().collect()
     }
 
     pub fn get_table(&self) -> Vec<Vec<String>> {
-        let mut result = self.board.get_table();
+        to_table(&self.get_cells())
+    }
+
+    pub fn get_cells(&self) -> Vec<Vec<Cell>> {
+        let mut result = self.background.clone();
 
-        Self::put_food(&mut result, &self.food);
-        Self::put_snake(&mut result, &self.snake);
+        for (from, to) in &self.shift {
+            Self::put_cell(&mut result, from, Cell::new(CellKind::Shifting, "▒"));
+            Self::put_cell(&mut result, to, Cell::new(CellKind::Shifting, "░"));
+        }
+        for pellet in self.pellets.iter().flatten() {
+            Self::put_cell(&mut result, pellet, Cell::new(CellKind::Food, "·"));
+        }
+        for food in &self.food {
+            Self::put_food(&mut result, food);
+        }
+        if let Some((mouse, _)) = &self.mouse {
+            Self::put_cell(&mut result, mouse, Cell::new(CellKind::Food, "ᘛ"));
+        }
+        for (hazard, _) in &self.hazards {
+            Self::put_snake(
+                &mut result,
+                hazard,
+                CellKind::RivalHead,
+                CellKind::RivalSnake,
+            );
+        }
+        Self::put_snake(
+            &mut result,
+            &self.snake,
+            CellKind::SnakeHead,
+            CellKind::Snake,
+        );
 
         result
     }
 }
 
 impl Game<'_> {
-    fn put_snake(result: &mut [Vec<String>], snake: &Snake) {
-        fn get_char(before: &Direction, after: &Direction) -> String {
+    pub(super) fn put_snake(
+        result: &mut [Vec<Cell>],
+        snake: &Snake,
+        head_kind: CellKind,
+        body_kind: CellKind,
+    ) {
+        fn get_char(before: &Direction, after: &Direction) -> &'static str {
             match (before, after) {
-                (&Direction::Down, &Direction::Right) => "┌".to_string(),
-                (&Direction::Right, &Direction::Down) => "┌".to_string(),
-                (&Direction::Down, &Direction::Left) => "┐".to_string(),
-                (&Direction::Left, &Direction::Down) => "┐".to_string(),
-                (&Direction::Up, &Direction::Right) => "└".to_string(),
-                (&Direction::Right, &Direction::Up) => "└".to_string(),
-                (&Direction::Up, &Direction::Left) => "┘".to_string(),
-                (&Direction::Left, &Direction::Up) => "┘".to_string(),
-                (&Direction::None, &Direction::Left) => "─".to_string(),
-                (&Direction::Left, &Direction::None) => "─".to_string(),
-                (&Direction::None, &Direction::Right) => "─".to_string(),
-                (&Direction::Right, &Direction::None) => "─".to_string(),
-                (&Direction::Left, &Direction::Left) => "─".to_string(),
-                (&Direction::Right, &Direction::Right) => "─".to_string(),
-                (&Direction::Left, &Direction::Right) => "─".to_string(),
-                (&Direction::Right, &Direction::Left) => "─".to_string(),
-                (&Direction::Down, &Direction::Up) => "│".to_string(),
-                (&Direction::Up, &Direction::Down) => "│".to_string(),
-                (&Direction::None, &Direction::Down) => "│".to_string(),
-                (&Direction::Down, &Direction::None) => "│".to_string(),
-                (&Direction::None, &Direction::Up) => "│".to_string(),
-                (&Direction::Up, &Direction::None) => "│".to_string(),
-                (&Direction::Up, &Direction::Up) => "│".to_string(),
-                (&Direction::Down, &Direction::Down) => "│".to_string(),
+                (&Direction::Down, &Direction::Right) => "┌",
+                (&Direction::Right, &Direction::Down) => "┌",
+                (&Direction::Down, &Direction::Left) => "┐",
+                (&Direction::Left, &Direction::Down) => "┐",
+                (&Direction::Up, &Direction::Right) => "└",
+                (&Direction::Right, &Direction::Up) => "└",
+                (&Direction::Up, &Direction::Left) => "┘",
+                (&Direction::Left, &Direction::Up) => "┘",
+                (&Direction::None, &Direction::Left) => "─",
+                (&Direction::Left, &Direction::None) => "─",
+                (&Direction::None, &Direction::Right) => "─",
+                (&Direction::Right, &Direction::None) => "─",
+                (&Direction::Left, &Direction::Left) => "─",
+                (&Direction::Right, &Direction::Right) => "─",
+                (&Direction::Left, &Direction::Right) => "─",
+                (&Direction::Right, &Direction::Left) => "─",
+                (&Direction::Down, &Direction::Up) => "│",
+                (&Direction::Up, &Direction::Down) => "│",
+                (&Direction::None, &Direction::Down) => "│",
+                (&Direction::Down, &Direction::None) => "│",
+                (&Direction::None, &Direction::Up) => "│",
+                (&Direction::Up, &Direction::None) => "│",
+                (&Direction::Up, &Direction::Up) => "│",
+                (&Direction::Down, &Direction::Down) => "│",
                 _ => {
                     panic!("{:?} {:?}", before, after)
                 }
@@ -113,59 +757,736 @@ impl Game<'_> {
         if let Some(first) = iter.next() {
             let mut prev_direction = Direction::None;
             let mut current = first;
+            let mut kind = head_kind;
 
             for next in iter {
                 let char_to_set = get_char(&prev_direction, &current.direction_of_neighbor(next));
                 result[(current.get_x() + 1) as usize][(current.get_y() + 1) as usize] =
-                    char_to_set;
+                    Cell::new(kind, char_to_set);
+                kind = body_kind;
 
                 prev_direction = next.direction_of_neighbor(current);
                 current = next;
             }
 
-            result[(current.get_x() + 1) as usize][(current.get_y() + 1) as usize] =
-                get_char(&prev_direction, &Direction::None);
+            result[(current.get_x() + 1) as usize][(current.get_y() + 1) as usize] =
+                Cell::new(kind, get_char(&prev_direction, &Direction::None));
+        }
+    }
+
+    pub(super) fn put_food(result: &mut [Vec<Cell>], food: &Point) {
+        Self::put_cell(result, food, Cell::new(CellKind::Food, "●"));
+    }
+
+    fn put_cell(result: &mut [Vec<Cell>], point: &Point, cell: Cell) {
+        result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] = cell;
+    }
+}
+
+impl Game<'_> {
+    fn record(&mut self, input: ReplayInput) {
+        self.inputs.push(TimedInput {
+            tick: self.tick,
+            input,
+        });
+    }
+
+    fn turn(&mut self, direction: Direction) {
+        if !self.turned {
+            let heading = self.heading();
+            if direction != heading && !heading.is_opposite(&direction) {
+                self.direction = direction;
+                self.turned = true;
+            }
+        } else if self.pending_turns.len() < MAX_PENDING_TURNS {
+            let last = self.pending_turns.back().unwrap_or(&self.direction);
+            if direction != *last && !last.is_opposite(&direction) {
+                self.pending_turns.push_back(direction);
+            }
+        }
+    }
+
+    /// Boosting burns energy and every few boosted ticks a point of score; resting recharges.
+    fn update_boost(&mut self) {
+        if self.is_boosting() {
+            self.boost_energy -= 1;
+            self.boost_ticks += 1;
+            if self.boost_ticks.is_multiple_of(BOOST_DRAIN_TICKS) {
+                self.score = self.score.saturating_sub(1);
+            }
+        } else if !self.boost {
+            self.boost_energy = (self.boost_energy + 1).min(MAX_BOOST_ENERGY);
+        }
+    }
+
+    /// Direction the head actually moved in last tick, taken from the neck.
+    fn heading(&self) -> Direction {
+        match (self.snake.front(), self.snake.iter().nth(1)) {
+            (Some(head), Some(neck)) => neck.direction_of_neighbor(head),
+            _ => self.direction,
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        let heading = self.heading();
+        if heading.is_opposite(&self.direction) {
+            self.direction = heading;
+        }
+
+        let head = self.snake.front().unwrap();
+
+        let new_head = head.get_neighbor(&self.direction, self.board.get_size());
+
+        let collides_with_walls =
+            self.board.is_wall(&new_head) || self.leaves_board(head, &new_head);
+        let collides_with_body =
+            Self::is_snake(&self.snake, &new_head) || self.is_hazard(&new_head);
+
+        if (collides_with_body || collides_with_walls) && self.zen {
+            if let Some(direction) = self.bounce() {
+                self.direction = direction;
+                return self.step();
+            }
+        }
+
+        if (collides_with_body || collides_with_walls) && (self.no_death || self.zen) {
+            self.events.push(GameEvent::Bumped);
+            true
+        } else if collides_with_body || collides_with_walls {
+            let cause = if self.board.is_wall(&new_head) {
+                DeathCause::Wall
+            } else if self.leaves_board(head, &new_head) {
+                DeathCause::Edge
+            } else if self.is_hazard(&new_head) {
+                DeathCause::RoamingSnake
+            } else {
+                DeathCause::Body
+            };
+            self.death = Some((cause, new_head));
+            self.snake.pop_back();
+            self.snake.pop_front();
+
+            false
+        } else if self
+            .mouse
+            .as_ref()
+            .is_some_and(|(mouse, _)| *mouse == new_head)
+        {
+            self.mouse = None;
+            self.snake.push_front(new_head.clone());
+            self.moves += 1;
+            self.score += MOUSE_SCORE;
+            self.events.push(GameEvent::Ate {
+                at: new_head,
+                points: MOUSE_SCORE,
+            });
+
+            true
+        } else if let Some(index) = self.food.iter().position(|food| *food == new_head) {
+            self.food.remove(index);
+            self.snake.push_front(new_head.clone());
+            self.moves += 1;
+            self.score += FOOD_SCORE;
+            self.events.push(GameEvent::Ate {
+                at: new_head,
+                points: FOOD_SCORE,
+            });
+            if self.max_moves.is_none() {
+                match self.find_lunch_point() {
+                    Some(food) => self.food.push(food),
+                    None => self.filled = self.food.is_empty(),
+                }
+            }
+            if self.mice
+                && self.max_moves.is_none()
+                && self.mouse.is_none()
+                && self.rng.gen_ratio(1, MOUSE_CHANCE)
+            {
+                self.mouse = self.find_lunch_point().map(|at| (at, MOUSE_TICKS));
+            }
+            self.apples += 1;
+            self.open_gate();
+            if self.shift_every > 0 && self.apples.is_multiple_of(self.shift_every) {
+                self.plan_shift();
+            }
+
+            true
+        } else {
+            self.snake.push_front(new_head);
+            self.snake.pop_back();
+            self.moves += 1;
+
+            true
+        }
+    }
+
+    /// Brings a dead snake back at its last checkpoint, when it has reached one and has a life
+    /// left, returning whether it did.
+    fn respawn(&mut self) -> bool {
+        let Some((point, direction)) = self.checkpoint.clone() else {
+            return false;
+        };
+        if self.lives == 0 {
+            return false;
+        }
+
+        self.lives -= 1;
+        self.death = None;
+        self.snake = Self::spawn_snake(&self.board, &point, direction, self.length);
+        self.direction = direction;
+        self.pending_turns.clear();
+        self.events.push(GameEvent::Respawned);
+        true
+    }
+
+    fn eat_pellet(&mut self) {
+        let head = Self::get_head(&self.snake);
+        if let Some(pellets) = &mut self.pellets {
+            pellets.retain(|pellet| *pellet != head);
+        }
+    }
+
+    /// Grows the walls the score has earned since the last ones. When no cell is safe any
+    /// more the board stays as it is.
+    fn grow_walls(&mut self) {
+        let Some(grown) = self.grown_walls else {
+            return;
+        };
+        let due = self.score / MAZE_SCORE_STEP;
+        if grown >= due {
+            return;
+        }
+
+        for _ in grown..due {
+            let Some(at) = self.safe_wall_cell() else {
+                break;
+            };
+            let _ = self.board.to_mut().add_wall(at);
+        }
+        self.grown_walls = Some(due);
+        self.background = self.board.get_cells();
+    }
+
+    /// A random open cell away from the head whose wall would leave every other free cell
+    /// reachable.
+    fn safe_wall_cell(&mut self) -> Option<Point> {
+        let head = Self::get_head(&self.snake);
+        let size = self.board.get_size();
+        let mut cells: Vec<Point> = (0..size as i16)
+            .flat_map(|x| (0..size as i16).map(move |y| Point::new(x, y)))
+            .filter(|point| {
+                self.is_open(point)
+                    && Self::distance(point, &head, size) >= MAZE_SAFE_DISTANCE
+                    && !self.board.get_checkpoints().contains(point)
+            })
+            .collect();
+        cells.shuffle(&mut self.rng);
+
+        let open = self.count_reachable(&head, &[]);
+        cells
+            .into_iter()
+            .find(|cell| self.count_reachable(&head, std::slice::from_ref(cell)) == open - 1)
+    }
+
+    /// How many cells can be reached from `from`; the snakes move out of the way, so only
+    /// walls and `blocked` stop it.
+    fn count_reachable(&self, from: &Point, blocked: &[Point]) -> usize {
+        self.board
+            .get_reachable(std::slice::from_ref(from), blocked)
+            .iter()
+            .flatten()
+            .filter(|reached| **reached)
+            .count()
+    }
+
+    fn touch_checkpoint(&mut self) {
+        let head = Self::get_head(&self.snake);
+        let reached = self
+            .checkpoint
+            .as_ref()
+            .is_some_and(|(point, _)| *point == head);
+        if !reached && self.board.get_checkpoints().contains(&head) {
+            self.checkpoint = Some((head.clone(), self.heading()));
+            self.events.push(GameEvent::Checkpoint { at: head });
+        }
+    }
+
+    /// A side the head can turn to without running into anything.
+    fn bounce(&self) -> Option<Direction> {
+        let head = self.snake.front().unwrap();
+        let right = self.direction.clockwise();
+        [right, right.opposite()].into_iter().find(|direction| {
+            let next = head.get_neighbor(direction, self.board.get_size());
+            Self::is_free(&self.snake, &self.board, &next)
+                && !self.is_hazard(&next)
+                && !self.leaves_board(head, &next)
+        })
+    }
+
+    /// Picks a few walls and free cells to move them to, shown as a warning until they move.
+    fn plan_shift(&mut self) {
+        let free = Self::free_cells(&self.snake, &self.board, &self.food);
+        let from = self
+            .board
+            .get_walls()
+            .choose_multiple(&mut self.rng, SHIFTING_WALLS);
+        let to = free.choose_multiple(&mut self.rng, SHIFTING_WALLS);
+
+        self.shift = from.cloned().zip(to.cloned()).collect();
+        self.shift_countdown = SHIFT_WARNING_TICKS;
+    }
+
+    /// Moves the warned walls once the warning runs out, unless the snake or food got there first.
+    fn update_shift(&mut self) {
+        if self.shift.is_empty() {
+            return;
+        }
+        self.shift_countdown = self.shift_countdown.saturating_sub(1);
+        if self.shift_countdown > 0 {
+            return;
+        }
+
+        for (from, to) in std::mem::take(&mut self.shift) {
+            if Self::is_free(&self.snake, &self.board, &to) && !self.food.contains(&to) {
+                let board = self.board.to_mut();
+                let color = board.get_wall_color(&from);
+                board.remove_wall(&from);
+                let _ = board.add_wall(to.clone());
+                board.paint_walls(&to, &to, color);
+            }
+        }
+        self.background = self.board.get_cells();
+    }
+
+    /// Drifts each apple to a random free neighbor, when it has one.
+    fn update_food(&mut self) {
+        if !self.moving_food
+            || self.max_moves.is_some()
+            || !(self.tick + 1).is_multiple_of(FOOD_DRIFT_TICKS)
+        {
+            return;
+        }
+
+        let size = self.board.get_size();
+        for index in 0..self.food.len() {
+            let free: Vec<Point> = [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .iter()
+            .map(|direction| self.food[index].get_neighbor(direction, size))
+            .filter(|point| self.is_open(point) && !self.leaves_board(&self.food[index], point))
+            .collect();
+            if let Some(to) = free.choose(&mut self.rng) {
+                self.food[index] = to.clone();
+            }
+        }
+    }
+
+    /// Runs the mouse one cell away from the head every `MOUSE_MOVE_TICKS`, staying put when
+    /// no free neighbor is farther; it escapes once its time is up.
+    fn update_mouse(&mut self) {
+        let Some((mouse, ticks)) = self.mouse.take() else {
+            return;
+        };
+        if ticks == 0 {
+            return;
+        }
+
+        let head = Self::get_head(&self.snake);
+        let size = self.board.get_size();
+        let mut to = mouse.clone();
+        if ticks.is_multiple_of(MOUSE_MOVE_TICKS) {
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                let next = mouse.get_neighbor(&direction, size);
+                if self.is_open(&next)
+                    && Self::distance(&next, &head, size) > Self::distance(&to, &head, size)
+                {
+                    to = next;
+                }
+            }
+        }
+        self.mouse = Some((to, ticks - 1));
+    }
+
+    /// Moves each roaming snake on, mostly straight ahead, turning at random now and then or
+    /// when blocked, and waiting when it is boxed in.
+    fn update_hazards(&mut self) {
+        if !(self.tick + 1).is_multiple_of(HAZARD_MOVE_TICKS) {
+            return;
+        }
+
+        let size = self.board.get_size();
+        for index in 0..self.hazards.len() {
+            let (hazard, heading) = &self.hazards[index];
+            let head = hazard.front().unwrap().clone();
+            let heading = *heading;
+            let mut options: Vec<Direction> = [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .into_iter()
+            .filter(|direction| {
+                !direction.is_opposite(&heading)
+                    && self.is_open(&head.get_neighbor(direction, size))
+            })
+            .collect();
+            if options.contains(&heading) && !self.rng.gen_ratio(1, HAZARD_TURN_CHANCE) {
+                options = vec![heading];
+            }
+            let Some(direction) = options.choose(&mut self.rng).copied() else {
+                continue;
+            };
+
+            let (hazard, heading) = &mut self.hazards[index];
+            hazard.push_front(head.get_neighbor(&direction, size));
+            if hazard.len() > HAZARD_LENGTH {
+                hazard.pop_back();
+            }
+            *heading = direction;
+        }
+    }
+
+    /// Whether a move from `from` to `to` went across an edge on a board where edges kill.
+    fn leaves_board(&self, from: &Point, to: &Point) -> bool {
+        !self.board.get_rules().is_wrap()
+            && (from.get_x().abs_diff(to.get_x()) > 1 || from.get_y().abs_diff(to.get_y()) > 1)
+    }
+
+    fn is_hazard(&self, point: &Point) -> bool {
+        self.hazards
+            .iter()
+            .any(|(hazard, _)| hazard.contains(point))
+    }
+
+    /// Steps between two cells, going across the wrapped edges when that is shorter.
+    fn distance(a: &Point, b: &Point, size: u16) -> u16 {
+        let axis = |a: i16, b: i16| {
+            let d = a.abs_diff(b);
+            d.min(size - d)
+        };
+        axis(a.get_x(), b.get_x()) + axis(a.get_y(), b.get_y())
+    }
+
+    /// Free of walls, the snakes, apples and the mouse.
+    fn is_open(&self, point: &Point) -> bool {
+        Self::is_free(&self.snake, &self.board, point)
+            && !self.is_hazard(point)
+            && !self.food.contains(point)
+            && self.mouse.as_ref().is_none_or(|(mouse, _)| mouse != point)
+    }
+
+    fn create_snake(table_size: u16, length: u16) -> Snake {
+        let half = (table_size as i16 - 1) / 2;
+        let offset = length as i16 / 2;
+
+        let range = if length.is_multiple_of(2) {
+            -offset..=offset - 1
+        } else {
+            -offset..=offset
+        };
+
+        range.rev().map(|i| Point::new(half, half + i)).collect()
+    }
+
+    /// A snake with its head on the board's spawn and its body trailing behind, cut short by
+    /// walls.
+    pub(super) fn spawn_snake(
+        board: &Board,
+        head: &Point,
+        direction: Direction,
+        length: u16,
+    ) -> Snake {
+        let behind = direction.opposite();
+        let mut snake = Snake::from([head.clone()]);
+        let mut cell = head.clone();
+        while snake.len() < length as usize {
+            cell = cell.get_neighbor(&behind, board.get_size());
+            if board.is_wall(&cell) || snake.contains(&cell) {
+                break;
+            }
+            snake.push_back(cell.clone());
+        }
+        snake
+    }
+
+    /// Where the next apple or mouse goes: a free cell picked by the board's food zones,
+    /// preferring ones that do not touch the other apples; `None` once no cell is left for it.
+    fn find_lunch_point(&mut self) -> Option<Food> {
+        let free = self.spawn_cells(&self.food);
+        let apart: Vec<Point> = free
+            .iter()
+            .filter(|point| !self.food.iter().any(|other| Self::touches(point, other)))
+            .cloned()
+            .collect();
+
+        self.board
+            .pick_food_cell(&apart, &mut self.rng)
+            .or_else(|| self.board.pick_food_cell(&free, &mut self.rng))
+    }
+
+    /// The cells food can be put on: free of walls, the snakes, `food` and the mouse, and
+    /// reachable from the head, so nothing lands in a pocket the snake cannot get into.
+    fn spawn_cells(&self, food: &[Food]) -> Vec<Point> {
+        let heads: Vec<Point> = self.snake.front().cloned().into_iter().collect();
+        let reachable = self.board.get_reachable(&heads, &[]);
+        Self::free_cells(&self.snake, &self.board, food)
+            .into_iter()
+            .filter(|point| {
+                reachable[point.get_x() as usize][point.get_y() as usize]
+                    && !self.is_hazard(point)
+                    && self.mouse.as_ref().is_none_or(|(mouse, _)| mouse != point)
+            })
+            .collect()
+    }
+
+    fn free_cells(snake: &Snake, board: &Board, food: &[Food]) -> Vec<Point> {
+        let size = board.get_size() as i16;
+        (0..size)
+            .flat_map(|x| (0..size).map(move |y| Point::new(x, y)))
+            .filter(|point| Self::is_free(snake, board, point) && !food.contains(point))
+            .collect()
+    }
+
+    fn touches(a: &Point, b: &Point) -> bool {
+        (a.get_x() - b.get_x()).abs() <= 1 && (a.get_y() - b.get_y()).abs() <= 1
+    }
+
+    fn get_head(snake: &Snake) -> Point {
+        (*snake.front().unwrap()).clone()
+    }
+
+    fn is_snake(snake: &Snake, point: &Point) -> bool {
+        snake.contains(point)
+    }
+
+    fn check_in_range(board: &Board, point: &Point) -> Result<(), Error> {
+        let valid_range = 0..board.get_size() as i16;
+        if valid_range.contains(&point.get_x()) && valid_range.contains(&point.get_y()) {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange(point.clone()))
         }
     }
 
-    fn put_food(result: &mut [Vec<String>], food: &Point) {
-        result[(food.get_x() + 1) as usize][(food.get_y() + 1) as usize] = "●".to_string();
+    fn is_free(snake: &Snake, board: &Board, point: &Point) -> bool {
+        !Self::is_snake(snake, point) && !board.is_wall(point)
     }
 }
 
-impl Game<'_> {
-    fn create_snake(table_size: u16, length: u16) -> Snake {
-        let half = (table_size as i16 - 1) / 2;
-        let offset = length as i16 / 2;
-
-        let range = if length % 2 == 0 {
-            -offset..=offset - 1
-        } else {
-            -offset..=offset
-        };
+/// A game in full, its boards and RNG included, for saving, syncing over the network or
+/// checkpointing a replay. `resume` picks the game up exactly where it was.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameSnapshot {
+    /// The board as played, when shifting walls have changed it.
+    board: Option<Board>,
+    origin: Board,
+    seed: u64,
+    rng: ChaCha12Rng,
+    length: u16,
+    food_count: usize,
+    tick: u32,
+    inputs: Vec<TimedInput>,
+    spawn: Snake,
+    snake: Snake,
+    food: Vec<Food>,
+    score: u16,
+    direction: Direction,
+    turned: bool,
+    pending_turns: VecDeque<Direction>,
+    boost: bool,
+    boost_energy: u16,
+    boost_ticks: u16,
+    no_death: bool,
+    zen: bool,
+    hardcore: bool,
+    #[serde(default)]
+    speed_adjusted: bool,
+    moving_food: bool,
+    mice: bool,
+    mouse: Option<(Point, u16)>,
+    hazards: Vec<(Snake, Direction)>,
+    lives: u16,
+    checkpoint: Option<(Point, Direction)>,
+    shift_every: u16,
+    apples: u16,
+    shift: Vec<(Wall, Wall)>,
+    shift_countdown: u16,
+    moves: u16,
+    max_moves: Option<u16>,
+    target_length: Option<u16>,
+    pellets: Option<Vec<Point>>,
+    #[serde(default)]
+    grown_walls: Option<u16>,
+    events: Vec<GameEvent>,
+    over: bool,
+    #[serde(default)]
+    filled: bool,
+    #[serde(default)]
+    death: Option<(DeathCause, Point)>,
+}
 
-        range.rev().map(|i| Point::new(half, half + i)).collect()
+impl Game<'_> {
+    pub fn snapshot(&self) -> GameSnapshot {
+        let Game {
+            board,
+            origin,
+            seed,
+            rng,
+            length,
+            food_count,
+            tick,
+            inputs,
+            spawn,
+            snake,
+            food,
+            score,
+            direction,
+            turned,
+            pending_turns,
+            boost,
+            boost_energy,
+            boost_ticks,
+            no_death,
+            zen,
+            hardcore,
+            speed_adjusted,
+            moving_food,
+            mice,
+            mouse,
+            hazards,
+            lives,
+            checkpoint,
+            shift_every,
+            apples,
+            shift,
+            shift_countdown,
+            moves,
+            max_moves,
+            target_length,
+            pellets,
+            grown_walls,
+            events,
+            over,
+            filled,
+            background: _,
+            death,
+        } = self;
+        GameSnapshot {
+            board: match board {
+                Cow::Owned(board) => Some(board.clone()),
+                Cow::Borrowed(_) => None,
+            },
+            origin: (*origin).clone(),
+            seed: *seed,
+            rng: rng.clone(),
+            length: *length,
+            food_count: *food_count,
+            tick: *tick,
+            inputs: inputs.clone(),
+            spawn: spawn.clone(),
+            snake: snake.clone(),
+            food: food.clone(),
+            score: *score,
+            direction: *direction,
+            turned: *turned,
+            pending_turns: pending_turns.clone(),
+            boost: *boost,
+            boost_energy: *boost_energy,
+            boost_ticks: *boost_ticks,
+            no_death: *no_death,
+            zen: *zen,
+            hardcore: *hardcore,
+            speed_adjusted: *speed_adjusted,
+            moving_food: *moving_food,
+            mice: *mice,
+            mouse: mouse.clone(),
+            hazards: hazards.clone(),
+            lives: *lives,
+            checkpoint: checkpoint.clone(),
+            shift_every: *shift_every,
+            apples: *apples,
+            shift: shift.clone(),
+            shift_countdown: *shift_countdown,
+            moves: *moves,
+            max_moves: *max_moves,
+            target_length: *target_length,
+            pellets: pellets.clone(),
+            grown_walls: *grown_walls,
+            events: events.clone(),
+            over: *over,
+            filled: *filled,
+            death: death.clone(),
+        }
     }
+}
 
-    fn find_lunch_point(snake: &Snake, board: &Board) -> Food {
-        let table_size = board.get_size();
-        let mut rng = rand::thread_rng();
-        let mut food = Self::get_head(snake);
-        while Self::is_snake(snake, &food) || board.is_wall(&food) {
-            food = Food::new(
-                rng.gen_range(0..table_size as i16),
-                rng.gen_range(0..table_size as i16),
-            );
-        }
-        food
+impl GameSnapshot {
+    /// The board the game started on.
+    pub fn get_board(&self) -> &Board {
+        &self.origin
     }
 
-    fn get_head(snake: &Snake) -> Point {
-        (*snake.front().unwrap()).clone()
+    pub fn get_score(&self) -> u16 {
+        self.score
     }
 
-    fn is_snake(snake: &Snake, point: &Point) -> bool {
-        snake.contains(point)
+    /// The game the snapshot was taken of, borrowing its boards.
+    pub fn resume(&self) -> Game<'_> {
+        let board = self.board.as_ref().unwrap_or(&self.origin);
+        Game {
+            board: Cow::Borrowed(board),
+            origin: &self.origin,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            length: self.length,
+            food_count: self.food_count,
+            tick: self.tick,
+            inputs: self.inputs.clone(),
+            spawn: self.spawn.clone(),
+            snake: self.snake.clone(),
+            food: self.food.clone(),
+            score: self.score,
+            direction: self.direction,
+            turned: self.turned,
+            pending_turns: self.pending_turns.clone(),
+            boost: self.boost,
+            boost_energy: self.boost_energy,
+            boost_ticks: self.boost_ticks,
+            no_death: self.no_death,
+            zen: self.zen,
+            hardcore: self.hardcore,
+            speed_adjusted: self.speed_adjusted,
+            moving_food: self.moving_food,
+            mice: self.mice,
+            mouse: self.mouse.clone(),
+            hazards: self.hazards.clone(),
+            lives: self.lives,
+            checkpoint: self.checkpoint.clone(),
+            shift_every: self.shift_every,
+            apples: self.apples,
+            shift: self.shift.clone(),
+            shift_countdown: self.shift_countdown,
+            moves: self.moves,
+            max_moves: self.max_moves,
+            target_length: self.target_length,
+            pellets: self.pellets.clone(),
+            grown_walls: self.grown_walls,
+            events: self.events.clone(),
+            over: self.over,
+            filled: self.filled,
+            background: board.get_cells(),
+            death: self.death.clone(),
+        }
     }
 }
 
@@ -173,7 +1494,14 @@ impl Game<'_> {
 mod test_game {
     use std::collections::LinkedList;
 
-    use crate::core::{point::Point, Board, Direction, Game};
+    use super::{
+        CHECKPOINT_LIVES, FOOD_DRIFT_TICKS, HAZARD_LENGTH, MAZE_SAFE_DISTANCE, MAZE_SCORE_STEP,
+        MOUSE_SCORE, MOUSE_TICKS,
+    };
+    use crate::core::{
+        point::Point, Board, BoardRules, CellKind, DeathCause, Direction, FoodZone, Game,
+        GameEvent, GameSnapshot, Gate, Objective,
+    };
 
     #[test]
     fn check_create_table() {
@@ -216,7 +1544,7 @@ mod test_game {
     fn walk() {
         let board = Board::new("test".to_string(), 5, Vec::new());
         let mut game = Game::new(&board, 3);
-        game.food = Point::new(0, 0);
+        game.food = vec![Point::new(0, 0)];
 
         assert_eq!(
             game.snake,
@@ -253,7 +1581,7 @@ mod test_game {
 
         let board = Board::new("test".to_string(), 7, Vec::new());
         let mut game = Game::new(&board, 5);
-        game.food = Point::new(6, 6);
+        game.food = vec![Point::new(6, 6)];
 
         game.snake = LinkedList::from([
             Point::new(1, 1),
@@ -277,7 +1605,7 @@ mod test_game {
             LinkedList::from([Point::new(3, 4), Point::new(3, 3), Point::new(3, 2)])
         );
 
-        game.food = Point::new(4, 5);
+        game.food = vec![Point::new(4, 5)];
 
         assert!(game.walk());
 
@@ -307,9 +1635,9 @@ mod test_game {
             Point::new(3, 4),
             Point::new(3, 3)
         ]
-        .contains(&game.food));
+        .contains(&game.food[0]));
 
-        game.food = Point::new(0, 0);
+        game.food = vec![Point::new(0, 0)];
 
         assert!(game.walk());
         assert_eq!(
@@ -324,4 +1652,593 @@ mod test_game {
 
         assert!(game.walk());
     }
+
+    #[test]
+    fn race_ends_at_target_length() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.set_target_length(4);
+        game.food = vec![Point::new(3, 5)];
+
+        assert!(!game.is_target_reached());
+        assert!(!game.walk());
+        assert!(game.is_target_reached());
+        assert_eq!(game.get_length(), 4);
+        assert_eq!(game.get_replay().get_rules().target_length, Some(4));
+    }
+
+    #[test]
+    fn eat_event() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(3, 5)];
+
+        assert!(game.walk());
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::Ate {
+                at: Point::new(3, 5),
+                points: 1
+            }]
+        );
+        assert!(game.take_events().is_empty());
+
+        game.food = vec![Point::new(0, 0)];
+        assert!(game.walk());
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn cells_mark_head() {
+        let board = Board::new("test".to_string(), 7, vec![Point::new(0, 0)]);
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(6, 6)];
+
+        let cells = game.get_cells();
+        assert_eq!(cells[4][5].get_kind(), CellKind::SnakeHead);
+        assert_eq!(cells[4][4].get_kind(), CellKind::Snake);
+        assert_eq!(cells[4][3].get_kind(), CellKind::Snake);
+        assert_eq!(cells[7][7].get_kind(), CellKind::Food);
+        assert_eq!(cells[1][1].get_kind(), CellKind::Wall);
+        assert_eq!(cells[0][0].get_kind(), CellKind::Border);
+        assert_eq!(cells[2][2].get_kind(), CellKind::Empty);
+    }
+
+    #[test]
+    fn buffered_turns() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(6, 6)];
+
+        game.rotation(Direction::Up);
+        game.rotation(Direction::Left);
+        game.rotation(Direction::Down);
+        game.rotation(Direction::Right);
+
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(2, 4)));
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(2, 3)));
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(3, 3)));
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(4, 3)));
+    }
+
+    #[test]
+    fn no_reversal_into_neck() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(6, 6)];
+
+        game.rotation(Direction::Left);
+        assert_eq!(game.direction, Direction::Right);
+
+        game.direction = Direction::Left;
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(3, 5)));
+
+        game.snake = LinkedList::from([Point::new(3, 0), Point::new(3, 6), Point::new(3, 5)]);
+        game.rotation(Direction::Left);
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(3, 1)));
+    }
+
+    #[test]
+    fn boost_drains_energy_and_score() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(0, 0)];
+        game.score = 3;
+
+        game.set_boost(true);
+        for _ in 0..5 {
+            assert!(game.is_boosting());
+            assert!(game.walk());
+        }
+        assert_eq!(game.score, 2);
+        assert_eq!(game.boost_energy, 35);
+
+        game.boost_energy = 1;
+        assert!(game.walk());
+        assert!(!game.is_boosting());
+        assert!(game.walk());
+        assert_eq!(game.boost_energy, 0);
+
+        game.set_boost(false);
+        assert!(game.walk());
+        assert_eq!(game.boost_energy, 1);
+        assert_eq!(game.get_boost_gauge(), 1.0 / 40.0);
+    }
+
+    #[test]
+    fn food_spawn_cells_avoid_walls_and_snake() {
+        let board = Board::new("test".to_string(), 5, vec![Point::new(0, 0)]);
+        let game = Game::new(&board, 3);
+        let cells = game.get_food_spawn_cells();
+
+        assert_eq!(cells.len(), 25 - 1 - 3);
+        assert!(!cells.contains(&Point::new(0, 0)));
+        assert!(game
+            .get_snake_spawn_cells()
+            .iter()
+            .all(|point| !cells.contains(point)));
+    }
+
+    #[test]
+    fn food_never_spawns_in_a_closed_pocket() {
+        let walls = vec![
+            Point::new(0, 1),
+            Point::new(1, 0),
+            Point::new(0, 4),
+            Point::new(4, 0),
+        ];
+        let board = Board::new("test".to_string(), 5, walls);
+        let mut game = Game::with_seed(&board, 2, 3);
+        assert!(!game.get_food_spawn_cells().contains(&Point::new(0, 0)));
+
+        game.set_food_count(16);
+        assert_eq!(game.food.len(), 16);
+        assert!(!game.food.contains(&Point::new(0, 0)));
+        game.set_food_count(20);
+        assert_eq!(game.food.len(), 25 - 4 - 1 - 2);
+    }
+
+    #[test]
+    fn filling_the_board_wins() {
+        let walls = (1..4)
+            .flat_map(|x| (0..4).map(move |y| Point::new(x, y)))
+            .collect();
+        let board = Board::new("test".to_string(), 4, walls);
+        let mut game = Game::with_seed(&board, 3, 1);
+        game.snake = LinkedList::from([Point::new(0, 2), Point::new(0, 1), Point::new(0, 0)]);
+        game.direction = Direction::Right;
+        game.food = vec![Point::new(0, 3)];
+
+        assert!(!game.walk());
+        assert!(game.is_board_filled());
+        assert!(game.is_over());
+        assert!(game.get_death().is_none());
+        assert_eq!(game.get_score(), 1);
+    }
+
+    #[test]
+    fn shifting_walls_move_after_a_warning() {
+        let walls = vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)];
+        let board = Board::new("test".to_string(), 7, walls.clone());
+        let mut game = Game::new(&board, 3);
+        game.set_shifting_walls(1);
+        game.food = vec![Point::new(3, 5)];
+
+        assert!(game.walk());
+        assert_eq!(game.shift.len(), 3);
+        let cells = game.get_cells();
+        for (from, to) in &game.shift {
+            assert!(walls.contains(from));
+            assert!(!walls.contains(to) && !game.snake.contains(to) && !game.food.contains(to));
+            assert_eq!(
+                cells[(to.get_x() + 1) as usize][(to.get_y() + 1) as usize].get_kind(),
+                CellKind::Shifting
+            );
+        }
+
+        let shift = game.shift.clone();
+        game.set_shifting_walls(0);
+        for _ in 1..super::SHIFT_WARNING_TICKS {
+            game.walk();
+        }
+        assert!(game.shift.is_empty());
+        assert!(board.is_wall(&Point::new(0, 0)));
+        for (from, to) in shift {
+            if game.board.is_wall(&to) {
+                assert!(!game.board.is_wall(&from));
+            }
+        }
+        assert_eq!(game.board.get_walls().len(), 3);
+        assert_eq!(game.background, game.board.get_cells());
+    }
+
+    #[test]
+    fn several_apples_spread_out() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.set_food_count(5);
+        assert_eq!(game.food.len(), 5);
+
+        for (i, food) in game.food.iter().enumerate() {
+            assert!(!game.snake.contains(food));
+            assert!(game.food[i + 1..]
+                .iter()
+                .all(|other| !Game::touches(food, other)));
+        }
+
+        game.food = vec![Point::new(4, 6), Point::new(0, 0)];
+        assert!(game.walk());
+        assert_eq!(game.food.len(), 2);
+        assert_eq!(game.food[0], Point::new(0, 0));
+
+        game.set_food_count(1);
+        assert_eq!(game.food, vec![Point::new(0, 0)]);
+    }
+
+    #[test]
+    fn food_spawns_in_zones() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board.add_food_zone(FoodZone::new(&Point::new(0, 0), &Point::new(0, 6), 1));
+        let mut game = Game::new(&board, 3);
+        game.set_food_count(3);
+
+        assert!(game.food.iter().all(|food| food.get_x() == 0));
+        assert_eq!(game.get_food_spawn_cells().len(), 7);
+    }
+
+    #[test]
+    fn board_spawn() {
+        let mut board = Board::new("test".to_string(), 7, vec![Point::new(2, 1)]);
+        board.set_spawn(Point::new(0, 1), Direction::Up).unwrap();
+        let mut game = Game::new(&board, 3);
+
+        assert_eq!(
+            game.snake,
+            LinkedList::from([Point::new(0, 1), Point::new(1, 1)])
+        );
+        assert_eq!(game.get_direction(), Direction::Up);
+
+        game.food = vec![Point::new(0, 0)];
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(6, 1)));
+    }
+
+    #[test]
+    fn puzzle_moves() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board.toggle_puzzle_food(Point::new(3, 5)).unwrap();
+        board.toggle_puzzle_food(Point::new(3, 6)).unwrap();
+        board.set_puzzle_moves(3);
+
+        let mut game = Game::new(&board, 3);
+        game.set_food_count(4);
+        assert_eq!(game.food, vec![Point::new(3, 5), Point::new(3, 6)]);
+        assert_eq!(game.get_moves_left(), Some(3));
+
+        assert!(game.walk());
+        assert_eq!(game.get_food_left(), 1);
+        assert!(!game.walk());
+        assert!(game.is_puzzle_cleared());
+        assert_eq!(game.get_moves(), 2);
+
+        board.set_puzzle_moves(1);
+        let mut game = Game::new(&board, 3);
+        assert!(!game.walk());
+        assert!(!game.is_puzzle_cleared());
+        assert_eq!(game.get_moves_left(), Some(0));
+    }
+
+    #[test]
+    fn objective_apples_and_timer() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board.set_objective(Some(Objective::new(2, 30)));
+
+        let mut game = Game::new(&board, 3);
+        game.food = vec![Point::new(3, 5), Point::new(3, 6)];
+        assert!(game.walk());
+        assert_eq!(game.get_apples_eaten(), 1);
+        assert!(!game.is_objective_met());
+        assert!(!game.walk());
+        assert!(game.is_objective_met());
+        assert_eq!(game.get_death(), None);
+
+        let mut game = Game::new(&board, 3);
+        game.run_out_of_time();
+        assert!(game.is_over());
+        assert_eq!(
+            game.get_death().map(|(cause, _)| *cause),
+            Some(DeathCause::OutOfTime)
+        );
+
+        let board = Board::new("endless".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+        game.run_out_of_time();
+        assert!(!game.is_over());
+    }
+
+    #[test]
+    fn gates_open_stage_by_stage() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board
+            .add_gate(Gate::new(vec![Point::new(1, 1)], 1))
+            .unwrap();
+        board
+            .add_gate(Gate::new(vec![Point::new(2, 2)], 2))
+            .unwrap();
+
+        let mut game = Game::new(&board, 3);
+        assert!(game.board.is_wall(&Point::new(1, 1)));
+        assert_eq!(game.get_cells()[2][2].get_kind(), CellKind::Gate);
+        assert_eq!(game.get_gate_progress(), Some((0, 1)));
+
+        game.food = vec![Point::new(3, 5)];
+        assert!(game.walk());
+        assert!(game.take_events().contains(&GameEvent::GateOpened {
+            cells: vec![Point::new(1, 1)]
+        }));
+        assert!(!game.board.is_wall(&Point::new(1, 1)));
+        assert!(game.board.is_wall(&Point::new(2, 2)));
+        assert_eq!(game.get_cells()[2][2].get_kind(), CellKind::Empty);
+        assert_eq!(game.get_gate_progress(), Some((0, 2)));
+
+        let snapshot = game.snapshot();
+        let resumed = snapshot.resume();
+        assert_eq!(resumed.get_gate_progress(), Some((0, 2)));
+        assert!(!resumed.board.is_wall(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn cheats() {
+        let board = Board::new("test".to_string(), 5, vec![Point::new(2, 3)]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_no_death(true);
+
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(2, 2)));
+
+        assert!(game.teleport(Point::new(4, 4)).is_ok());
+        assert_eq!(
+            game.snake,
+            LinkedList::from([Point::new(4, 4), Point::new(4, 3)])
+        );
+        assert!(game.teleport(Point::new(2, 4)).is_err());
+
+        assert!(game.place_food(Point::new(0, 0)).is_ok());
+        assert_eq!(game.food, vec![Point::new(0, 0)]);
+        assert!(game.place_food(Point::new(2, 3)).is_err());
+    }
+
+    #[test]
+    fn zen_bounces_off_walls() {
+        let board = Board::new("test".to_string(), 5, vec![Point::new(2, 3)]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_zen(true);
+        let heading = game.get_direction();
+
+        assert!(game.walk());
+        let head = game.snake.front().unwrap().clone();
+        assert!(head == Point::new(1, 2) || head == Point::new(3, 2));
+        assert_ne!(game.get_direction(), heading);
+    }
+
+    #[test]
+    fn checkpoints_respawn_at_a_cost() {
+        let mut board = Board::new("test".to_string(), 10, vec![Point::new(5, 7)]);
+        board.set_spawn(Point::new(5, 2), Direction::Right).unwrap();
+        board.toggle_checkpoint(Point::new(5, 4)).unwrap();
+        let mut game = Game::with_seed(&board, 2, 1);
+        assert_eq!(game.get_lives(), CHECKPOINT_LIVES);
+
+        let mut deaths = 0;
+        let mut touched = false;
+        for _ in 0..100 {
+            let alive = game.walk();
+            let events = game.take_events();
+            touched |= events.contains(&GameEvent::Checkpoint {
+                at: Point::new(5, 4),
+            });
+            if events.contains(&GameEvent::Respawned) {
+                deaths += 1;
+                assert_eq!(game.snake.front(), Some(&Point::new(5, 4)));
+            }
+            if !alive {
+                break;
+            }
+        }
+        assert!(touched);
+        assert_eq!(deaths, CHECKPOINT_LIVES);
+        assert_eq!(game.get_lives(), 0);
+
+        let mut hardcore = Game::with_seed(&board, 2, 1);
+        hardcore.set_hardcore(true);
+        assert_eq!(hardcore.get_lives(), 0);
+    }
+
+    #[test]
+    fn moving_food_drifts_to_free_cells() {
+        let walls = vec![Point::new(0, 1), Point::new(1, 0)];
+        let board = Board::new("test".to_string(), 10, walls);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_moving_food(true);
+        game.set_no_death(true);
+        game.food = vec![Point::new(0, 0)];
+
+        // Walled in on two sides, it can only drift across the wrapped edges.
+        for _ in 0..FOOD_DRIFT_TICKS * 5 {
+            game.walk();
+            let food = &game.food[0];
+            assert!(!board.is_wall(food));
+            assert!(!game.snake.contains(food));
+        }
+
+        game.food = vec![Point::new(7, 7)];
+        for _ in 0..FOOD_DRIFT_TICKS {
+            game.walk();
+        }
+        assert_ne!(game.food, [Point::new(7, 7)]);
+    }
+
+    #[test]
+    fn mice_flee_and_escape() {
+        let board = Board::new("test".to_string(), 10, vec![]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_mice(true);
+        game.food = vec![Point::new(0, 0)];
+
+        // Right in front of the head on a tick it does not move.
+        game.mouse = Some((Point::new(4, 5), MOUSE_TICKS - 1));
+        assert!(game.walk());
+        assert_eq!(game.get_score(), MOUSE_SCORE);
+        assert_eq!(game.get_mouse_ticks_left(), None);
+
+        game.mouse = Some((Point::new(4, 8), MOUSE_TICKS));
+        for _ in 0..MOUSE_TICKS {
+            let (before, _) = game.mouse.clone().unwrap();
+            game.walk();
+            let head = game.snake.front().unwrap().clone();
+            let (after, _) = game.mouse.clone().unwrap();
+            assert!(Game::distance(&after, &head, 10) >= Game::distance(&before, &head, 10) - 1);
+        }
+        assert_eq!(game.get_mouse_ticks_left(), Some(0));
+        game.walk();
+        assert_eq!(game.get_mouse_ticks_left(), None);
+    }
+
+    #[test]
+    fn a_short_snake_dies_next_to_a_mouse() {
+        let board = Board::new("test".to_string(), 10, vec![Point::new(4, 5)]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_mice(true);
+        game.food = vec![Point::new(0, 0)];
+        game.mouse = Some((Point::new(3, 4), MOUSE_TICKS));
+
+        assert!(!game.walk());
+        assert!(game.snake.is_empty());
+        assert_eq!(game.get_mouse_ticks_left(), Some(MOUSE_TICKS));
+    }
+
+    #[test]
+    fn roaming_snakes_wander_and_kill() {
+        let board = Board::new("test".to_string(), 12, vec![Point::new(0, 0)]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.set_no_death(true);
+        game.set_hazards(3);
+        assert_eq!(game.get_hazard_count(), 3);
+
+        for _ in 0..40 {
+            game.walk();
+            for (hazard, _) in &game.hazards {
+                assert!(hazard.len() <= HAZARD_LENGTH);
+                for cell in hazard {
+                    assert!(!board.is_wall(cell));
+                    assert!(!game.snake.contains(cell));
+                    assert!(!game.food.contains(cell));
+                }
+            }
+        }
+
+        let mut game = Game::with_seed(&board, 2, 1);
+        let ahead = game
+            .snake
+            .front()
+            .unwrap()
+            .get_neighbor(&Direction::Right, 12);
+        game.hazards = vec![(LinkedList::from([ahead]), Direction::Up)];
+        assert!(!game.walk());
+    }
+
+    #[test]
+    fn pellets_cover_the_free_cells() {
+        let board = Board::new("test".to_string(), 5, vec![Point::new(0, 0)]);
+        let mut game = Game::with_seed(&board, 2, 1);
+        game.start_pellets();
+        assert!(game.food.is_empty());
+        assert_eq!(game.get_pellets_left(), Some(25 - 1 - 2));
+
+        assert!(game.walk());
+        assert_eq!(game.get_pellets_left(), Some(21));
+
+        game.pellets = Some(vec![game
+            .snake
+            .front()
+            .unwrap()
+            .get_neighbor(&Direction::Right, 5)]);
+        assert!(!game.walk());
+        assert!(game.is_pellets_cleared());
+        assert!(game.get_replay().get_rules().pellets);
+    }
+
+    #[test]
+    fn maze_walls_grow_with_the_score_without_closing_the_board() {
+        let board = Board::new("test".to_string(), 7, vec![]);
+        let mut game = Game::with_seed(&board, 3, 5);
+        game.start_maze();
+        game.score = MAZE_SCORE_STEP - 1;
+        game.grow_walls();
+        assert_eq!(game.get_grown_walls(), Some(0));
+        assert!(game.board.get_walls().is_empty());
+
+        game.score = MAZE_SCORE_STEP * 12;
+        game.grow_walls();
+        assert_eq!(game.get_grown_walls(), Some(12));
+        let walls = game.board.get_walls().to_vec();
+        assert_eq!(walls.len(), 12);
+
+        let head = game.snake.front().unwrap().clone();
+        for wall in &walls {
+            assert!(Game::distance(wall, &head, 7) >= MAZE_SAFE_DISTANCE);
+            assert!(!game.snake.contains(wall) && !game.food.contains(wall));
+        }
+        assert_eq!(game.count_reachable(&head, &[]), 49 - walls.len());
+        assert!(game.get_replay().get_rules().maze);
+        assert_eq!(game.snapshot().resume().get_grown_walls(), Some(12));
+    }
+
+    #[test]
+    fn edges_kill_without_wrap() {
+        let mut board = Board::new("test".to_string(), 5, vec![]);
+        board.set_spawn(Point::new(2, 3), Direction::Right).unwrap();
+        let mut game = Game::with_seed(&board, 2, 1);
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(game.snake.front(), Some(&Point::new(2, 0)));
+
+        board.set_rules(BoardRules::new(Some(false), None, None, None));
+        let mut game = Game::with_seed(&board, 2, 1);
+        assert!(game.walk());
+        assert!(!game.walk());
+    }
+
+    #[test]
+    fn snapshot_resumes_where_it_left() {
+        let board = Board::new("test".to_string(), 12, vec![Point::new(6, 6)]);
+        let mut game = Game::with_seed(&board, 3, 9);
+        game.set_food_count(3);
+        game.set_shifting_walls(1);
+        game.set_mice(true);
+        game.set_hazards(2);
+        for _ in 0..20 {
+            game.walk();
+        }
+
+        let json = serde_json::to_string(&game.snapshot()).unwrap();
+        let snapshot: GameSnapshot = serde_json::from_str(&json).unwrap();
+        let mut resumed = snapshot.resume();
+        for turn in [Direction::Down, Direction::Left, Direction::Up] {
+            game.rotation(turn);
+            resumed.rotation(turn);
+            for _ in 0..5 {
+                assert_eq!(game.walk(), resumed.walk());
+                assert_eq!(game.get_cells(), resumed.get_cells());
+                assert_eq!(game.take_events(), resumed.take_events());
+            }
+        }
+        assert_eq!(game.get_score(), resumed.get_score());
+    }
 }
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadAction {
+    Confirm,
+    Back,
+    Pause,
+    Boost,
+}
+
+impl GamepadAction {
+    pub const ALL: [GamepadAction; 4] = [
+        GamepadAction::Confirm,
+        GamepadAction::Back,
+        GamepadAction::Pause,
+        GamepadAction::Boost,
+    ];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            GamepadAction::Confirm => "confirm",
+            GamepadAction::Back => "back",
+            GamepadAction::Pause => "pause",
+            GamepadAction::Boost => "boost",
+        }
+    }
+}
+
+/// Which controller button triggers each action; directions always come from the d-pad/stick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct GamepadMapping {
+    confirm: u8,
+    back: u8,
+    pause: u8,
+    boost: u8,
+}
+
+impl Default for GamepadMapping {
+    fn default() -> Self {
+        Self {
+            confirm: 0,
+            back: 1,
+            pause: 7,
+            boost: 2,
+        }
+    }
+}
+
+impl GamepadMapping {
+    pub fn get_button(&self, action: GamepadAction) -> u8 {
+        match action {
+            GamepadAction::Confirm => self.confirm,
+            GamepadAction::Back => self.back,
+            GamepadAction::Pause => self.pause,
+            GamepadAction::Boost => self.boost,
+        }
+    }
+
+    pub fn set_button(&mut self, action: GamepadAction, button: u8) {
+        match action {
+            GamepadAction::Confirm => self.confirm = button,
+            GamepadAction::Back => self.back = button,
+            GamepadAction::Pause => self.pause = button,
+            GamepadAction::Boost => self.boost = button,
+        }
+    }
+
+    pub fn get_action(&self, button: u8) -> Option<GamepadAction> {
+        GamepadAction::ALL
+            .into_iter()
+            .find(|action| self.get_button(*action) == button)
+    }
+}
+
+#[cfg(test)]
+mod test_gamepad {
+    use super::{GamepadAction, GamepadMapping};
+
+    #[test]
+    fn remap_button() {
+        let mut mapping = GamepadMapping::default();
+        assert_eq!(mapping.get_action(0), Some(GamepadAction::Confirm));
+        assert_eq!(mapping.get_action(5), None);
+
+        mapping.set_button(GamepadAction::Confirm, 5);
+        assert_eq!(mapping.get_action(5), Some(GamepadAction::Confirm));
+        assert_eq!(mapping.get_action(0), None);
+    }
+}
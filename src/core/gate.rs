@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use super::Point;
+
+/// Cells that block the way like walls until the snake eats `apples` apples in the stage
+/// before them, then open for good.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Gate {
+    cells: Vec<Point>,
+    apples: u16,
+}
+
+impl Gate {
+    pub fn new(cells: Vec<Point>, apples: u16) -> Self {
+        Self {
+            cells,
+            apples: apples.max(1),
+        }
+    }
+
+    pub fn get_cells(&self) -> &[Point] {
+        &self.cells
+    }
+
+    /// The apples to eat in the stage before the gate opens.
+    pub fn get_apples(&self) -> u16 {
+        self.apples
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.cells.contains(point)
+    }
+}
@@ -0,0 +1,110 @@
+//! The text of the interface, looked up by key from a catalog per language. English is built
+//! in; other languages are JSON files of keys to text in `./src/lang`, named by language code.
+
+use std::{collections::HashMap, fmt::Display, fs};
+
+use super::store;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+const LANGUAGE_DIR: &str = "./src/lang";
+const ENGLISH: &str = include_str!("../lang/en.json");
+
+#[derive(Debug, Clone)]
+pub struct Messages {
+    language: String,
+    messages: HashMap<String, String>,
+    /// The English text, for keys a catalog has not translated yet.
+    fallback: HashMap<String, String>,
+}
+
+impl Messages {
+    /// The catalog of `language`. A missing or unreadable file leaves everything in English;
+    /// an unreadable one is reported through `take_store_errors`.
+    pub fn load(language: &str) -> Self {
+        let fallback: HashMap<String, String> = serde_json::from_str(ENGLISH).unwrap();
+        let messages = match language {
+            DEFAULT_LANGUAGE => fallback.clone(),
+            _ => store::load(&language_path(language), HashMap::new),
+        };
+        Self {
+            language: language.to_string(),
+            messages,
+            fallback,
+        }
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    /// The text of `key`, the key itself when no catalog has it.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// The text of `key` with each `{name}` in it replaced by the value of `name` in `args`.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), &value.to_string())
+            })
+    }
+
+    /// The codes of the languages there are catalogs for, English first.
+    pub fn list_languages() -> Vec<String> {
+        let mut languages: Vec<String> = fs::read_dir(LANGUAGE_DIR)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                Some(name.strip_suffix(".json")?.to_string())
+            })
+            .filter(|code| code != DEFAULT_LANGUAGE)
+            .collect();
+        languages.sort();
+        languages.insert(0, DEFAULT_LANGUAGE.to_string());
+        languages
+    }
+}
+
+fn language_path(language: &str) -> String {
+    format!("{LANGUAGE_DIR}/{language}.json")
+}
+
+#[cfg(test)]
+mod test_messages {
+    use std::collections::HashMap;
+
+    use super::Messages;
+
+    #[test]
+    fn falls_back_to_english() {
+        let mut messages = Messages::load("en");
+        assert_eq!(messages.get("language.name"), "English");
+        assert_eq!(messages.get("no.such.key"), "no.such.key");
+
+        messages.messages = HashMap::from([(
+            "settings.volume".to_string(),
+            "Lautstärke: {volume}%".to_string(),
+        )]);
+        assert_eq!(
+            messages.format("settings.volume", &[("volume", &40)]),
+            "Lautstärke: 40%"
+        );
+        assert_eq!(messages.get("language.name"), "English");
+    }
+
+    #[test]
+    fn missing_catalog_is_english() {
+        let messages = Messages::load("xx");
+        assert_eq!(messages.get_language(), "xx");
+        assert_eq!(
+            messages.format("settings.volume", &[("volume", &40)]),
+            "Volume: 40%"
+        );
+        assert_eq!(Messages::list_languages()[0], "en");
+    }
+}
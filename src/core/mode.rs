@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// The way a game is played. Scores of different modes are kept apart.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub enum Mode {
+    #[default]
+    Classic,
+    /// A race to grow the snake to this length, scored by the time it took.
+    TargetLength(u16),
+    /// Steering around with no death and no score; never recorded.
+    Zen,
+    /// Clearing the board of pellets, scored by the time it took.
+    Pellets,
+    /// Walls grow on the board as the score goes up.
+    Maze,
+    /// Top speed, one life and no pausing, with a streak kept apart from the scores.
+    Hardcore,
+}
+
+impl Mode {
+    pub fn get_name(&self) -> String {
+        match self {
+            Self::Classic => "Classic".to_string(),
+            Self::TargetLength(target) => format!("Race to {target}"),
+            Self::Zen => "Zen".to_string(),
+            Self::Hardcore => "Hardcore".to_string(),
+            Self::Pellets => "Pellets".to_string(),
+            Self::Maze => "Maze".to_string(),
+        }
+    }
+
+    /// Whether scores are times, where lower is better.
+    pub fn is_timed(&self) -> bool {
+        matches!(self, Self::TargetLength(_) | Self::Pellets)
+    }
+
+    /// A score as it is shown: points, or a time in tenths of a second.
+    pub fn format_score(&self, score: u16) -> String {
+        match self.is_timed() {
+            true => format!("{}.{}s", score / 10, score % 10),
+            false => score.to_string(),
+        }
+    }
+}
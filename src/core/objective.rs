@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// A goal a board sets instead of an endless game: eat `apples` apples before `seconds` of
+/// game time run out.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Objective {
+    apples: u16,
+    seconds: u16,
+}
+
+impl Objective {
+    pub fn new(apples: u16, seconds: u16) -> Self {
+        Self {
+            apples: apples.max(1),
+            seconds: seconds.max(1),
+        }
+    }
+
+    pub fn get_apples(&self) -> u16 {
+        self.apples
+    }
+
+    pub fn get_seconds(&self) -> u16 {
+        self.seconds
+    }
+
+    /// How the objective is shown with the board.
+    pub fn describe(&self) -> String {
+        format!("eat {} apples in {}s", self.apples, self.seconds)
+    }
+}
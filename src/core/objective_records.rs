@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::store;
+
+pub(super) const JSON_FILE_PATH: &str = "./src/objectives.json";
+
+/// How the games on one objective board went.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct ObjectiveRecord {
+    wins: u32,
+    losses: u32,
+    /// The fastest win, in tenths of a second.
+    best: Option<u16>,
+}
+
+impl ObjectiveRecord {
+    pub fn get_wins(&self) -> u32 {
+        self.wins
+    }
+
+    pub fn get_losses(&self) -> u32 {
+        self.losses
+    }
+
+    pub fn get_best(&self) -> Option<u16> {
+        self.best
+    }
+}
+
+/// The wins and losses of each board with an objective, kept apart from the endless scores.
+#[derive(Debug)]
+pub struct ObjectiveRecords {
+    records: HashMap<String, ObjectiveRecord>,
+}
+
+impl ObjectiveRecords {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        let records = store::load(path, HashMap::new);
+        Self { records }
+    }
+
+    /// Records a win in `time` tenths of a second, or a loss for `None`, returning whether
+    /// the win was the fastest yet.
+    pub fn add(&mut self, board_name: String, time: Option<u16>) -> bool {
+        let record = self.records.entry(board_name).or_default();
+        let Some(time) = time else {
+            record.losses += 1;
+            return false;
+        };
+        record.wins += 1;
+        if record.best.is_none_or(|best| time <= best) {
+            record.best = Some(time);
+            return true;
+        }
+        false
+    }
+
+    pub fn get(&self, board_name: &str) -> Option<ObjectiveRecord> {
+        self.records.get(board_name).copied()
+    }
+}
+
+impl Default for ObjectiveRecords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ObjectiveRecords {
+    fn drop(&mut self) {
+        store::save(JSON_FILE_PATH, &self.records);
+    }
+}
+
+#[cfg(test)]
+mod test_objective_records {
+    use std::{collections::HashMap, mem::ManuallyDrop};
+
+    use super::ObjectiveRecords;
+    use crate::core::store;
+
+    #[test]
+    fn counts_wins_and_losses_apart() {
+        // `ManuallyDrop` keeps the test from writing objectives.json.
+        let mut records = ManuallyDrop::new(ObjectiveRecords {
+            records: HashMap::new(),
+        });
+
+        assert!(!records.add("sprint".to_string(), None));
+        assert!(records.add("sprint".to_string(), Some(300)));
+        assert!(!records.add("sprint".to_string(), Some(350)));
+        assert!(records.add("sprint".to_string(), Some(250)));
+
+        let record = records.get("sprint").unwrap();
+        assert_eq!(record.get_wins(), 3);
+        assert_eq!(record.get_losses(), 1);
+        assert_eq!(record.get_best(), Some(250));
+        assert_eq!(records.get("other"), None);
+    }
+
+    #[test]
+    fn a_corrupt_file_starts_empty() {
+        let path = store::test_file("objective_records.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing objectives.json.
+        let objective = ManuallyDrop::new(ObjectiveRecords::load(&path));
+        assert!(objective.records.is_empty());
+        assert!(store::is_unreadable(&path));
+    }
+}
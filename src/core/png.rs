@@ -0,0 +1,212 @@
+//! Draws a board as a PNG picture. The image is written uncompressed (stored deflate blocks),
+//! which every viewer reads and which needs no compression library; boards are small.
+
+use super::{Board, CellKind, WallColor};
+
+/// Pixels per side of a cell, fewer on boards that would be wider than `MAX_SIDE`.
+pub const CELL_PIXELS: usize = 8;
+const MAX_SIDE: usize = 1024;
+
+const EMPTY: [u8; 3] = [30, 30, 30];
+const BORDER: [u8; 3] = [128, 128, 128];
+const WALL: [u8; 3] = [200, 200, 200];
+const CHECKPOINT: [u8; 3] = [230, 200, 40];
+const GATE: [u8; 3] = [200, 90, 200];
+const SPAWN: [u8; 3] = [60, 200, 80];
+/// The most a stored deflate block holds.
+const MAX_BLOCK: usize = 65535;
+
+/// The board with its border, walls, gates, checkpoints and snake spawn, as a PNG file.
+pub fn board_png(board: &Board) -> Vec<u8> {
+    let mut cells: Vec<Vec<[u8; 3]>> = board
+        .get_cells()
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell.get_kind() {
+                    CellKind::Border => BORDER,
+                    CellKind::Wall => cell.get_tint().map_or(WALL, rgb),
+                    CellKind::Checkpoint => CHECKPOINT,
+                    CellKind::Gate => GATE,
+                    _ => EMPTY,
+                })
+                .collect()
+        })
+        .collect();
+    if let Some((spawn, _)) = board.get_spawn() {
+        cells[(spawn.get_x() + 1) as usize][(spawn.get_y() + 1) as usize] = SPAWN;
+    }
+
+    let scale = (MAX_SIDE / cells.len()).clamp(1, CELL_PIXELS);
+    let side = cells.len() * scale;
+    let mut pixels = Vec::with_capacity(side * side * 3);
+    for row in &cells {
+        for _ in 0..scale {
+            for color in row {
+                for _ in 0..scale {
+                    pixels.extend_from_slice(color);
+                }
+            }
+        }
+    }
+    encode(side as u32, side as u32, &pixels)
+}
+
+fn rgb(color: WallColor) -> [u8; 3] {
+    match color {
+        WallColor::Red => [205, 49, 49],
+        WallColor::Green => [13, 188, 121],
+        WallColor::Blue => [36, 114, 200],
+        WallColor::Yellow => [229, 229, 16],
+        WallColor::Magenta => [188, 63, 188],
+        WallColor::Cyan => [17, 168, 205],
+        WallColor::White => [229, 229, 229],
+        WallColor::Gray => [118, 118, 118],
+    }
+}
+
+/// An 8-bit RGB image of `width`x`height` pixels, given row by row.
+fn encode(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let row_len = width as usize * 3;
+    let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+    for row in pixels.chunks(row_len) {
+        // Filter type 0: the row as it is.
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGB, deflate, no filtering choice, no interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&[kind.as_slice(), data].concat());
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(MAX_BLOCK).collect();
+    for (index, block) in blocks.iter().enumerate() {
+        let last = index + 1 == blocks.len();
+        zlib.push(last as u8);
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    if blocks.is_empty() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod test_png {
+    use super::{adler32, board_png, crc32, CELL_PIXELS, SPAWN, WALL};
+    use crate::core::{Board, Direction, Point};
+
+    /// The chunks of a PNG, checking each one's CRC.
+    fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut chunks = Vec::new();
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc32(&[kind, data].concat()), crc);
+            chunks.push((String::from_utf8(kind.to_vec()).unwrap(), data.to_vec()));
+            rest = &rest[12 + len..];
+        }
+        chunks
+    }
+
+    /// The bytes of stored deflate blocks, checking the checksum.
+    fn inflate_stored(zlib: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut rest = &zlib[2..];
+        loop {
+            let last = rest[0] & 1 == 1;
+            let len = u16::from_le_bytes([rest[1], rest[2]]) as usize;
+            data.extend_from_slice(&rest[5..5 + len]);
+            rest = &rest[5 + len..];
+            if last {
+                break;
+            }
+        }
+        assert_eq!(u32::from_be_bytes(rest.try_into().unwrap()), adler32(&data));
+        data
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn draws_the_board() {
+        let mut board = Board::new("test".to_string(), 40, vec![Point::new(0, 0)]);
+        board.set_spawn(Point::new(2, 3), Direction::Right).unwrap();
+
+        let chunks = chunks(&board_png(&board));
+        let names: Vec<&str> = chunks.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["IHDR", "IDAT", "IEND"]);
+
+        let side = 42 * CELL_PIXELS;
+        let header = &chunks[0].1;
+        assert_eq!(
+            u32::from_be_bytes(header[..4].try_into().unwrap()),
+            side as u32
+        );
+        assert_eq!(
+            u32::from_be_bytes(header[4..8].try_into().unwrap()),
+            side as u32
+        );
+
+        // Big enough to take several stored blocks.
+        let raw = inflate_stored(&chunks[1].1);
+        assert_eq!(raw.len(), side * (side * 3 + 1));
+        let pixel = |x: usize, y: usize| {
+            let at = x * (side * 3 + 1) + 1 + y * 3;
+            [raw[at], raw[at + 1], raw[at + 2]]
+        };
+        assert_eq!(pixel(CELL_PIXELS, CELL_PIXELS), WALL);
+        assert_eq!(pixel(3 * CELL_PIXELS + 1, 4 * CELL_PIXELS + 1), SPAWN);
+    }
+}
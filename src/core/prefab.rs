@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use super::Point;
+
+/// A reusable wall shape for the board editor, stored relative to its top-left corner.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Prefab {
+    name: String,
+    cells: Vec<Point>,
+}
+
+impl Prefab {
+    pub fn new(name: String, cells: Vec<Point>) -> Self {
+        let min_x = cells.iter().map(Point::get_x).min().unwrap_or(0);
+        let min_y = cells.iter().map(Point::get_y).min().unwrap_or(0);
+        let cells = cells
+            .iter()
+            .map(|p| Point::new(p.get_x() - min_x, p.get_y() - min_y))
+            .fold(Vec::new(), |mut cells, p| {
+                if !cells.contains(&p) {
+                    cells.push(p);
+                }
+                cells
+            });
+
+        Self { name, cells }
+    }
+
+    /// The shapes every editor starts with; the border frames a board of `board_size`.
+    pub fn builtins(board_size: u16) -> Vec<Self> {
+        vec![
+            Self::parse("Room", &["#####", "#...#", "#....", "#...#", "#####"]),
+            Self::parse(
+                "Spiral",
+                &[
+                    "#######", "#......", "#.#####", "#.#...#", "#.#.#.#", "#...#.#", "#####.#",
+                ],
+            ),
+            Self::parse(
+                "Chicane",
+                &["###....", "..#....", "..###..", "....#..", "....###"],
+            ),
+            Self::frame("Border", board_size),
+        ]
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_cells(&self) -> &[Point] {
+        &self.cells
+    }
+
+    /// Turned 90° clockwise.
+    pub fn rotated(&self) -> Self {
+        let height = self.height();
+        let cells = self
+            .cells
+            .iter()
+            .map(|p| Point::new(p.get_y(), height - 1 - p.get_x()))
+            .collect();
+        Self::new(self.name.clone(), cells)
+    }
+
+    /// Mirrored left to right.
+    pub fn flipped(&self) -> Self {
+        let width = self.width();
+        let cells = self
+            .cells
+            .iter()
+            .map(|p| Point::new(p.get_x(), width - 1 - p.get_y()))
+            .collect();
+        Self::new(self.name.clone(), cells)
+    }
+}
+
+impl Prefab {
+    fn parse(name: &str, rows: &[&str]) -> Self {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(x, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(y, _)| Point::new(x as i16, y as i16))
+            })
+            .collect();
+        Self::new(name.to_string(), cells)
+    }
+
+    fn frame(name: &str, size: u16) -> Self {
+        let last = size as i16 - 1;
+        let cells = (0..size as i16)
+            .flat_map(|i| {
+                [
+                    Point::new(0, i),
+                    Point::new(last, i),
+                    Point::new(i, 0),
+                    Point::new(i, last),
+                ]
+            })
+            .collect();
+        Self::new(name.to_string(), cells)
+    }
+
+    fn height(&self) -> i16 {
+        self.cells.iter().map(|p| p.get_x() + 1).max().unwrap_or(0)
+    }
+
+    fn width(&self) -> i16 {
+        self.cells.iter().map(|p| p.get_y() + 1).max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test_prefab {
+    use super::Prefab;
+    use crate::core::Point;
+
+    #[test]
+    fn rotate_and_flip() {
+        let prefab = Prefab::new(
+            "L".to_string(),
+            vec![Point::new(5, 5), Point::new(6, 5), Point::new(6, 6)],
+        );
+        assert_eq!(
+            prefab.get_cells(),
+            &[Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)]
+        );
+
+        assert_eq!(
+            prefab.rotated().get_cells(),
+            &[Point::new(0, 1), Point::new(0, 0), Point::new(1, 0)]
+        );
+        assert_eq!(
+            prefab.flipped().get_cells(),
+            &[Point::new(0, 1), Point::new(1, 1), Point::new(1, 0)]
+        );
+        assert_eq!(prefab.rotated().rotated().rotated().rotated(), prefab);
+    }
+
+    #[test]
+    fn border_frames_the_board() {
+        let border = Prefab::builtins(4).pop().unwrap();
+        assert_eq!(border.get_cells().len(), 12);
+    }
+}
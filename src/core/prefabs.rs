@@ -0,0 +1,71 @@
+use super::{store, Error, Prefab};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/prefabs.json";
+
+/// Prefabs saved from editor selections.
+#[derive(Debug)]
+pub struct Prefabs {
+    prefabs: Vec<Prefab>,
+}
+
+impl Prefabs {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        let prefabs: Vec<Prefab> = store::load(path, Vec::new);
+
+        Self { prefabs }
+    }
+
+    pub fn add(&mut self, prefab: Prefab) -> Result<(), Error> {
+        if self
+            .prefabs
+            .iter()
+            .any(|p| p.get_name() == prefab.get_name())
+        {
+            return Err(Error::NameTaken {
+                kind: "Prefab",
+                name: prefab.get_name().to_string(),
+            });
+        }
+
+        self.prefabs.push(prefab);
+        Ok(())
+    }
+
+    pub fn get_all(&self) -> &[Prefab] {
+        &self.prefabs
+    }
+}
+
+impl Default for Prefabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Prefabs {
+    fn drop(&mut self) {
+        store::save(JSON_FILE_PATH, &self.prefabs);
+    }
+}
+
+#[cfg(test)]
+mod test_prefabs {
+    use std::mem::ManuallyDrop;
+
+    use super::Prefabs;
+    use crate::core::store;
+
+    #[test]
+    fn a_corrupt_file_starts_empty() {
+        let path = store::test_file("prefabs.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing prefabs.json.
+        let prefabs = ManuallyDrop::new(Prefabs::load(&path));
+        assert!(prefabs.prefabs.is_empty());
+        assert!(store::is_unreadable(&path));
+    }
+}
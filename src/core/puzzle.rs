@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use super::Point;
+
+/// Fixed apples to collect within a number of moves, replacing the random food of a board.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    food: Vec<Point>,
+    max_moves: u16,
+}
+
+impl Puzzle {
+    pub fn new(food: Vec<Point>, max_moves: u16) -> Self {
+        Self { food, max_moves }
+    }
+
+    pub fn get_food(&self) -> &[Point] {
+        &self.food
+    }
+
+    pub fn get_max_moves(&self) -> u16 {
+        self.max_moves
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use super::store;
+
+pub(super) const JSON_FILE_PATH: &str = "./src/puzzles.json";
+
+/// The fewest moves each puzzle board has been cleared in.
+#[derive(Debug)]
+pub struct PuzzleRecords {
+    records: HashMap<String, u16>,
+}
+
+impl PuzzleRecords {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        let records = store::load(path, HashMap::new);
+        Self { records }
+    }
+
+    /// Records a clear, returning whether it beat the previous best.
+    pub fn add(&mut self, board_name: String, moves: u16) -> bool {
+        let best = self.records.entry(board_name).or_insert(moves);
+        if moves < *best {
+            *best = moves;
+            return true;
+        }
+        moves == *best
+    }
+
+    pub fn get(&self, board_name: &str) -> Option<u16> {
+        self.records.get(board_name).copied()
+    }
+}
+
+impl Default for PuzzleRecords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for PuzzleRecords {
+    fn drop(&mut self) {
+        store::save(JSON_FILE_PATH, &self.records);
+    }
+}
+
+#[cfg(test)]
+mod test_puzzle_records {
+    use std::{collections::HashMap, mem::ManuallyDrop};
+
+    use super::PuzzleRecords;
+    use crate::core::store;
+
+    #[test]
+    fn keeps_the_fewest_moves() {
+        // `ManuallyDrop` keeps the test from writing puzzles.json.
+        let mut records = ManuallyDrop::new(PuzzleRecords {
+            records: HashMap::new(),
+        });
+
+        assert!(records.add("maze".to_string(), 30));
+        assert!(!records.add("maze".to_string(), 40));
+        assert!(records.add("maze".to_string(), 25));
+        assert_eq!(records.get("maze"), Some(25));
+        assert_eq!(records.get("other"), None);
+    }
+
+    #[test]
+    fn a_corrupt_file_starts_empty() {
+        let path = store::test_file("puzzle_records.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing puzzles.json.
+        let puzzle = ManuallyDrop::new(PuzzleRecords::load(&path));
+        assert!(puzzle.records.is_empty());
+        assert!(store::is_unreadable(&path));
+    }
+}
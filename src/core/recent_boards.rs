@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use super::store;
+
+pub(super) const JSON_FILE_PATH: &str = "./src/recent.json";
+
+/// When each board was last played, in seconds since the Unix epoch.
+#[derive(Debug)]
+pub struct RecentBoards {
+    played: HashMap<String, u64>,
+}
+
+impl RecentBoards {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(JSON_FILE_PATH)
+    }
+
+    fn load(path: &str) -> Self {
+        let played = store::load(path, HashMap::new);
+        Self { played }
+    }
+
+    pub fn add(&mut self, board_name: String, at: u64) {
+        self.played.insert(board_name, at);
+    }
+
+    /// Up to `limit` board names, the last played first.
+    pub fn get_recent(&self, limit: usize) -> Vec<String> {
+        let mut played: Vec<_> = self.played.iter().collect();
+        played.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        played
+            .into_iter()
+            .take(limit)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+impl Default for RecentBoards {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RecentBoards {
+    fn drop(&mut self) {
+        store::save(JSON_FILE_PATH, &self.played);
+    }
+}
+
+#[cfg(test)]
+mod test_recent_boards {
+    use std::{collections::HashMap, mem::ManuallyDrop};
+
+    use super::RecentBoards;
+    use crate::core::store;
+
+    #[test]
+    fn last_played_first() {
+        // `ManuallyDrop` keeps the test from writing recent.json.
+        let mut recent = ManuallyDrop::new(RecentBoards {
+            played: HashMap::new(),
+        });
+
+        recent.add("maze".to_string(), 10);
+        recent.add("open".to_string(), 20);
+        recent.add("spiral".to_string(), 15);
+        recent.add("maze".to_string(), 30);
+
+        assert_eq!(recent.get_recent(2), vec!["maze", "open"]);
+        assert_eq!(recent.get_recent(5).len(), 3);
+    }
+
+    #[test]
+    fn a_corrupt_file_starts_empty() {
+        let path = store::test_file("recent_boards.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing recent.json.
+        let recent = ManuallyDrop::new(RecentBoards::load(&path));
+        assert!(recent.played.is_empty());
+        assert!(store::is_unreadable(&path));
+    }
+}
@@ -0,0 +1,221 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Difficulty, Direction, Error, Game};
+
+/// Bumped whenever the format changes, so old files are refused instead of replayed wrongly.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// The settings a game was played with, which a replay needs to reproduce it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Rules {
+    pub length: u16,
+    pub apples: u8,
+    pub shifting_walls: u8,
+    /// Set for races, which end at this snake length.
+    #[serde(default)]
+    pub target_length: Option<u16>,
+    /// Set when the game was about clearing pellets.
+    #[serde(default)]
+    pub pellets: bool,
+    /// Set when walls grew with the score.
+    #[serde(default)]
+    pub maze: bool,
+    #[serde(default)]
+    pub hardcore: bool,
+    #[serde(default)]
+    pub moving_food: bool,
+    #[serde(default)]
+    pub mice: bool,
+    /// Roaming snakes on the board.
+    #[serde(default)]
+    pub hazards: u8,
+    /// Set when the player changed the speed during the game.
+    #[serde(default)]
+    pub speed_adjusted: bool,
+    /// How hard the board played, `None` in replays saved before it was kept.
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ReplayInput {
+    Turn(Direction),
+    Boost(bool),
+}
+
+/// An input applied before the game's `tick`-th step.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimedInput {
+    pub tick: u32,
+    pub input: ReplayInput,
+}
+
+/// A recorded single-player game: the board, the random seed and rules it started from, and
+/// every input in order. Replaying the inputs on a game built by `start` reproduces it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Replay {
+    version: u32,
+    board: Board,
+    seed: u64,
+    rules: Rules,
+    inputs: Vec<TimedInput>,
+    ticks: u32,
+    score: u16,
+}
+
+impl Replay {
+    pub fn new(
+        board: Board,
+        seed: u64,
+        rules: Rules,
+        inputs: Vec<TimedInput>,
+        ticks: u32,
+        score: u16,
+    ) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            board,
+            seed,
+            rules,
+            inputs,
+            ticks,
+            score,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| Error::Json(e.to_string()))?;
+        match value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == REPLAY_VERSION as u64 => {
+                serde_json::from_value(value).map_err(|e| Error::Json(e.to_string()))
+            }
+            Some(version) => Err(Error::UnsupportedReplayVersion(version)),
+            None => Err(Error::NotAReplay),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let json = fs::read_to_string(path).map_err(|e| Error::Io {
+            path: path.to_string(),
+            source: e,
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.to_json()).map_err(|e| Error::Io {
+            path: path.to_string(),
+            source: e,
+        })
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+
+    pub fn get_rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// The game as it was before the first input, on `board` (normally `get_board`).
+    pub fn start<'a>(&self, board: &'a Board) -> Game<'a> {
+        let mut game = Game::with_seed(board, self.rules.length, self.seed);
+        game.set_food_count(self.rules.apples as usize);
+        game.set_shifting_walls(self.rules.shifting_walls as u16);
+        if let Some(target) = self.rules.target_length {
+            game.set_target_length(target);
+        }
+        game.set_hardcore(self.rules.hardcore);
+        game.set_moving_food(self.rules.moving_food);
+        game.set_mice(self.rules.mice);
+        game.set_hazards(self.rules.hazards);
+        if self.rules.pellets {
+            game.start_pellets();
+        }
+        if self.rules.maze {
+            game.start_maze();
+        }
+        game
+    }
+
+    /// Feeds `game` the inputs recorded before its next step.
+    pub fn apply(&self, game: &mut Game) {
+        let tick = game.get_tick();
+        for timed in self.inputs.iter().filter(|timed| timed.tick == tick) {
+            match timed.input {
+                ReplayInput::Turn(direction) => game.rotation(direction),
+                ReplayInput::Boost(boost) => game.set_boost(boost),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_replay {
+    use super::{Replay, REPLAY_VERSION};
+    use crate::core::{Board, Direction, Error, Game, Point};
+
+    #[test]
+    fn replays_the_same_game() {
+        let board = Board::new("test".to_string(), 9, vec![Point::new(0, 0)]);
+        let mut game = Game::new(&board, 3);
+        game.set_food_count(3);
+        game.set_shifting_walls(1);
+
+        let turns = [
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+        ];
+        for tick in 0..60 {
+            game.set_boost(tick % 7 == 0);
+            if tick % 3 == 0 {
+                game.rotation(turns[tick / 3 % 4]);
+            }
+            if !game.walk() {
+                break;
+            }
+        }
+
+        let replay = Replay::from_json(&game.get_replay().to_json()).unwrap();
+        let mut replayed = replay.start(replay.get_board());
+        while replayed.get_tick() < replay.get_ticks() {
+            replay.apply(&mut replayed);
+            replayed.walk();
+        }
+
+        assert_eq!(replayed.get_score(), replay.get_score());
+        assert_eq!(replayed.get_cells(), game.get_cells());
+    }
+
+    #[test]
+    fn refuses_other_versions() {
+        let board = Board::new("test".to_string(), 5, Vec::new());
+        let json = Game::new(&board, 3)
+            .get_replay()
+            .to_json()
+            .replace(&format!("\"version\":{REPLAY_VERSION}"), "\"version\":999");
+
+        assert_eq!(
+            Replay::from_json(&json).unwrap_err(),
+            Error::UnsupportedReplayVersion(999)
+        );
+        assert_eq!(Replay::from_json("{}").unwrap_err(), Error::NotAReplay);
+    }
+}
@@ -0,0 +1,107 @@
+//! The game the player paused or left for later, kept so the main menu can carry it on.
+//! There is one at a time; it is cleared once the game ends or is continued.
+
+use std::{fs, io::ErrorKind};
+
+use serde::{Deserialize, Serialize};
+
+use super::{store, Error, Game, GameSnapshot, Mode};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/saved_game.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedGame {
+    mode: Mode,
+    /// Game time played before it was saved, without pauses.
+    elapsed_ms: u64,
+    /// When it was saved, in seconds since the Unix epoch.
+    at: u64,
+    snapshot: GameSnapshot,
+}
+
+impl SavedGame {
+    pub fn new(game: &Game, mode: Mode, elapsed_ms: u64, at: u64) -> Self {
+        Self {
+            mode,
+            elapsed_ms,
+            at,
+            snapshot: game.snapshot(),
+        }
+    }
+
+    /// The saved game, `None` when there is none. An unreadable file is reported through
+    /// `take_store_errors`.
+    pub fn load() -> Option<Self> {
+        store::load(JSON_FILE_PATH, || None)
+    }
+
+    /// Puts the game in place of the one saved before.
+    pub fn save(&self) {
+        store::save(JSON_FILE_PATH, self);
+    }
+
+    /// Forgets the saved game, so a game is never carried on twice.
+    pub fn clear() -> Result<(), Error> {
+        match fs::remove_file(JSON_FILE_PATH) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::Io {
+                path: JSON_FILE_PATH.to_string(),
+                source: e,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn get_board_name(&self) -> &str {
+        self.snapshot.get_board().get_name()
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.snapshot.get_score()
+    }
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn get_elapsed_ms(&self) -> u64 {
+        self.elapsed_ms
+    }
+
+    pub fn get_at(&self) -> u64 {
+        self.at
+    }
+
+    pub fn get_snapshot(&self) -> &GameSnapshot {
+        &self.snapshot
+    }
+}
+
+#[cfg(test)]
+mod test_saved_game {
+    use super::SavedGame;
+    use crate::core::{Board, Direction, Game, Mode};
+
+    #[test]
+    fn carries_the_game_on() {
+        let board = Board::new("open".to_string(), 12, vec![]);
+        let mut game = Game::with_seed(&board, 3, 11);
+        game.rotation(Direction::Down);
+        for _ in 0..5 {
+            game.walk();
+        }
+
+        let saved = SavedGame::new(&game, Mode::Classic, 2500, 1000);
+        let json = serde_json::to_string(&saved).unwrap();
+        let loaded: SavedGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_board_name(), "open");
+        assert_eq!(loaded.get_score(), game.get_score());
+        assert_eq!(loaded.get_elapsed_ms(), 2500);
+
+        let mut resumed = loaded.get_snapshot().resume();
+        assert_eq!(resumed.get_tick(), game.get_tick());
+        for _ in 0..5 {
+            assert_eq!(resumed.walk(), game.walk());
+        }
+        assert_eq!(resumed.get_cells(), game.get_cells());
+    }
+}
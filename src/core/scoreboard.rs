@@ -1,38 +1,475 @@
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, Write},
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
 };
 
-const JSON_FILE_PATH: &str = "./src/scoreboard.json";
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-type ScoreboardType = HashMap<String, Vec<u16>>;
+use super::{store, Difficulty, Mode, Rules, Settings};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/scoreboard.json";
+
+/// Bumped when the saved scoreboard changes in a way older games cannot read.
+pub const SCOREBOARD_VERSION: u64 = 1;
+
+/// A hardcore game needs at least this score to keep its board's streak going.
+pub const STREAK_SCORE: u16 = 10;
+
+/// The mode, difficulty and rules a score was earned with; scores are only compared within
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScoreCategory {
+    mode: Mode,
+    /// How hard the board played, `None` for scores saved before it was kept.
+    difficulty: Option<Difficulty>,
+    apples: u8,
+    shifting_walls: u8,
+    moving_food: bool,
+    mice: bool,
+    roaming_snakes: bool,
+    speed_adjusted: bool,
+}
+
+impl ScoreCategory {
+    pub fn new(mode: Mode, apples: u8, shifting_walls: u8) -> Self {
+        Self {
+            mode,
+            difficulty: None,
+            apples,
+            shifting_walls,
+            moving_food: false,
+            mice: false,
+            roaming_snakes: false,
+            speed_adjusted: false,
+        }
+    }
+
+    /// The category of a game played with `rules`.
+    pub fn from_rules(rules: &Rules) -> Self {
+        let mode = match rules.target_length {
+            _ if rules.hardcore => Mode::Hardcore,
+            _ if rules.pellets => Mode::Pellets,
+            _ if rules.maze => Mode::Maze,
+            Some(target) => Mode::TargetLength(target),
+            None => Mode::Classic,
+        };
+        Self::new(mode, rules.apples, rules.shifting_walls)
+            .with_difficulty(rules.difficulty)
+            .with_moving_food(rules.moving_food)
+            .with_mice(rules.mice)
+            .with_roaming_snakes(rules.hazards > 0)
+            .with_speed_adjusted(rules.speed_adjusted)
+    }
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn with_mode(self, mode: Mode) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn get_difficulty(&self) -> Option<Difficulty> {
+        self.difficulty
+    }
+
+    pub fn with_difficulty(self, difficulty: Option<Difficulty>) -> Self {
+        Self { difficulty, ..self }
+    }
+
+    pub fn with_apples(self, apples: u8) -> Self {
+        Self { apples, ..self }
+    }
+
+    pub fn with_moving_food(self, moving_food: bool) -> Self {
+        Self {
+            moving_food,
+            ..self
+        }
+    }
+
+    /// Mice are worth more than apples, so games with them are ranked among themselves.
+    pub fn with_mice(self, mice: bool) -> Self {
+        Self { mice, ..self }
+    }
+
+    /// Their count follows from the board's difficulty, so only whether there were any counts.
+    pub fn with_roaming_snakes(self, roaming_snakes: bool) -> Self {
+        Self {
+            roaming_snakes,
+            ..self
+        }
+    }
+
+    /// Games whose speed the player changed midway are ranked among themselves.
+    pub fn with_speed_adjusted(self, speed_adjusted: bool) -> Self {
+        Self {
+            speed_adjusted,
+            ..self
+        }
+    }
+
+    pub fn is_speed_adjusted(&self) -> bool {
+        self.speed_adjusted
+    }
+
+    pub fn format_score(&self, score: u16) -> String {
+        self.mode.format_score(score)
+    }
+
+    /// Orders scores best first: the highest, or the fastest time.
+    fn rank(&self, score: u16) -> Reverse<i32> {
+        match self.mode.is_timed() {
+            true => Reverse(-(score as i32)),
+            false => Reverse(score as i32),
+        }
+    }
+
+    /// Whether `score` is better than `record`: higher, or faster for timed modes.
+    pub fn beats(&self, score: u16, record: u16) -> bool {
+        self.rank(score) < self.rank(record)
+    }
+
+    /// How far `score` is ahead of `record`, like `+12` points or `-1.5s`.
+    pub fn format_gain(&self, score: u16, record: u16) -> String {
+        let sign = if self.mode.is_timed() { '-' } else { '+' };
+        format!("{sign}{}", self.format_score(score.abs_diff(record)))
+    }
+
+    pub fn get_name(&self) -> String {
+        let difficulty = match self.difficulty {
+            Some(difficulty) => format!(", {}", difficulty.get_name().to_lowercase()),
+            None => "".to_string(),
+        };
+        let apples = match self.apples {
+            1 => "1 apple".to_string(),
+            apples => format!("{apples} apples"),
+        };
+        let walls = match self.shifting_walls {
+            0 => "still walls".to_string(),
+            1 => "walls shift every apple".to_string(),
+            every => format!("walls shift every {every} apples"),
+        };
+        let moving = match self.moving_food {
+            true => ", moving apples",
+            false => "",
+        };
+        let mice = match self.mice {
+            true => ", mice",
+            false => "",
+        };
+        let roaming = match self.roaming_snakes {
+            true => ", roaming snakes",
+            false => "",
+        };
+        let speed = match self.speed_adjusted {
+            true => ", speed-adjusted",
+            false => "",
+        };
+        format!(
+            "{}{difficulty}, {apples}, {walls}{moving}{mice}{roaming}{speed}",
+            self.mode.get_name()
+        )
+    }
+}
+
+/// The mode of a saved game: one this game knows, or one from a newer game, kept as it was
+/// written so it survives being saved again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+enum SavedMode {
+    Known(Mode),
+    Unknown(Value),
+}
+
+/// One finished game. Fields added later need a default, so older entries still load.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+struct Entry {
+    score: u16,
+    mode: SavedMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    difficulty: Option<Difficulty>,
+    apples: u8,
+    shifting_walls: u8,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    moving_food: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    mice: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    roaming_snakes: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    speed_adjusted: bool,
+    /// The name of the snake that played it, when it had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl Entry {
+    fn new(category: ScoreCategory, score: u16) -> Self {
+        Self {
+            score,
+            mode: SavedMode::Known(category.mode),
+            difficulty: category.difficulty,
+            apples: category.apples,
+            shifting_walls: category.shifting_walls,
+            moving_food: category.moving_food,
+            mice: category.mice,
+            roaming_snakes: category.roaming_snakes,
+            speed_adjusted: category.speed_adjusted,
+            name: None,
+        }
+    }
+
+    /// `None` for a game in a mode this game does not know, which is kept but never shown.
+    fn get_category(&self) -> Option<ScoreCategory> {
+        let SavedMode::Known(mode) = self.mode else {
+            return None;
+        };
+        let category = ScoreCategory::new(mode, self.apples, self.shifting_walls)
+            .with_difficulty(self.difficulty)
+            .with_moving_food(self.moving_food)
+            .with_mice(self.mice)
+            .with_roaming_snakes(self.roaming_snakes)
+            .with_speed_adjusted(self.speed_adjusted);
+        Some(category)
+    }
+}
+
+impl Default for Entry {
+    /// The rules every game was played with before they could be changed.
+    fn default() -> Self {
+        Self::new(ScoreCategory::new(Mode::Classic, 1, 0), 0)
+    }
+}
+
+type ScoreboardType = HashMap<String, Vec<Entry>>;
+
+/// The hardcore games of a board in a row that scored at least `STREAK_SCORE`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Streak {
+    current: u32,
+    best: u32,
+}
+
+impl Streak {
+    pub fn get_current(&self) -> u32 {
+        self.current
+    }
+
+    pub fn get_best(&self) -> u32 {
+        self.best
+    }
+
+    fn record(&mut self, score: u16) {
+        match score >= STREAK_SCORE {
+            true => self.current += 1,
+            false => self.current = 0,
+        }
+        self.best = self.best.max(self.current);
+    }
+}
+
+/// The scoreboard file, migrated to the current version as it is read.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(try_from = "Value")]
+struct SavedScoreboard {
+    version: u64,
+    scores: ScoreboardType,
+    /// Added without a version bump: files without it simply have no streaks yet.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    streaks: HashMap<String, Streak>,
+}
+
+impl TryFrom<Value> for SavedScoreboard {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, String> {
+        let version = match (value.get("version"), value.get("scores")) {
+            (Some(version), Some(_)) => version.as_u64().ok_or("invalid scoreboard version")?,
+            // Before versions the file was a bare map of board names to scores.
+            _ => 0,
+        };
+
+        let streaks = match value.get("streaks") {
+            Some(streaks) if version > 0 => {
+                serde_json::from_value(streaks.clone()).map_err(|e| e.to_string())?
+            }
+            _ => HashMap::new(),
+        };
+
+        let scores = match version {
+            0 => serde_json::from_value::<HashMap<String, Vec<u16>>>(value)
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|(name, scores)| {
+                    let entries = scores
+                        .into_iter()
+                        .map(|score| Entry {
+                            score,
+                            ..Entry::default()
+                        })
+                        .collect();
+                    (name, entries)
+                })
+                .collect(),
+            SCOREBOARD_VERSION => {
+                serde_json::from_value(value["scores"].clone()).map_err(|e| e.to_string())?
+            }
+            version => {
+                return Err(format!(
+                    "scoreboard version {version} is newer than this game supports"
+                ))
+            }
+        };
+
+        Ok(Self {
+            version: SCOREBOARD_VERSION,
+            scores,
+            streaks,
+        })
+    }
+}
 
 #[derive(Debug)]
 pub struct Scoreboard {
     scoreboard: ScoreboardType,
+    streaks: HashMap<String, Streak>,
 }
 
 impl Scoreboard {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
     pub fn new() -> Self {
-        let file = File::open(JSON_FILE_PATH).unwrap();
+        Self::load(JSON_FILE_PATH)
+    }
 
-        let reader = BufReader::new(file);
-        let scoreboard: ScoreboardType = serde_json::from_reader(reader).unwrap();
-        Self { scoreboard }
+    fn load(path: &str) -> Self {
+        let saved = store::load(path, || None::<SavedScoreboard>);
+        Self::from_saved(saved)
     }
 
-    pub fn add(&mut self, board_name: String, score: u16) {
-        self.scoreboard.entry(board_name).or_default().push(score);
+    fn from_saved(saved: Option<SavedScoreboard>) -> Self {
+        match saved {
+            Some(saved) => Self {
+                scoreboard: saved.scores,
+                streaks: saved.streaks,
+            },
+            None => Self {
+                scoreboard: HashMap::new(),
+                streaks: HashMap::new(),
+            },
+        }
     }
 
-    pub fn get(&self, board_name: &str) -> Option<Vec<u16>> {
-        self.scoreboard.get(board_name).map(|s| {
-            let mut s = s.clone();
-            s.sort_unstable();
-            s.reverse();
-            s
-        })
+    /// Records a finished game; a hardcore one also extends or ends the board's streak.
+    pub fn add(&mut self, board_name: String, category: ScoreCategory, score: u16) {
+        if category.mode == Mode::Hardcore {
+            self.streaks
+                .entry(board_name.clone())
+                .or_default()
+                .record(score);
+        }
+        self.scoreboard
+            .entry(board_name)
+            .or_default()
+            .push(Entry::new(category, score));
+    }
+
+    /// Names the snake of the board's latest game; an empty name leaves it unnamed.
+    pub fn name_last(&mut self, board_name: &str, name: &str) {
+        if let Some(entry) = self
+            .scoreboard
+            .get_mut(board_name)
+            .and_then(|entries| entries.last_mut())
+        {
+            entry.name = (!name.is_empty()).then(|| name.to_string());
+        }
+    }
+
+    /// The scores of the board in `category`, best first (the fastest, for timed modes);
+    /// `None` when there are none.
+    pub fn get(&self, board_name: &str, category: ScoreCategory) -> Option<Vec<u16>> {
+        let scores = self.get_named(board_name, category)?;
+        Some(scores.into_iter().map(|(score, _)| score).collect())
+    }
+
+    /// Like [`Scoreboard::get`], with the name of the snake of each score.
+    pub fn get_named(
+        &self,
+        board_name: &str,
+        category: ScoreCategory,
+    ) -> Option<Vec<(u16, Option<String>)>> {
+        let mut scores: Vec<_> = self
+            .scoreboard
+            .get(board_name)?
+            .iter()
+            .filter(|entry| entry.get_category() == Some(category))
+            .map(|entry| (entry.score, entry.name.clone()))
+            .collect();
+        // Stable, so equal scores stay oldest first.
+        scores.sort_by_key(|(score, _)| category.rank(*score));
+        (!scores.is_empty()).then_some(scores)
+    }
+
+    pub fn get_best(&self, board_name: &str, category: ScoreCategory) -> Option<u16> {
+        self.get(board_name, category)?.first().copied()
+    }
+
+    /// The categories the board has scores in.
+    pub fn get_categories(&self, board_name: &str) -> Vec<ScoreCategory> {
+        let mut categories: Vec<_> = self
+            .scoreboard
+            .get(board_name)
+            .into_iter()
+            .flatten()
+            .filter_map(Entry::get_category)
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    pub fn get_streak(&self, board_name: &str) -> Streak {
+        self.streaks.get(board_name).copied().unwrap_or_default()
+    }
+
+    /// Every finished game leaves a score, so this is how often the board was played.
+    pub fn get_times_played(&self, board_name: &str) -> usize {
+        self.scoreboard.get(board_name).map_or(0, Vec::len)
+    }
+
+    /// Keeps the `top` best scores and the `recent` latest games of each board and category;
+    /// 0 for either keeps everything. Games in modes this game does not know are all kept.
+    pub fn prune(&mut self, top: usize, recent: usize) {
+        if top == 0 || recent == 0 {
+            return;
+        }
+
+        for entries in self.scoreboard.values_mut() {
+            let mut kept: HashSet<usize> = (0..entries.len())
+                .filter(|index| entries[*index].get_category().is_none())
+                .collect();
+            for category in entries
+                .iter()
+                .filter_map(Entry::get_category)
+                .collect::<HashSet<_>>()
+            {
+                let group: Vec<usize> = (0..entries.len())
+                    .filter(|index| entries[*index].get_category() == Some(category))
+                    .collect();
+                let mut by_score = group.clone();
+                by_score.sort_by_key(|index| category.rank(entries[*index].score));
+                let latest = group[group.len().saturating_sub(recent)..].iter().copied();
+                kept.extend(by_score.into_iter().take(top).chain(latest));
+            }
+
+            let mut index = 0;
+            entries.retain(|_| {
+                index += 1;
+                kept.contains(&(index - 1))
+            });
+        }
     }
 
     pub fn get_names(&self) -> Vec<String> {
@@ -40,10 +477,247 @@ impl Scoreboard {
     }
 }
 
+impl Default for Scoreboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for Scoreboard {
     fn drop(&mut self) {
-        let json = serde_json::to_string(&self.scoreboard).unwrap();
-        let mut file = File::create(JSON_FILE_PATH).unwrap();
-        file.write_all(json.as_bytes()).unwrap();
+        let settings = Settings::new();
+        self.prune(
+            settings.get_kept_top_scores() as usize,
+            settings.get_kept_recent_games() as usize,
+        );
+
+        let saved = SavedScoreboard {
+            version: SCOREBOARD_VERSION,
+            scores: std::mem::take(&mut self.scoreboard),
+            streaks: std::mem::take(&mut self.streaks),
+        };
+        store::save(JSON_FILE_PATH, &saved);
+    }
+}
+
+#[cfg(test)]
+mod test_scoreboard {
+    use std::mem::ManuallyDrop;
+
+    use serde_json::json;
+
+    use super::{
+        Entry, SavedScoreboard, ScoreCategory, Scoreboard, SCOREBOARD_VERSION, STREAK_SCORE,
+    };
+    use crate::core::{store, Difficulty, Mode};
+
+    fn entry(score: u16) -> Entry {
+        Entry {
+            score,
+            ..Entry::default()
+        }
+    }
+
+    fn read(value: serde_json::Value) -> Result<SavedScoreboard, String> {
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let saved = read(json!({ "maze": [3, 7], "version": [1] })).unwrap();
+        assert_eq!(saved.version, SCOREBOARD_VERSION);
+        assert_eq!(saved.scores["maze"], [entry(3), entry(7)]);
+        assert_eq!(saved.scores["version"], [entry(1)]);
+    }
+
+    #[test]
+    fn reads_what_it_writes() {
+        let saved = read(json!({ "maze": [5] })).unwrap();
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "version": SCOREBOARD_VERSION,
+                "scores": { "maze": [
+                    { "score": 5, "mode": "Classic", "apples": 1, "shifting_walls": 0 }
+                ] }
+            })
+        );
+        assert_eq!(read(json).unwrap(), saved);
+
+        let later = json!({ "version": 1, "scores": { "maze": [{ "score": 2, "mode": "x" }] } });
+        let saved = read(later).unwrap();
+        assert_eq!(saved.scores["maze"][0].score, 2);
+        assert_eq!(saved.scores["maze"][0].get_category(), None);
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(json["scores"]["maze"][0]["mode"], "x");
+
+        let before_modes = json!({ "version": 1, "scores": { "maze": [{ "score": 2 }] } });
+        assert_eq!(read(before_modes).unwrap().scores["maze"], [entry(2)]);
+    }
+
+    #[test]
+    fn get_filters_by_category() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let easy = ScoreCategory::new(Mode::Classic, 1, 0).with_difficulty(Some(Difficulty::Easy));
+        let hard = easy.with_difficulty(Some(Difficulty::Hard));
+        let race = hard.with_mode(Mode::TargetLength(20));
+        scoreboard.add("maze".to_string(), easy, 12);
+        scoreboard.add("maze".to_string(), hard, 5);
+        scoreboard.add("maze".to_string(), hard, 7);
+        scoreboard.add("maze".to_string(), race, 300);
+        scoreboard.add("other".to_string(), easy, 40);
+        let saved = json!({ "score": 9, "mode": { "Future": 1 } });
+        let unknown: Entry = serde_json::from_value(saved).unwrap();
+        scoreboard.scoreboard.get_mut("maze").unwrap().push(unknown);
+
+        assert_eq!(scoreboard.get("maze", easy), Some(vec![12]));
+        assert_eq!(scoreboard.get("maze", hard), Some(vec![7, 5]));
+        assert_eq!(scoreboard.get("maze", race), Some(vec![300]));
+        assert_eq!(scoreboard.get("maze", easy.with_difficulty(None)), None);
+        assert_eq!(scoreboard.get("missing", easy), None);
+        assert_eq!(scoreboard.get_categories("maze"), [easy, hard, race]);
+        assert_eq!(scoreboard.get_categories("other"), [easy]);
+        assert!(hard.get_name().starts_with("Classic, hard, "));
+
+        // The unknown game stays; only the older of the hard scores goes.
+        scoreboard.prune(1, 1);
+        assert_eq!(scoreboard.get_times_played("maze"), 4);
+        assert_eq!(scoreboard.get("maze", hard), Some(vec![7]));
+    }
+
+    #[test]
+    fn prune_keeps_the_best_and_the_latest() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let classic = ScoreCategory::new(Mode::Classic, 1, 0);
+        for score in [9, 1, 8, 2, 3, 4] {
+            scoreboard.add("maze".to_string(), classic, score);
+        }
+        let busy = ScoreCategory::new(Mode::Classic, 3, 0);
+        scoreboard.add("maze".to_string(), busy, 1);
+
+        scoreboard.prune(0, 1);
+        assert_eq!(scoreboard.get_times_played("maze"), 7);
+
+        scoreboard.prune(2, 2);
+        let kept: Vec<u16> = scoreboard.scoreboard["maze"]
+            .iter()
+            .map(|entry| entry.score)
+            .collect();
+        assert_eq!(kept, [9, 8, 3, 4, 1]);
+        assert_eq!(scoreboard.get("maze", busy), Some(vec![1]));
+        assert_eq!(scoreboard.get_best("maze", classic), Some(9));
+        assert_eq!(scoreboard.get_categories("maze"), [classic, busy]);
+    }
+
+    #[test]
+    fn timed_modes_rank_the_fastest_first() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let race = ScoreCategory::new(Mode::TargetLength(20), 1, 0);
+        for time in [300, 120, 450] {
+            scoreboard.add("maze".to_string(), race, time);
+        }
+
+        assert_eq!(scoreboard.get("maze", race), Some(vec![120, 300, 450]));
+        assert_eq!(scoreboard.get_best("maze", race), Some(120));
+        assert_eq!(race.format_score(123), "12.3s");
+        scoreboard.prune(1, 1);
+        assert_eq!(scoreboard.get("maze", race), Some(vec![120, 450]));
+    }
+
+    #[test]
+    fn speed_adjusted_games_are_kept_apart() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let classic = ScoreCategory::new(Mode::Classic, 1, 0);
+        let adjusted = classic.with_speed_adjusted(true);
+        scoreboard.add("maze".to_string(), classic, 4);
+        scoreboard.add("maze".to_string(), adjusted, 9);
+
+        assert_eq!(scoreboard.get_best("maze", classic), Some(4));
+        assert_eq!(scoreboard.get_best("maze", adjusted), Some(9));
+        assert!(adjusted.get_name().ends_with(", speed-adjusted"));
+
+        let mice = classic.with_mice(true);
+        scoreboard.add("maze".to_string(), mice, 15);
+        assert_eq!(scoreboard.get_best("maze", classic), Some(4));
+        assert_eq!(scoreboard.get_best("maze", mice), Some(15));
+        assert!(mice.get_name().ends_with(", mice"));
+        let json = serde_json::to_value(&scoreboard.scoreboard["maze"][1]).unwrap();
+        assert_eq!(json["speed_adjusted"], true);
+    }
+
+    #[test]
+    fn hardcore_games_keep_a_streak() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let hardcore = ScoreCategory::new(Mode::Hardcore, 1, 0);
+        let classic = ScoreCategory::new(Mode::Classic, 1, 0);
+        for score in [STREAK_SCORE, STREAK_SCORE + 5, 2, STREAK_SCORE] {
+            scoreboard.add("maze".to_string(), hardcore, score);
+        }
+        scoreboard.add("maze".to_string(), classic, 0);
+
+        let streak = scoreboard.get_streak("maze");
+        assert_eq!((streak.get_current(), streak.get_best()), (1, 2));
+        assert_eq!(scoreboard.get_streak("empty").get_best(), 0);
+
+        let saved = json!({
+            "version": 1,
+            "scores": {},
+            "streaks": { "maze": { "current": 3, "best": 4 } }
+        });
+        assert_eq!(read(saved).unwrap().streaks["maze"].get_best(), 4);
+    }
+
+    #[test]
+    fn latest_game_takes_the_snake_name() {
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let mut scoreboard = ManuallyDrop::new(Scoreboard::from_saved(None));
+        let classic = ScoreCategory::new(Mode::Classic, 1, 0);
+        scoreboard.add("maze".to_string(), classic, 4);
+        scoreboard.name_last("maze", "Noodle");
+        scoreboard.add("maze".to_string(), classic, 9);
+        assert_eq!(
+            scoreboard.get_named("maze", classic),
+            Some(vec![(9, None), (4, Some("Noodle".to_string()))])
+        );
+
+        scoreboard.name_last("maze", "Slinky");
+        scoreboard.name_last("maze", "");
+        assert_eq!(scoreboard.get_named("maze", classic).unwrap()[0].1, None);
+        assert!(!serde_json::to_string(&entry(3)).unwrap().contains("name"));
+    }
+
+    #[test]
+    fn records_are_beaten_the_right_way() {
+        let classic = ScoreCategory::new(Mode::Classic, 1, 0);
+        assert!(classic.beats(12, 7));
+        assert!(!classic.beats(7, 7));
+        assert_eq!(classic.format_gain(12, 7), "+5");
+
+        let race = ScoreCategory::new(Mode::TargetLength(20), 1, 0);
+        assert!(race.beats(95, 110));
+        assert!(!race.beats(110, 95));
+        assert_eq!(race.format_gain(95, 110), "-1.5s");
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let newer = json!({ "version": SCOREBOARD_VERSION + 1, "scores": {} });
+        assert!(read(newer).unwrap_err().contains("newer"));
+    }
+
+    #[test]
+    fn a_corrupt_file_starts_an_empty_scoreboard() {
+        let path = store::test_file("scoreboard.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing scoreboard.json.
+        let scoreboard = ManuallyDrop::new(Scoreboard::load(&path));
+        assert!(scoreboard.scoreboard.is_empty());
+        assert!(scoreboard.streaks.is_empty());
+        assert!(store::is_unreadable(&path));
     }
 }
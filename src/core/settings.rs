@@ -0,0 +1,670 @@
+use std::{collections::HashMap, fs, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use super::{store, Appearance, Error, WallColor, DEFAULT_LANGUAGE};
+
+use super::{GamepadAction, GamepadMapping};
+
+pub(super) const JSON_FILE_PATH: &str = "./src/settings.json";
+const PROFILE_DIR: &str = "./src";
+const PROFILE_PREFIX: &str = "settings-";
+const MAX_PROFILE_NAME_LEN: usize = 32;
+pub const MAX_SNAKE_NAME_LEN: usize = 16;
+
+/// The profile whose settings file is read and written, `None` for the default one.
+static PROFILE: Mutex<Option<String>> = Mutex::new(None);
+const MAX_VOLUME: u8 = 100;
+const VOLUME_STEP: u8 = 10;
+const MAX_SHIFTING_WALLS: u8 = 10;
+/// The most apples on the board at once, from the settings or a board's rules.
+pub(super) const MAX_APPLES: u8 = 10;
+const MAX_KEPT_SCORES: u8 = 100;
+const MIN_TARGET_LENGTH: u16 = 5;
+const MAX_TARGET_LENGTH: u16 = 100;
+const TARGET_LENGTH_STEP: u16 = 5;
+const KEPT_SCORES_STEP: u8 = 5;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Theme {
+    Classic,
+    HighContrast,
+}
+
+impl Theme {
+    pub fn next(&self) -> Self {
+        match self {
+            Theme::Classic => Theme::HighContrast,
+            Theme::HighContrast => Theme::Classic,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Theme::Classic => "classic",
+            Theme::HighContrast => "high contrast",
+        }
+    }
+}
+
+/// A faint pattern drawn on the empty cells of the board, to help judge distances.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Background {
+    #[default]
+    Plain,
+    Checkerboard,
+    Dots,
+}
+
+impl Background {
+    pub fn next(&self) -> Self {
+        match self {
+            Background::Plain => Background::Checkerboard,
+            Background::Checkerboard => Background::Dots,
+            Background::Dots => Background::Plain,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Background::Plain => "plain",
+            Background::Checkerboard => "checkerboard",
+            Background::Dots => "dots",
+        }
+    }
+}
+
+/// The lines the board's frame is drawn with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum BorderSet {
+    #[default]
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+    Ascii,
+}
+
+impl BorderSet {
+    pub fn next(&self) -> Self {
+        match self {
+            BorderSet::Plain => BorderSet::Rounded,
+            BorderSet::Rounded => BorderSet::Double,
+            BorderSet::Double => BorderSet::Thick,
+            BorderSet::Thick => BorderSet::Ascii,
+            BorderSet::Ascii => BorderSet::Plain,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            BorderSet::Plain => "plain",
+            BorderSet::Rounded => "rounded",
+            BorderSet::Double => "double",
+            BorderSet::Thick => "thick",
+            BorderSet::Ascii => "ASCII",
+        }
+    }
+}
+
+/// How a theme draws the frame around the board.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoardBorder {
+    set: BorderSet,
+    /// `None` for the theme's own color.
+    color: Option<WallColor>,
+}
+
+impl BoardBorder {
+    pub fn get_set(&self) -> BorderSet {
+        self.set
+    }
+
+    pub fn get_color(&self) -> Option<WallColor> {
+        self.color
+    }
+
+    /// The next color after `color`, back to the theme's own after the last one.
+    fn next_color(&self) -> Option<WallColor> {
+        match self.color {
+            None => Some(WallColor::ALL[0]),
+            Some(color) => WallColor::ALL
+                .iter()
+                .skip_while(|other| **other != color)
+                .nth(1)
+                .copied(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Settings {
+    screen_shake: bool,
+    /// A short fading afterimage behind the snake.
+    trail: bool,
+    volume: u8,
+    muted: bool,
+    bell: bool,
+    theme: Theme,
+    /// Whether the theme is drawn for a dark or light terminal, or follows the terminal.
+    appearance: Appearance,
+    head_marker: bool,
+    /// The background of each theme; themes left out are plain.
+    #[serde(default)]
+    backgrounds: HashMap<Theme, Background>,
+    /// The board frame of each theme; themes left out draw plain lines.
+    #[serde(default)]
+    borders: HashMap<Theme, BoardBorder>,
+    /// Apples on the board at once.
+    apples: u8,
+    /// Apples between wall shifts, 0 when walls stay put.
+    shifting_walls: u8,
+    moving_food: bool,
+    /// Eating an apple now and then brings out a mouse worth more points.
+    mice: bool,
+    /// The speed follows how the game goes: faster after quick catches, slower after deaths.
+    adaptive_speed: bool,
+    /// Small wandering snakes that kill on contact, more of them on harder boards.
+    roaming_snakes: bool,
+    /// Only a circle around the head is lit.
+    spotlight: bool,
+    /// The board drawn as an image on terminals with the Kitty graphics protocol.
+    pixel_board: bool,
+    /// Every game is written to an asciinema cast as it is drawn.
+    record_casts: bool,
+    tutorial_done: bool,
+    /// The snake length a race ends at.
+    target_length: u16,
+    /// The best scores of each board kept when the scoreboard is saved, 0 for all of them.
+    kept_top_scores: u8,
+    /// The latest games of each board kept as well, 0 for all of them.
+    kept_recent_games: u8,
+    /// Kept in every build, so one without the `gamepad` feature does not drop it on saving.
+    gamepad: GamepadMapping,
+    /// Shown in the HUD and saved with the scores; empty for an unnamed snake.
+    #[serde(default)]
+    snake_name: String,
+    /// The code of the language the interface is shown in.
+    #[serde(default = "default_language")]
+    language: String,
+    /// Where the list of community board packs is fetched from.
+    /// Where the board packs are listed; empty until the player enters one, as there is no
+    /// public index to default to.
+    #[serde(default)]
+    pack_index_url: String,
+    /// The game being played is shown on the player's Discord profile.
+    #[serde(default)]
+    discord_presence: bool,
+    /// The file the settings came from and are saved back to.
+    #[serde(skip, default)]
+    path: String,
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            screen_shake: true,
+            trail: false,
+            volume: 50,
+            muted: false,
+            bell: false,
+            theme: Theme::Classic,
+            appearance: Appearance::Auto,
+            head_marker: false,
+            backgrounds: HashMap::new(),
+            borders: HashMap::new(),
+            apples: 1,
+            shifting_walls: 0,
+            moving_food: false,
+            mice: false,
+            adaptive_speed: false,
+            roaming_snakes: false,
+            spotlight: false,
+            pixel_board: false,
+            record_casts: false,
+            tutorial_done: false,
+            target_length: 20,
+            kept_top_scores: 10,
+            kept_recent_games: 20,
+            gamepad: GamepadMapping::default(),
+            snake_name: String::new(),
+            language: default_language(),
+            pack_index_url: String::new(),
+            discord_presence: false,
+            path: JSON_FILE_PATH.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Falls back to the defaults when the file cannot be read, reporting it through `take_store_errors`.
+    pub fn new() -> Self {
+        Self::load(profile_path())
+    }
+
+    fn load(path: String) -> Self {
+        let mut settings = store::load(&path, Self::default);
+        settings.path = path;
+        settings
+    }
+
+    /// Makes later settings come from the named profile, or the default settings for `None`.
+    /// A profile that does not exist yet starts from the defaults and is saved on first use.
+    pub fn use_profile(name: Option<&str>) -> Result<(), Error> {
+        if let Some(name) = name {
+            let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+            if name.is_empty() || name.len() > MAX_PROFILE_NAME_LEN || !name.chars().all(valid) {
+                return Err(Error::Invalid(
+                    "a profile name is letters, digits, '-' and '_'",
+                ));
+            }
+        }
+        *PROFILE.lock().unwrap() = name.map(str::to_string);
+        Ok(())
+    }
+
+    /// The profile in use, `None` for the default settings.
+    pub fn get_profile() -> Option<String> {
+        PROFILE.lock().unwrap().clone()
+    }
+
+    /// The names of the saved profiles, sorted.
+    pub fn list_profiles() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(PROFILE_DIR) else {
+            return Vec::new();
+        };
+        let mut profiles: Vec<String> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let profile = name.strip_prefix(PROFILE_PREFIX)?.strip_suffix(".json")?;
+                Some(profile.to_string())
+            })
+            .collect();
+        profiles.sort();
+        profiles
+    }
+
+    pub fn is_screen_shake_enabled(&self) -> bool {
+        self.screen_shake
+    }
+
+    pub fn toggle_screen_shake(&mut self) {
+        self.screen_shake = !self.screen_shake;
+    }
+
+    pub fn is_trail_enabled(&self) -> bool {
+        self.trail
+    }
+
+    pub fn toggle_trail(&mut self) {
+        self.trail = !self.trail;
+    }
+
+    pub fn get_volume(&self) -> u8 {
+        self.volume
+    }
+
+    pub fn increase_volume(&mut self) {
+        self.volume = (self.volume + VOLUME_STEP).min(MAX_VOLUME);
+    }
+
+    pub fn decrease_volume(&mut self) {
+        self.volume = self.volume.saturating_sub(VOLUME_STEP);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+
+    pub fn is_bell_enabled(&self) -> bool {
+        self.bell
+    }
+
+    pub fn toggle_bell(&mut self) {
+        self.bell = !self.bell;
+    }
+
+    pub fn get_theme(&self) -> Theme {
+        self.theme
+    }
+
+    pub fn next_theme(&mut self) {
+        self.theme = self.theme.next();
+    }
+
+    pub fn get_appearance(&self) -> Appearance {
+        self.appearance
+    }
+
+    pub fn next_appearance(&mut self) {
+        self.appearance = self.appearance.next();
+    }
+
+    /// The background of the current theme.
+    pub fn get_background(&self) -> Background {
+        self.backgrounds
+            .get(&self.theme)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn next_background(&mut self) {
+        let background = self.get_background().next();
+        self.backgrounds.insert(self.theme, background);
+    }
+
+    /// The board frame of the current theme.
+    pub fn get_border(&self) -> BoardBorder {
+        self.borders.get(&self.theme).copied().unwrap_or_default()
+    }
+
+    pub fn next_border_set(&mut self) {
+        let mut border = self.get_border();
+        border.set = border.set.next();
+        self.borders.insert(self.theme, border);
+    }
+
+    pub fn next_border_color(&mut self) {
+        let mut border = self.get_border();
+        border.color = border.next_color();
+        self.borders.insert(self.theme, border);
+    }
+
+    pub fn is_head_marker_enabled(&self) -> bool {
+        self.head_marker
+    }
+
+    pub fn toggle_head_marker(&mut self) {
+        self.head_marker = !self.head_marker;
+    }
+
+    pub fn get_apples(&self) -> u8 {
+        self.apples
+    }
+
+    pub fn increase_apples(&mut self) {
+        self.apples = (self.apples + 1).min(MAX_APPLES);
+    }
+
+    pub fn decrease_apples(&mut self) {
+        self.apples = self.apples.saturating_sub(1).max(1);
+    }
+
+    pub fn is_moving_food_enabled(&self) -> bool {
+        self.moving_food
+    }
+
+    pub fn toggle_moving_food(&mut self) {
+        self.moving_food = !self.moving_food;
+    }
+
+    pub fn is_mice_enabled(&self) -> bool {
+        self.mice
+    }
+
+    pub fn toggle_mice(&mut self) {
+        self.mice = !self.mice;
+    }
+
+    pub fn is_adaptive_speed_enabled(&self) -> bool {
+        self.adaptive_speed
+    }
+
+    pub fn toggle_adaptive_speed(&mut self) {
+        self.adaptive_speed = !self.adaptive_speed;
+    }
+
+    pub fn is_roaming_snakes_enabled(&self) -> bool {
+        self.roaming_snakes
+    }
+
+    pub fn toggle_roaming_snakes(&mut self) {
+        self.roaming_snakes = !self.roaming_snakes;
+    }
+
+    pub fn is_spotlight_enabled(&self) -> bool {
+        self.spotlight
+    }
+
+    pub fn toggle_spotlight(&mut self) {
+        self.spotlight = !self.spotlight;
+    }
+
+    pub fn is_pixel_board_enabled(&self) -> bool {
+        self.pixel_board
+    }
+
+    pub fn toggle_pixel_board(&mut self) {
+        self.pixel_board = !self.pixel_board;
+    }
+
+    pub fn get_snake_name(&self) -> &str {
+        &self.snake_name
+    }
+
+    /// Trims the name and cuts it to `MAX_SNAKE_NAME_LEN` characters.
+    pub fn set_snake_name(&mut self, name: &str) {
+        self.snake_name = name.trim().chars().take(MAX_SNAKE_NAME_LEN).collect();
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn set_language(&mut self, language: String) {
+        self.language = language;
+    }
+
+    pub fn is_recording_casts(&self) -> bool {
+        self.record_casts
+    }
+
+    pub fn toggle_record_casts(&mut self) {
+        self.record_casts = !self.record_casts;
+    }
+
+    pub fn is_discord_presence_enabled(&self) -> bool {
+        self.discord_presence
+    }
+
+    pub fn toggle_discord_presence(&mut self) {
+        self.discord_presence = !self.discord_presence;
+    }
+
+    pub fn get_shifting_walls(&self) -> u8 {
+        self.shifting_walls
+    }
+
+    pub fn increase_shifting_walls(&mut self) {
+        self.shifting_walls = (self.shifting_walls + 1).min(MAX_SHIFTING_WALLS);
+    }
+
+    pub fn decrease_shifting_walls(&mut self) {
+        self.shifting_walls = self.shifting_walls.saturating_sub(1);
+    }
+
+    /// Whether the tutorial has been shown, so it only starts by itself on the first run.
+    pub fn is_tutorial_done(&self) -> bool {
+        self.tutorial_done
+    }
+
+    pub fn set_tutorial_done(&mut self) {
+        self.tutorial_done = true;
+    }
+
+    pub fn get_target_length(&self) -> u16 {
+        self.target_length
+    }
+
+    pub fn increase_target_length(&mut self) {
+        self.target_length = (self.target_length + TARGET_LENGTH_STEP).min(MAX_TARGET_LENGTH);
+    }
+
+    pub fn decrease_target_length(&mut self) {
+        self.target_length = self
+            .target_length
+            .saturating_sub(TARGET_LENGTH_STEP)
+            .max(MIN_TARGET_LENGTH);
+    }
+
+    pub fn get_kept_top_scores(&self) -> u8 {
+        self.kept_top_scores
+    }
+
+    pub fn increase_kept_top_scores(&mut self) {
+        self.kept_top_scores = (self.kept_top_scores + KEPT_SCORES_STEP).min(MAX_KEPT_SCORES);
+    }
+
+    pub fn decrease_kept_top_scores(&mut self) {
+        self.kept_top_scores = self.kept_top_scores.saturating_sub(KEPT_SCORES_STEP);
+    }
+
+    pub fn get_kept_recent_games(&self) -> u8 {
+        self.kept_recent_games
+    }
+
+    pub fn increase_kept_recent_games(&mut self) {
+        self.kept_recent_games = (self.kept_recent_games + KEPT_SCORES_STEP).min(MAX_KEPT_SCORES);
+    }
+
+    pub fn decrease_kept_recent_games(&mut self) {
+        self.kept_recent_games = self.kept_recent_games.saturating_sub(KEPT_SCORES_STEP);
+    }
+
+    pub fn get_gamepad_mapping(&self) -> &GamepadMapping {
+        &self.gamepad
+    }
+
+    pub fn set_gamepad_button(&mut self, action: GamepadAction, button: u8) {
+        self.gamepad.set_button(action, button);
+    }
+
+    pub fn get_pack_index_url(&self) -> &str {
+        &self.pack_index_url
+    }
+
+    pub fn set_pack_index_url(&mut self, url: String) {
+        self.pack_index_url = url;
+    }
+}
+
+impl Drop for Settings {
+    fn drop(&mut self) {
+        store::save(&self.path, self);
+    }
+}
+
+fn profile_path() -> String {
+    match &*PROFILE.lock().unwrap() {
+        Some(name) => format!("{PROFILE_DIR}/{PROFILE_PREFIX}{name}.json"),
+        None => JSON_FILE_PATH.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test_settings {
+    use std::mem::ManuallyDrop;
+
+    use super::{Background, BoardBorder, BorderSet, GamepadAction, Settings, WallColor};
+    use crate::core::store;
+
+    #[test]
+    fn volume_stays_in_range() {
+        // `ManuallyDrop` keeps the test from writing settings.json.
+        let mut settings = ManuallyDrop::new(Settings::default());
+
+        for _ in 0..20 {
+            settings.increase_volume();
+        }
+        assert_eq!(settings.get_volume(), 100);
+
+        for _ in 0..20 {
+            settings.decrease_volume();
+        }
+        assert_eq!(settings.get_volume(), 0);
+    }
+
+    #[test]
+    fn snake_names_are_trimmed() {
+        // `ManuallyDrop` keeps the test from writing settings.json.
+        let mut settings = ManuallyDrop::new(Settings::default());
+        assert_eq!(settings.get_snake_name(), "");
+
+        settings.set_snake_name("  Noodle ");
+        assert_eq!(settings.get_snake_name(), "Noodle");
+        settings.set_snake_name("a very long snake name indeed");
+        assert_eq!(settings.get_snake_name(), "a very long snak");
+    }
+
+    #[test]
+    fn profile_names() {
+        assert!(Settings::use_profile(Some("")).is_err());
+        assert!(Settings::use_profile(Some("../escape")).is_err());
+        assert!(Settings::use_profile(Some("kid mode")).is_err());
+        assert_eq!(Settings::get_profile(), None);
+    }
+
+    #[test]
+    fn backgrounds_follow_the_theme() {
+        let mut settings = ManuallyDrop::new(Settings::default());
+        settings.next_background();
+        assert_eq!(settings.get_background(), Background::Checkerboard);
+
+        settings.next_theme();
+        assert_eq!(settings.get_background(), Background::Plain);
+        settings.next_theme();
+        assert_eq!(settings.get_background(), Background::Checkerboard);
+    }
+
+    #[test]
+    fn borders_follow_the_theme() {
+        let mut settings = ManuallyDrop::new(Settings::default());
+        settings.next_border_set();
+        settings.next_border_color();
+        assert_eq!(settings.get_border().get_set(), BorderSet::Rounded);
+        assert_eq!(settings.get_border().get_color(), Some(WallColor::Red));
+
+        settings.next_theme();
+        assert_eq!(settings.get_border(), BoardBorder::default());
+        settings.next_theme();
+        for _ in 1..WallColor::ALL.len() {
+            settings.next_border_color();
+        }
+        assert_eq!(settings.get_border().get_color(), Some(WallColor::Gray));
+        settings.next_border_color();
+        assert_eq!(settings.get_border().get_color(), None);
+        assert_eq!(settings.get_border().get_set(), BorderSet::Rounded);
+    }
+
+    #[test]
+    fn feature_settings_survive_any_build() {
+        let mut settings = ManuallyDrop::new(Settings::default());
+        settings.set_gamepad_button(GamepadAction::Back, 5);
+        settings.set_pack_index_url("http://example.com/index.json".to_string());
+        settings.toggle_discord_presence();
+
+        let json = serde_json::to_string(&*settings).unwrap();
+        let loaded = ManuallyDrop::new(serde_json::from_str::<Settings>(&json).unwrap());
+        assert_eq!(loaded.get_gamepad_mapping(), settings.get_gamepad_mapping());
+        assert_eq!(loaded.get_pack_index_url(), "http://example.com/index.json");
+        assert!(loaded.is_discord_presence_enabled());
+    }
+
+    #[test]
+    fn a_corrupt_file_falls_back_to_the_defaults() {
+        let path = store::test_file("settings.json", "{ not json");
+        // `ManuallyDrop` keeps the test from writing the file.
+        let settings = ManuallyDrop::new(Settings::load(path.clone()));
+        assert_eq!(settings.get_volume(), Settings::default().get_volume());
+        assert_eq!(settings.path, path);
+        assert!(store::is_unreadable(&path));
+    }
+}
@@ -0,0 +1,218 @@
+//! A log of every finished game, one JSON object per line, kept for the statistics screen
+//! and for anyone who wants to look at their games with other tools.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{DeathCause, Error, Game, Mode, Point};
+
+pub const STATS_LOG_PATH: &str = "./src/stats.jsonl";
+
+/// One finished game.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    board: String,
+    mode: Mode,
+    score: u16,
+    length: u16,
+    duration_ms: u64,
+    /// None when the game ended by reaching its goal.
+    death: Option<DeathCause>,
+    /// The cell the snake ran into.
+    died_at: Option<Point>,
+    seed: u64,
+    /// When the game ended, in seconds since the Unix epoch.
+    at: u64,
+}
+
+impl GameRecord {
+    /// The record of `game`, counted as given up when it is not over yet.
+    pub fn new(game: &Game, board: &str, mode: Mode, duration_ms: u64, at: u64) -> Self {
+        let (death, died_at) = match game.get_death() {
+            Some((cause, point)) => (Some(*cause), Some(point.clone())),
+            None if !game.is_over() => (Some(DeathCause::GaveUp), None),
+            None => (None, None),
+        };
+        Self {
+            board: board.to_string(),
+            mode,
+            score: game.get_score(),
+            length: game.get_length(),
+            duration_ms,
+            death,
+            died_at,
+            seed: game.get_seed(),
+            at,
+        }
+    }
+
+    pub fn get_board(&self) -> &str {
+        &self.board
+    }
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.length
+    }
+
+    pub fn get_duration_ms(&self) -> u64 {
+        self.duration_ms
+    }
+
+    pub fn get_death(&self) -> Option<DeathCause> {
+        self.death
+    }
+
+    pub fn get_died_at(&self) -> Option<&Point> {
+        self.died_at.as_ref()
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_at(&self) -> u64 {
+        self.at
+    }
+}
+
+/// Adds `record` at the end of the log.
+pub fn append_game(record: &GameRecord) -> Result<(), Error> {
+    append_game_to(STATS_LOG_PATH, record)
+}
+
+/// Every game of the log, oldest first. A missing log has none.
+pub fn read_games() -> Result<Vec<GameRecord>, Error> {
+    read_games_from(STATS_LOG_PATH)
+}
+
+/// The cells the snake ran into in the games on `board`, with how many games ended there,
+/// the deadliest first.
+pub fn death_heatmap(games: &[GameRecord], board: &str) -> Vec<(Point, usize)> {
+    let mut heatmap: Vec<(Point, usize)> = Vec::new();
+    for point in games
+        .iter()
+        .filter(|game| game.board == board)
+        .filter_map(GameRecord::get_died_at)
+    {
+        match heatmap.iter_mut().find(|(cell, _)| cell == point) {
+            Some((_, count)) => *count += 1,
+            None => heatmap.push((point.clone(), 1)),
+        }
+    }
+    heatmap.sort_by_key(|(point, count)| (usize::MAX - count, point.get_x(), point.get_y()));
+    heatmap
+}
+
+fn append_game_to(path: &str, record: &GameRecord) -> Result<(), Error> {
+    let io_error = |e: std::io::Error| Error::Io {
+        path: path.to_string(),
+        source: e,
+    };
+    let line = serde_json::to_string(record).map_err(|e| Error::Json(e.to_string()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+    writeln!(file, "{line}").map_err(io_error)
+}
+
+/// Lines that are not a game, like one cut short by a crash, are skipped.
+fn read_games_from(path: &str) -> Result<Vec<GameRecord>, Error> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(Error::Io {
+                path: path.to_string(),
+                source: e,
+            })
+        }
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod test_stats_log {
+    use std::{env, fs, io::Write};
+
+    use super::{append_game_to, death_heatmap, read_games_from, GameRecord};
+    use crate::core::{Board, DeathCause, Direction, Game, Mode, Point};
+
+    #[test]
+    fn appends_a_line_per_game() {
+        let dir = env::temp_dir().join(format!("snake-stats-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stats.jsonl");
+        let path = path.to_str().unwrap();
+        assert_eq!(read_games_from(path).unwrap(), Vec::new());
+
+        let walls = (0..7).map(|y| Point::new(3, y)).collect();
+        let board = Board::new("box".to_string(), 7, walls);
+        let mut game = Game::with_seed(&board, 3, 7);
+        let quit = GameRecord::new(&game, "box", Mode::Classic, 1500, 100);
+        assert_eq!(quit.get_death(), Some(DeathCause::GaveUp));
+
+        game.rotation(Direction::Up);
+        while game.walk() {}
+        let died = GameRecord::new(&game, "box", Mode::Classic, 3000, 200);
+        assert!(died.get_death().is_some());
+        assert!(died.get_died_at().is_some());
+        assert_eq!(died.get_seed(), 7);
+
+        append_game_to(path, &quit).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(b"{\"board\":\n")
+            .unwrap();
+        append_game_to(path, &died).unwrap();
+        assert_eq!(read_games_from(path).unwrap(), vec![quit, died]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn heatmap_counts_deaths_per_cell() {
+        let walls = (0..7).map(|y| Point::new(3, y)).collect();
+        let board = Board::new("box".to_string(), 7, walls);
+        let mut game = Game::with_seed(&board, 3, 7);
+        let quit = GameRecord::new(&game, "box", Mode::Classic, 1500, 100);
+        game.rotation(Direction::Up);
+        while game.walk() {}
+        let died = GameRecord::new(&game, "box", Mode::Classic, 3000, 200);
+        let elsewhere = GameRecord {
+            board: "other".to_string(),
+            ..died.clone()
+        };
+        let mut other_cell = died.clone();
+        other_cell.died_at = Some(Point::new(0, 0));
+
+        let games = [quit, died.clone(), other_cell, died.clone(), elsewhere];
+        let heatmap = death_heatmap(&games, "box");
+        assert_eq!(
+            heatmap,
+            vec![
+                (died.get_died_at().unwrap().clone(), 2),
+                (Point::new(0, 0), 1)
+            ]
+        );
+        assert!(death_heatmap(&games, "none").is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! A pure form of the game loop, for property tests and fuzzing.
+
+use super::{Direction, Game, GameEvent};
+
+/// Everything a game is, its seeded RNG included, so a state always steps the same way.
+pub type GameState<'a> = Game<'a>;
+
+/// The state after one tick with `input` turned first, and what happened during it; `state`
+/// itself is left as it was. Randomness comes only from the state's own RNG, seeded by
+/// `Game::with_seed`. A game that is over stays as it is.
+pub fn step<'a>(
+    state: &GameState<'a>,
+    input: Option<Direction>,
+) -> (GameState<'a>, Vec<GameEvent>) {
+    let mut next = state.clone();
+    if next.is_over() {
+        return (next, Vec::new());
+    }
+    if let Some(direction) = input {
+        next.rotation(direction);
+    }
+    next.walk();
+    let events = next.take_events();
+    (next, events)
+}
+
+#[cfg(test)]
+mod test_step {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::step;
+    use crate::core::{Board, CellKind, Direction, Game, Point, Wall};
+
+    const INPUTS: [Option<Direction>; 5] = [
+        None,
+        Some(Direction::Up),
+        Some(Direction::Down),
+        Some(Direction::Left),
+        Some(Direction::Right),
+    ];
+
+    fn maze() -> Board {
+        let walls = (0..10)
+            .flat_map(|i| [Wall::new(3, i), Wall::new(i, 7)])
+            .filter(|wall| wall.get_y() != 4 && wall.get_x() != 1)
+            .collect();
+        Board::new("maze".to_string(), 12, walls)
+    }
+
+    #[test]
+    fn snake_never_enters_a_wall() {
+        let board = maze();
+        for seed in 0..50 {
+            let mut inputs = StdRng::seed_from_u64(seed);
+            let mut state = Game::with_seed(&board, 4, seed);
+            for _ in 0..300 {
+                (state, _) = step(&state, *INPUTS.choose(&mut inputs).unwrap());
+
+                let cells = state.get_cells();
+                for (x, row) in cells.iter().enumerate().skip(1).take(12) {
+                    for (y, cell) in row.iter().enumerate().skip(1).take(12) {
+                        if matches!(cell.get_kind(), CellKind::Snake | CellKind::SnakeHead) {
+                            let point = Point::new(x as i16 - 1, y as i16 - 1);
+                            assert!(!board.is_wall(&point), "seed {seed}: snake on {point:?}");
+                        }
+                    }
+                }
+                if state.is_over() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_state_same_step() {
+        let board = maze();
+        let mut state = Game::with_seed(&board, 4, 7);
+        for input in INPUTS.iter().cycle().take(200) {
+            let before = state.get_cells();
+            let (a, events_a) = step(&state, *input);
+            let (b, events_b) = step(&state, *input);
+            assert_eq!(state.get_cells(), before);
+            assert_eq!(events_a, events_b);
+            assert_eq!(a.get_cells(), b.get_cells());
+            state = a;
+        }
+
+        // Running into the wall ahead ends the game, which then stays as it is.
+        while !state.is_over() {
+            state = step(&state, Some(Direction::Down)).0;
+        }
+        let (after, events) = step(&state, Some(Direction::Up));
+        assert!(events.is_empty());
+        assert_eq!(after.get_cells(), state.get_cells());
+    }
+}
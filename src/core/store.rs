@@ -0,0 +1,272 @@
+//! Reads and writes the JSON files behind the saved boards, scores and settings. Failures
+//! do not panic: they are collected for the interface to show, retry or skip.
+
+use std::{fs, io::ErrorKind, path::Path, sync::Mutex};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use super::{
+    backups, boards, campaign, error::Error, objective_records, prefabs, puzzle_records,
+    recent_boards, saved_game, scoreboard, settings,
+};
+
+const FILES: [&str; 9] = [
+    boards::JSON_FILE_PATH,
+    campaign::JSON_FILE_PATH,
+    objective_records::JSON_FILE_PATH,
+    prefabs::JSON_FILE_PATH,
+    puzzle_records::JSON_FILE_PATH,
+    recent_boards::JSON_FILE_PATH,
+    saved_game::JSON_FILE_PATH,
+    scoreboard::JSON_FILE_PATH,
+    settings::JSON_FILE_PATH,
+];
+
+static ERRORS: Mutex<Vec<StoreError>> = Mutex::new(Vec::new());
+/// Files that could not be read, which are left alone instead of being written over.
+static UNREADABLE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreError {
+    Read {
+        path: String,
+        error: String,
+    },
+    /// `json` is what should have been written, kept for a retry.
+    Write {
+        path: String,
+        error: String,
+        json: String,
+    },
+}
+
+impl StoreError {
+    pub fn get_path(&self) -> &str {
+        match self {
+            Self::Read { path, .. } | Self::Write { path, .. } => path,
+        }
+    }
+
+    pub fn get_message(&self) -> String {
+        match self {
+            Self::Read { path, error } => format!("Could not read {path}: {error}"),
+            Self::Write { path, error, .. } => format!("Could not save {path}: {error}"),
+        }
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, Self::Write { .. })
+    }
+
+    /// Tries the read or write again, returning the new error when it still fails.
+    pub fn retry(&self) -> Result<(), StoreError> {
+        match self {
+            Self::Read { path, .. } => {
+                read::<serde_json::Value>(path).map_err(|error| Self::Read {
+                    path: path.clone(),
+                    error: describe(error),
+                })?;
+                UNREADABLE.lock().unwrap().retain(|p| p != path);
+                Ok(())
+            }
+            Self::Write { path, json, .. } => fs::write(path, json).map_err(|e| Self::Write {
+                path: path.clone(),
+                error: e.to_string(),
+                json: json.clone(),
+            }),
+        }
+    }
+}
+
+/// The failures since the last call, oldest first.
+pub fn take_store_errors() -> Vec<StoreError> {
+    std::mem::take(&mut ERRORS.lock().unwrap())
+}
+
+/// Checks that every file can be read, so a broken one is reported before a screen needs it.
+pub fn check_files() {
+    for path in FILES {
+        if let Err(error) = read::<serde_json::Value>(path) {
+            fail_read(path, describe(error));
+        }
+    }
+}
+
+/// The contents of `path`, else `fallback()`; a missing file is not an error.
+pub(crate) fn load<T: DeserializeOwned>(path: &str, fallback: impl FnOnce() -> T) -> T {
+    match read(path) {
+        Ok(Some(value)) => value,
+        Ok(None) => fallback(),
+        Err(error) => {
+            fail_read(path, describe(error));
+            fallback()
+        }
+    }
+}
+
+pub(crate) fn save<T: Serialize>(path: &str, value: &T) {
+    if UNREADABLE.lock().unwrap().iter().any(|p| p == path) {
+        return;
+    }
+
+    let json = serde_json::to_string(value).unwrap();
+    backups::rotate(path, &json);
+    if let Err(e) = fs::write(path, &json) {
+        record(StoreError::Write {
+            path: path.to_string(),
+            error: e.to_string(),
+            json,
+        });
+    }
+}
+
+/// Appends `entries`, taken out of the file at `source`, to the quarantine file at `path`.
+/// When that fails `source` is treated as unreadable, so the entries are not written over.
+pub(crate) fn quarantine(source: &str, path: &str, entries: Vec<Value>) -> bool {
+    let io_error = |e: std::io::Error| Error::Io {
+        path: path.to_string(),
+        source: e,
+    };
+    let moved = (|| {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut kept: Vec<Value> = read(path)?.unwrap_or_default();
+        kept.extend(entries);
+        fs::write(path, serde_json::to_string_pretty(&kept).unwrap()).map_err(io_error)
+    })();
+
+    match moved {
+        Ok(()) => true,
+        Err(error) => {
+            fail_read(
+                source,
+                format!("could not move broken entries to {path}: {error}"),
+            );
+            false
+        }
+    }
+}
+
+/// Writes `contents` to a fresh file in the temporary directory, returning its path.
+#[cfg(test)]
+pub(crate) fn test_file(name: &str, contents: &str) -> String {
+    let dir = std::env::temp_dir().join(format!("snake-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name).to_string_lossy().to_string();
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Whether `path` was reported as unreadable and is being left alone.
+#[cfg(test)]
+pub(crate) fn is_unreadable(path: &str) -> bool {
+    UNREADABLE.lock().unwrap().iter().any(|p| p == path)
+}
+
+/// `None` when the file is missing, as it is before anything was saved.
+fn read<T: DeserializeOwned>(path: &str) -> Result<Option<T>, Error> {
+    match fs::read_to_string(path) {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io {
+            path: path.to_string(),
+            source: e,
+        }),
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| Error::Json(e.to_string())),
+    }
+}
+
+/// The reason alone, as the store error already names the file.
+fn describe(error: Error) -> String {
+    match error {
+        Error::Io { source, .. } => source.to_string(),
+        error => error.to_string(),
+    }
+}
+
+/// Reports a file once; it stays unreadable, and unreported, until a retry reads it.
+fn fail_read(path: &str, error: String) {
+    let mut unreadable = UNREADABLE.lock().unwrap();
+    if unreadable.iter().any(|p| p == path) {
+        return;
+    }
+    unreadable.push(path.to_string());
+    record(StoreError::Read {
+        path: path.to_string(),
+        error,
+    });
+}
+
+fn record(error: StoreError) {
+    let mut errors = ERRORS.lock().unwrap();
+    let known = errors
+        .iter()
+        .any(|e| e.get_path() == error.get_path() && e.is_write() == error.is_write());
+    if !known {
+        errors.push(error);
+    }
+}
+
+#[cfg(test)]
+mod test_store {
+    use std::{collections::HashMap, env, fs};
+
+    use serde_json::json;
+
+    use super::{load, quarantine, save, take_store_errors};
+
+    #[test]
+    fn broken_files_are_reported_and_kept() {
+        let dir = env::temp_dir().join(format!("snake-store-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let missing = dir.join("missing.json").to_string_lossy().to_string();
+        let broken = dir.join("broken.json").to_string_lossy().to_string();
+        fs::write(&broken, "{ not json").unwrap();
+
+        let scores: HashMap<String, u16> = load(&missing, HashMap::new);
+        assert!(scores.is_empty());
+        save(&missing, &HashMap::from([("maze".to_string(), 3u16)]));
+        let scores: HashMap<String, u16> = load(&missing, HashMap::new);
+        assert_eq!(scores.get("maze"), Some(&3));
+
+        let scores: HashMap<String, u16> = load(&broken, HashMap::new);
+        assert!(scores.is_empty());
+        save(&broken, &scores);
+        assert_eq!(fs::read_to_string(&broken).unwrap(), "{ not json");
+
+        let errors: Vec<_> = take_store_errors()
+            .into_iter()
+            .filter(|e| e.get_path() == broken)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].is_write());
+        assert!(errors[0].retry().is_err());
+
+        fs::write(&broken, "{}").unwrap();
+        assert!(errors[0].retry().is_ok());
+        save(&broken, &HashMap::from([("open".to_string(), 1u16)]));
+        assert_eq!(fs::read_to_string(&broken).unwrap(), r#"{"open":1}"#);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn quarantine_appends() {
+        let dir = env::temp_dir().join(format!("snake-quarantine-{}", std::process::id()));
+        let source = dir.join("boards.json").to_string_lossy().to_string();
+        let path = dir
+            .join("corrupt/boards.json")
+            .to_string_lossy()
+            .to_string();
+
+        assert!(quarantine(&source, &path, vec![json!(1)]));
+        assert!(quarantine(&source, &path, vec![json!(2), json!(3)]));
+        let kept: Vec<u8> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(kept, [1, 2, 3]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+use super::{Board, Direction, Game, GameEvent, Point};
+
+const WALL_MOVES: u16 = 30;
+const DASH_TICKS: u16 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TutorialStep {
+    Turn,
+    Eat,
+    AvoidWall,
+    Dash,
+    Done,
+}
+
+impl TutorialStep {
+    pub fn get_prompt(&self) -> &str {
+        match self {
+            TutorialStep::Turn => "Turn the snake with 🠀 🠂 🠁 🠃 or h j k l",
+            TutorialStep::Eat => "Eat the apple ●",
+            TutorialStep::AvoidWall => "Keep moving without bumping into the wall",
+            TutorialStep::Dash => "Hold space or your heading to dash",
+            TutorialStep::Done => "Well done! You are ready to play",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            TutorialStep::Turn => TutorialStep::Eat,
+            TutorialStep::Eat => TutorialStep::AvoidWall,
+            TutorialStep::AvoidWall => TutorialStep::Dash,
+            TutorialStep::Dash | TutorialStep::Done => TutorialStep::Done,
+        }
+    }
+}
+
+/// A guided first game; each step only advances once the player has done what it asks.
+pub struct Tutorial {
+    step: TutorialStep,
+    progress: u16,
+}
+
+impl Tutorial {
+    pub const STEPS: usize = 4;
+
+    pub fn new() -> Self {
+        Self {
+            step: TutorialStep::Turn,
+            progress: 0,
+        }
+    }
+
+    /// A tiny board with a single wall, the snake starting below it.
+    pub fn board() -> Board {
+        let walls = (1..=5).map(|x| Point::new(x, 4)).collect();
+        let mut board = Board::new("Tutorial".to_string(), 9, walls);
+        board.set_spawn(Point::new(7, 2), Direction::Right).unwrap();
+        board
+    }
+
+    pub fn get_step(&self) -> TutorialStep {
+        self.step
+    }
+
+    /// One-based number of the current step.
+    pub fn get_step_number(&self) -> usize {
+        match self.step {
+            TutorialStep::Turn => 1,
+            TutorialStep::Eat => 2,
+            TutorialStep::AvoidWall => 3,
+            TutorialStep::Dash | TutorialStep::Done => 4,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == TutorialStep::Done
+    }
+
+    /// Checks the tick that just happened against the current step.
+    pub fn update(&mut self, game: &Game, events: &[GameEvent]) {
+        let finished = match self.step {
+            TutorialStep::Turn => game.get_direction() != Direction::Right,
+            TutorialStep::Eat => events
+                .iter()
+                .any(|event| matches!(event, GameEvent::Ate { .. })),
+            TutorialStep::AvoidWall => {
+                if events.contains(&GameEvent::Bumped) {
+                    self.progress = 0;
+                } else {
+                    self.progress += 1;
+                }
+                self.progress >= WALL_MOVES
+            }
+            TutorialStep::Dash => {
+                if game.is_boosting() {
+                    self.progress += 1;
+                }
+                self.progress >= DASH_TICKS
+            }
+            TutorialStep::Done => false,
+        };
+
+        if finished {
+            self.step = self.step.next();
+            self.progress = 0;
+        }
+    }
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_tutorial {
+    use super::{Tutorial, TutorialStep};
+    use crate::core::{Direction, Game, GameEvent, Point};
+
+    #[test]
+    fn steps_wait_for_the_player() {
+        let board = Tutorial::board();
+        let mut game = Game::new(&board, 3);
+        game.set_no_death(true);
+        let mut tutorial = Tutorial::new();
+
+        tutorial.update(&game, &[]);
+        assert_eq!(tutorial.get_step(), TutorialStep::Turn);
+        game.rotation(Direction::Up);
+        tutorial.update(&game, &[]);
+        assert_eq!(tutorial.get_step(), TutorialStep::Eat);
+
+        let ate = GameEvent::Ate {
+            at: Point::new(0, 0),
+            points: 1,
+        };
+        tutorial.update(&game, &[ate]);
+        assert_eq!(tutorial.get_step(), TutorialStep::AvoidWall);
+
+        for _ in 0..super::WALL_MOVES - 1 {
+            tutorial.update(&game, &[]);
+        }
+        tutorial.update(&game, &[GameEvent::Bumped]);
+        assert_eq!(tutorial.get_step(), TutorialStep::AvoidWall);
+        for _ in 0..super::WALL_MOVES {
+            tutorial.update(&game, &[]);
+        }
+        assert_eq!(tutorial.get_step(), TutorialStep::Dash);
+
+        game.set_boost(true);
+        for _ in 0..super::DASH_TICKS {
+            tutorial.update(&game, &[]);
+        }
+        assert!(tutorial.is_done());
+        assert_eq!(tutorial.get_step_number(), Tutorial::STEPS);
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Round bookkeeping for a versus match; the round survivor scores a win.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Versus {
+    rounds: u8,
+    wins: Vec<u8>,
+    winners: Vec<Option<usize>>,
+}
+
+impl Versus {
+    pub fn new(rounds: u8, players: usize) -> Self {
+        Self {
+            rounds,
+            wins: vec![0; players],
+            winners: Vec::new(),
+        }
+    }
+
+    /// Records a finished round; `None` means every snake died at once.
+    pub fn finish_round(&mut self, survivor: Option<usize>) {
+        if let Some(win) = survivor.and_then(|player| self.wins.get_mut(player)) {
+            *win += 1;
+        }
+        self.winners.push(survivor);
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winners.len() >= self.rounds as usize
+    }
+
+    /// The round being played, starting at 1.
+    pub fn get_round(&self) -> u8 {
+        (self.winners.len() as u8 + 1).min(self.rounds)
+    }
+
+    pub fn get_rounds(&self) -> u8 {
+        self.rounds
+    }
+
+    pub fn get_wins(&self) -> &[u8] {
+        &self.wins
+    }
+
+    pub fn get_winners(&self) -> &[Option<usize>] {
+        &self.winners
+    }
+
+    /// The player with the most round wins, `None` on a tie.
+    pub fn get_champion(&self) -> Option<usize> {
+        let best = *self.wins.iter().max()?;
+        let mut leaders = self
+            .wins
+            .iter()
+            .enumerate()
+            .filter(|(_, wins)| **wins == best);
+        match (leaders.next(), leaders.next()) {
+            (Some((player, _)), None) => Some(player),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_versus {
+    use super::Versus;
+
+    #[test]
+    fn champion_after_all_rounds() {
+        let mut versus = Versus::new(3, 2);
+
+        versus.finish_round(Some(1));
+        assert_eq!(versus.get_round(), 2);
+        assert!(!versus.is_over());
+
+        versus.finish_round(None);
+        versus.finish_round(Some(1));
+        assert!(versus.is_over());
+        assert_eq!(versus.get_wins(), &[0, 2]);
+        assert_eq!(versus.get_champion(), Some(1));
+    }
+
+    #[test]
+    fn tie_has_no_champion() {
+        let mut versus = Versus::new(2, 2);
+
+        versus.finish_round(Some(0));
+        versus.finish_round(Some(1));
+        assert_eq!(versus.get_champion(), None);
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use super::Point;
+
+/// A color a board may give its walls, left to the interface to show.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WallColor {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl WallColor {
+    /// The colors in the order the editor cycles through them.
+    pub const ALL: [WallColor; 8] = [
+        WallColor::Red,
+        WallColor::Green,
+        WallColor::Blue,
+        WallColor::Yellow,
+        WallColor::Magenta,
+        WallColor::Cyan,
+        WallColor::White,
+        WallColor::Gray,
+    ];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            WallColor::Red => "red",
+            WallColor::Green => "green",
+            WallColor::Blue => "blue",
+            WallColor::Yellow => "yellow",
+            WallColor::Magenta => "magenta",
+            WallColor::Cyan => "cyan",
+            WallColor::White => "white",
+            WallColor::Gray => "gray",
+        }
+    }
+}
+
+/// Walls painted the same color, only to make a maze easier to read.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WallRegion {
+    color: WallColor,
+    walls: Vec<Point>,
+}
+
+impl WallRegion {
+    pub fn new(color: WallColor, walls: Vec<Point>) -> Self {
+        Self { color, walls }
+    }
+
+    pub fn get_color(&self) -> WallColor {
+        self.color
+    }
+
+    pub fn get_walls(&self) -> &[Point] {
+        &self.walls
+    }
+
+    pub fn contains(&self, wall: &Point) -> bool {
+        self.walls.contains(wall)
+    }
+
+    pub(super) fn add(&mut self, wall: Point) {
+        if !self.contains(&wall) {
+            self.walls.push(wall);
+        }
+    }
+
+    pub(super) fn remove(&mut self, wall: &Point) {
+        self.walls.retain(|w| w != wall);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.walls.is_empty()
+    }
+}
+
+/// What the walls are made of, which decides their glyph and color.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum WallMaterial {
+    #[default]
+    Blocks,
+    Bricks,
+    Trees,
+    Ice,
+}
+
+impl WallMaterial {
+    pub fn next(&self) -> Self {
+        match self {
+            WallMaterial::Blocks => WallMaterial::Bricks,
+            WallMaterial::Bricks => WallMaterial::Trees,
+            WallMaterial::Trees => WallMaterial::Ice,
+            WallMaterial::Ice => WallMaterial::Blocks,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            WallMaterial::Blocks => "blocks",
+            WallMaterial::Bricks => "bricks",
+            WallMaterial::Trees => "trees",
+            WallMaterial::Ice => "ice",
+        }
+    }
+
+    pub fn get_glyph(&self) -> &'static str {
+        match self {
+            WallMaterial::Blocks => "█",
+            WallMaterial::Bricks => "▓",
+            WallMaterial::Trees => "♣",
+            WallMaterial::Ice => "░",
+        }
+    }
+
+    pub fn get_color(&self) -> Option<WallColor> {
+        match self {
+            WallMaterial::Blocks => None,
+            WallMaterial::Bricks => Some(WallColor::Red),
+            WallMaterial::Trees => Some(WallColor::Green),
+            WallMaterial::Ice => Some(WallColor::Cyan),
+        }
+    }
+}
+
+/// How a board's walls look. A glyph or color given here wins over the material's.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct WallStyle {
+    material: WallMaterial,
+    glyph: Option<String>,
+    color: Option<WallColor>,
+}
+
+impl WallStyle {
+    pub fn new(material: WallMaterial, glyph: Option<String>, color: Option<WallColor>) -> Self {
+        Self {
+            material,
+            glyph,
+            color,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn get_material(&self) -> WallMaterial {
+        self.material
+    }
+
+    /// The same style made of `material`, keeping any glyph or color of its own.
+    pub fn with_material(&self, material: WallMaterial) -> Self {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+
+    /// The board's glyph if it is a single printable character, else the material's.
+    pub fn get_glyph(&self) -> &str {
+        match &self.glyph {
+            Some(glyph) if is_printable(glyph) => glyph,
+            _ => self.material.get_glyph(),
+        }
+    }
+
+    pub fn get_color(&self) -> Option<WallColor> {
+        self.color.or(self.material.get_color())
+    }
+}
+
+fn is_printable(glyph: &str) -> bool {
+    let mut chars = glyph.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if !c.is_control() && !c.is_whitespace())
+}
+
+#[cfg(test)]
+mod test_wall_style {
+    use super::{WallColor, WallMaterial, WallStyle};
+
+    #[test]
+    fn falls_back_to_the_material() {
+        let trees = WallStyle::new(WallMaterial::Trees, None, None);
+        assert_eq!(trees.get_glyph(), "♣");
+        assert_eq!(trees.get_color(), Some(WallColor::Green));
+
+        let custom = WallStyle::new(
+            WallMaterial::Ice,
+            Some("#".to_string()),
+            Some(WallColor::Blue),
+        );
+        assert_eq!(custom.get_glyph(), "#");
+        assert_eq!(custom.get_color(), Some(WallColor::Blue));
+
+        for glyph in ["", "ab", "\t", " "] {
+            let broken = WallStyle::new(WallMaterial::Bricks, Some(glyph.to_string()), None);
+            assert_eq!(broken.get_glyph(), "▓");
+        }
+        assert!(WallStyle::default().is_default());
+        assert_eq!(WallStyle::default().get_glyph(), "█");
+    }
+}
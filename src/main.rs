@@ -1,11 +1,31 @@
-mod core;
 mod tui;
 
-use tui::Tui;
+use std::env;
+
+use snake_game::core::Settings;
+use tui::{Script, Tui};
 
 #[tokio::main]
 async fn main() {
-    match Tui::render().await {
+    let cheats = env::args().any(|arg| arg == "--cheats");
+    let profile = env::args().skip_while(|arg| arg != "--profile").nth(1);
+    if let Err(e) = Settings::use_profile(profile.as_deref()) {
+        println!("{e}");
+        return;
+    }
+
+    let script = match env::args().skip_while(|arg| arg != "--script").nth(1) {
+        Some(path) => match Script::load(&path) {
+            Ok(script) => Some(script),
+            Err(e) => {
+                println!("{path}: {e}");
+                return;
+            }
+        },
+        None => None,
+    };
+
+    match Tui::render(cheats, script).await {
         Ok(_) => {}
         Err(e) => println!("{e}"),
     }
@@ -0,0 +1,16 @@
+mod client;
+mod host;
+#[cfg(feature = "packs")]
+mod packs;
+mod protocol;
+mod session;
+mod task;
+mod websocket;
+
+pub use client::Client;
+pub use host::Host;
+#[cfg(feature = "packs")]
+pub use packs::{check_url, fetch_index, Pack};
+pub use protocol::{ClientMessage, ServerMessage, DEFAULT_PORT};
+pub use session::Session;
+pub use task::Task;
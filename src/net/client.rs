@@ -0,0 +1,97 @@
+use tokio::{
+    io::{self, BufReader},
+    net::TcpStream,
+    sync::mpsc,
+};
+
+use super::protocol::{receive, send, ClientMessage, ServerMessage, DEFAULT_PORT};
+use crate::core::Board;
+
+/// Connection to a hosted game; messages are exchanged through channels so the TUI never waits.
+pub struct Client {
+    board: Board,
+    player: Option<usize>,
+    incoming: mpsc::UnboundedReceiver<ServerMessage>,
+    outgoing: mpsc::UnboundedSender<ClientMessage>,
+}
+
+impl Client {
+    /// Connects to `address`, using the default port when none is given.
+    pub async fn connect(address: &str, spectator: bool) -> io::Result<Self> {
+        let address = if address.contains(':') {
+            address.to_string()
+        } else {
+            format!("{address}:{DEFAULT_PORT}")
+        };
+
+        let (reader, mut writer) = TcpStream::connect(address).await?.into_split();
+        let mut reader = BufReader::new(reader);
+
+        send(&mut writer, &ClientMessage::Join { spectator }).await?;
+        let (board, player) = match receive(&mut reader).await? {
+            Some(ServerMessage::Welcome { board, player }) => (*board, player),
+            Some(ServerMessage::Full) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "every snake in the game is taken",
+                ))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the host did not welcome us",
+                ))
+            }
+        };
+
+        let (incoming_sender, incoming) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok(Some(message)) = receive(&mut reader).await {
+                if incoming_sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (outgoing, mut outgoing_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(message) = outgoing_receiver.recv().await {
+                if send(&mut writer, &message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            board,
+            player,
+            incoming,
+            outgoing,
+        })
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /// `None` when watching as a spectator.
+    pub fn get_player(&self) -> Option<usize> {
+        self.player
+    }
+
+    /// `Err` means the host has gone away.
+    pub fn try_recv(&mut self) -> io::Result<Option<ServerMessage>> {
+        match self.incoming.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(mpsc::error::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::error::TryRecvError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "the host has gone away",
+            )),
+        }
+    }
+
+    pub fn send(&self, message: ClientMessage) {
+        let _ = self.outgoing.send(message);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    net::{SocketAddr, UdpSocket},
+    sync::{Arc, Mutex},
+};
+
+use tokio::{
+    io::{self, AsyncBufRead, AsyncWrite, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+    time::{self, Duration},
+};
+
+use super::{
+    protocol::{receive, send, ClientMessage, ServerMessage},
+    websocket,
+};
+use crate::core::{ArenaDelta, ArenaSnapshot, Board};
+
+const BROADCAST_CAPACITY: usize = 64;
+/// How long a new connection has to say which transport it speaks and to join.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const DETECT_POLL: Duration = Duration::from_millis(10);
+
+/// Accepts players on a TCP port and relays their inputs to the authoritative game loop.
+pub struct Host {
+    port: u16,
+    inputs: mpsc::UnboundedReceiver<(usize, ClientMessage)>,
+    shared: Arc<Shared>,
+    accept: JoinHandle<()>,
+}
+
+/// What every connection needs from the host.
+struct Shared {
+    board: Board,
+    /// Whether each snake is controlled; the numbers below `first_player` belong to the host.
+    slots: Mutex<Vec<bool>>,
+    inputs: mpsc::UnboundedSender<(usize, ClientMessage)>,
+    /// Locked while broadcasting, so a connection that subscribes anew and takes the state
+    /// never misses or repeats a change.
+    outgoing: Mutex<Outgoing>,
+}
+
+struct Outgoing {
+    sender: broadcast::Sender<ServerMessage>,
+    state: Option<ArenaSnapshot>,
+}
+
+impl Host {
+    /// Remote players take the lowest free number from `first_player` up to `players - 1`,
+    /// which starts at 1 when the hosting player plays as 0; a number is free again once its
+    /// player leaves. Spectators are not numbered.
+    pub async fn bind(
+        port: u16,
+        board: Board,
+        first_player: usize,
+        players: usize,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+        let port = listener.local_addr()?.port();
+        let (input_sender, inputs) = mpsc::unbounded_channel();
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+
+        let shared = Arc::new(Shared {
+            board,
+            slots: Mutex::new((0..players).map(|slot| slot < first_player).collect()),
+            inputs: input_sender,
+            outgoing: Mutex::new(Outgoing {
+                sender,
+                state: None,
+            }),
+        });
+        let connections = shared.clone();
+        let accept = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Self::serve(stream, connections.clone()));
+            }
+        });
+
+        Ok(Self {
+            port,
+            inputs,
+            shared,
+            accept,
+        })
+    }
+
+    /// Best guess at the address other machines on the LAN can reach this one on.
+    pub fn get_address(&self) -> SocketAddr {
+        let ip = UdpSocket::bind("0.0.0.0:0")
+            .and_then(|socket| {
+                socket.connect("8.8.8.8:80")?;
+                socket.local_addr()
+            })
+            .map(|address| address.ip())
+            .unwrap_or([127, 0, 0, 1].into());
+
+        SocketAddr::new(ip, self.port)
+    }
+
+    /// The snakes that are controlled right now, the hosting player's own included.
+    pub fn get_player_count(&self) -> usize {
+        self.shared
+            .slots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|taken| **taken)
+            .count()
+    }
+
+    pub fn try_recv(&mut self) -> Option<(usize, ClientMessage)> {
+        self.inputs.try_recv().ok()
+    }
+
+    pub async fn recv(&mut self) -> Option<(usize, ClientMessage)> {
+        self.inputs.recv().await
+    }
+
+    pub fn broadcast(&self, message: ServerMessage) {
+        let mut outgoing = self.shared.outgoing.lock().unwrap();
+        if let ServerMessage::State(state) = &message {
+            outgoing.state = Some(state.clone());
+        }
+        let _ = outgoing.sender.send(message);
+    }
+
+    /// Sends the changes that lead to `state`, which is what joining or lagging players get.
+    pub fn broadcast_delta(&self, delta: ArenaDelta, state: ArenaSnapshot) {
+        let mut outgoing = self.shared.outgoing.lock().unwrap();
+        outgoing.state = Some(state);
+        let _ = outgoing.sender.send(ServerMessage::Delta(delta));
+    }
+
+    async fn serve(stream: TcpStream, shared: Arc<Shared>) -> io::Result<()> {
+        let (pongs, mut pings) = mpsc::unbounded_channel();
+        let handshake = time::timeout(HANDSHAKE_TIMEOUT, Self::handshake(stream, &pongs))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "the client did not join"))?;
+        let Some((transport, mut reader, mut writer, spectator)) = handshake? else {
+            return Ok(());
+        };
+
+        let slot = match spectator {
+            true => None,
+            false => match Slot::take(&shared) {
+                Some(slot) => Some(slot),
+                None => return transport.send(&mut writer, &ServerMessage::Full).await,
+            },
+        };
+        let player = slot.as_ref().map(|slot| slot.player);
+
+        let welcome = ServerMessage::Welcome {
+            board: Box::new(shared.board.clone()),
+            player,
+        };
+        transport.send(&mut writer, &welcome).await?;
+
+        let inputs = shared.inputs.clone();
+        if let Some(slot) = &slot {
+            let _ = inputs.send((slot.player, ClientMessage::Join { spectator }));
+        }
+        // Spectators are read from too, so their pings get answered; their inputs are dropped.
+        tokio::spawn(async move {
+            // Holds the slot until the player's connection closes.
+            let slot = slot;
+            while let Ok(Some(message)) = transport.receive(&mut reader, &pongs).await {
+                let Some(slot) = &slot else {
+                    continue;
+                };
+                if inputs.send((slot.player, message)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut outgoing = Self::sync(&shared, &mut writer, transport).await?;
+        loop {
+            tokio::select! {
+                message = outgoing.recv() => match message {
+                    Ok(message) => transport.send(&mut writer, &message).await?,
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        outgoing = Self::sync(&shared, &mut writer, transport).await?;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                },
+                // The reading side stops when the client leaves or sends something it
+                // should not, which ends the connection.
+                payload = pings.recv() => match payload {
+                    Some(payload) => websocket::pong(&mut writer, &payload).await?,
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+
+    /// Finds out the transport, completes a WebSocket upgrade and waits for the client to
+    /// join; `None` when it leaves or asks for something else first.
+    async fn handshake(
+        stream: TcpStream,
+        pongs: &mpsc::UnboundedSender<Vec<u8>>,
+    ) -> io::Result<Option<(Transport, BufReader<OwnedReadHalf>, OwnedWriteHalf, bool)>> {
+        let transport = Transport::detect(&stream).await?;
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        if let Transport::WebSocket = transport {
+            match websocket::read_request(&mut reader).await? {
+                Some(key) => websocket::accept(&mut writer, &key).await?,
+                None => {
+                    websocket::refuse(&mut writer).await?;
+                    return Ok(None);
+                }
+            }
+        }
+
+        match transport.receive(&mut reader, pongs).await? {
+            Some(ClientMessage::Join { spectator }) => {
+                Ok(Some((transport, reader, writer, spectator)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Subscribes from now on and sends the state the coming changes build on, so a player who
+    /// joins mid-game or falls behind does not wait for the next keyframe.
+    async fn sync<W>(
+        shared: &Shared,
+        writer: &mut W,
+        transport: Transport,
+    ) -> io::Result<broadcast::Receiver<ServerMessage>>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (receiver, state) = {
+            let outgoing = shared.outgoing.lock().unwrap();
+            (outgoing.sender.subscribe(), outgoing.state.clone())
+        };
+        if let Some(state) = state {
+            transport.send(writer, &ServerMessage::State(state)).await?;
+        }
+        Ok(receiver)
+    }
+}
+
+/// A snake number held by a connected player and given back when they leave.
+struct Slot {
+    player: usize,
+    shared: Arc<Shared>,
+}
+
+impl Slot {
+    /// `None` when every snake in the arena is taken.
+    fn take(shared: &Arc<Shared>) -> Option<Self> {
+        let mut slots = shared.slots.lock().unwrap();
+        let player = slots.iter().position(|taken| !taken)?;
+        slots[player] = true;
+        Some(Self {
+            player,
+            shared: shared.clone(),
+        })
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.shared.slots.lock().unwrap()[self.player] = false;
+    }
+}
+
+/// TCP clients speak newline-delimited JSON, bots the same JSON in WebSocket frames; any HTTP
+/// request counts as the latter until it turns out not to ask for an upgrade.
+#[derive(Clone, Copy)]
+enum Transport {
+    Lines,
+    WebSocket,
+}
+
+impl Transport {
+    /// Waits until the first bytes tell the transports apart, as they may come a few at a time.
+    async fn detect(stream: &TcpStream) -> io::Result<Self> {
+        const REQUEST: &[u8] = b"GET ";
+
+        let mut start = [0u8; REQUEST.len()];
+        loop {
+            let read = stream.peek(&mut start).await?;
+            if read == 0 || start[..read] != REQUEST[..read] {
+                return Ok(Self::Lines);
+            }
+            if read == REQUEST.len() {
+                return Ok(Self::WebSocket);
+            }
+            time::sleep(DETECT_POLL).await;
+        }
+    }
+
+    async fn receive<R>(
+        self,
+        reader: &mut R,
+        pongs: &mpsc::UnboundedSender<Vec<u8>>,
+    ) -> io::Result<Option<ClientMessage>>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Lines => receive(reader).await,
+            Self::WebSocket => match websocket::receive(reader, pongs).await? {
+                Some(text) => Ok(Some(serde_json::from_str(&text)?)),
+                None => Ok(None),
+            },
+        }
+    }
+
+    async fn send<W>(self, writer: &mut W, message: &ServerMessage) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Lines => send(writer, message).await,
+            Self::WebSocket => websocket::send(writer, &serde_json::to_string(message)?).await,
+        }
+    }
+}
+
+impl Host {
+    /// Stops accepting players and waits until the port is released.
+    pub async fn close(mut self) {
+        self.accept.abort();
+        let _ = (&mut self.accept).await;
+    }
+}
+
+impl Drop for Host {
+    /// Frees the port so another game can be hosted right after this one.
+    fn drop(&mut self) {
+        self.accept.abort();
+    }
+}
+
+#[cfg(test)]
+mod test_host {
+    use tokio::time::{sleep, Duration};
+
+    use super::Host;
+    use crate::{core::Board, net::Client};
+
+    #[tokio::test]
+    async fn turns_players_away_when_full_and_frees_their_snakes() {
+        let host = Host::bind(0, Board::default(), 1, 3).await.unwrap();
+        let address = format!("127.0.0.1:{}", host.get_address().port());
+
+        let first = Client::connect(&address, false).await.unwrap();
+        let second = Client::connect(&address, false).await.unwrap();
+        assert_eq!(first.get_player(), Some(1));
+        assert_eq!(second.get_player(), Some(2));
+        assert_eq!(host.get_player_count(), 3);
+        assert!(Client::connect(&address, false).await.is_err());
+        assert_eq!(
+            Client::connect(&address, true).await.unwrap().get_player(),
+            None
+        );
+
+        drop(first);
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(host.get_player_count(), 2);
+        let third = Client::connect(&address, false).await.unwrap();
+        assert_eq!(third.get_player(), Some(1));
+    }
+}
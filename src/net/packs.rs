@@ -0,0 +1,235 @@
+//! Community board packs: an index of packs and the packs themselves, fetched with a plain
+//! HTTP/1.0 GET. Only `http://` URLs are supported, as there is no TLS support.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::{
+    io::{self, AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time,
+};
+
+use crate::core::{Board, CampaignLevel, DEFAULT_CLEAR_SCORE};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_RESPONSE: u64 = 8 << 20;
+
+/// An entry of the pack index.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Pack {
+    name: String,
+    #[serde(default)]
+    description: String,
+    /// Where the pack's boards file is, absolute or relative to the index.
+    url: String,
+    /// The names of the boards that unlock one after another, each once the one before it
+    /// was cleared. Boards left out are never locked.
+    #[serde(default)]
+    unlock_order: Vec<String>,
+    /// The score a game needs to clear a board of the unlock order.
+    #[serde(default = "default_clear_score")]
+    clear_score: u16,
+}
+
+impl Pack {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// Whether some of the pack's boards unlock one after another.
+    pub fn is_campaign(&self) -> bool {
+        !self.unlock_order.is_empty()
+    }
+
+    /// The pack's boards, in the same format as an exported boards file.
+    pub async fn fetch(&self, index_url: &str) -> io::Result<Vec<Board>> {
+        let json = get(&resolve(index_url, &self.url)).await?;
+        let value: serde_json::Value = serde_json::from_str(&json).map_err(invalid_data)?;
+        let mut boards = if value.is_array() {
+            serde_json::from_value(value).map_err(invalid_data)?
+        } else {
+            serde_json::from_value(value)
+                .map(|board| vec![board])
+                .map_err(invalid_data)?
+        };
+        self.set_levels(&mut boards);
+        Ok(boards)
+    }
+
+    /// Numbers the boards of the unlock order that the pack has, in that order. Only the
+    /// index decides what is locked, whatever the boards file says.
+    fn set_levels(&self, boards: &mut [Board]) {
+        boards.iter_mut().for_each(|board| board.set_campaign(None));
+        let mut level = 0;
+        for name in &self.unlock_order {
+            let Some(board) = boards.iter_mut().find(|board| board.get_name() == name) else {
+                continue;
+            };
+            if board.get_campaign().is_some() {
+                continue;
+            }
+            level += 1;
+            let campaign = CampaignLevel::new(self.name.clone(), level, self.clear_score);
+            board.set_campaign(Some(campaign));
+        }
+    }
+}
+
+fn default_clear_score() -> u16 {
+    DEFAULT_CLEAR_SCORE
+}
+
+/// The packs listed by the index at `url`.
+pub async fn fetch_index(url: &str) -> io::Result<Vec<Pack>> {
+    serde_json::from_str(&get(url).await?).map_err(invalid_data)
+}
+
+async fn get(url: &str) -> io::Result<String> {
+    let (host, path) = split_url(url)?;
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+
+    let request = async {
+        let mut stream = TcpStream::connect(address).await?;
+        let request = format!("GET {path} HTTP/1.0\r\nHost: {host}\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = Vec::new();
+        stream.take(MAX_RESPONSE).read_to_end(&mut response).await?;
+        Ok::<_, io::Error>(response)
+    };
+    let response = time::timeout(TIMEOUT, request)
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "the server did not answer"))??;
+
+    body(&String::from_utf8_lossy(&response))
+}
+
+/// Fails with the reason when packs cannot be fetched from `url`, so it can be refused
+/// before anything is sent.
+pub fn check_url(url: &str) -> io::Result<()> {
+    split_url(url).map(|_| ())
+}
+
+/// The host, with any port, and the path of an `http://` URL.
+fn split_url(url: &str) -> io::Result<(&str, &str)> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        let reason = if url.starts_with("https://") {
+            "https:// URLs are not supported, as there is no TLS support; use an http:// address"
+        } else {
+            "only http:// URLs are supported"
+        };
+        io::Error::new(io::ErrorKind::InvalidInput, reason)
+    })?;
+    Ok(match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash..]),
+        None => (rest, "/"),
+    })
+}
+
+/// `url` as seen from the page at `base`.
+fn resolve(base: &str, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+    let Ok((host, path)) = split_url(base) else {
+        return url.to_string();
+    };
+    if url.starts_with('/') {
+        return format!("http://{host}{url}");
+    }
+    let dir = &path[..path.rfind('/').map_or(0, |slash| slash + 1)];
+    format!("http://{host}{dir}{url}")
+}
+
+/// The body of a successful response.
+fn body(response: &str) -> io::Result<String> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| invalid_data("the response has no body"))?;
+    let status = head.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(io::Error::other(format!(
+            "the server answered \"{status}\""
+        ))),
+    }
+}
+
+fn invalid_data(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+#[cfg(test)]
+mod test_packs {
+    use super::{body, check_url, resolve, split_url, Pack};
+    use crate::core::Board;
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            split_url("http://example.com:8000/packs/index.json").unwrap(),
+            ("example.com:8000", "/packs/index.json")
+        );
+        assert_eq!(
+            split_url("http://example.com").unwrap(),
+            ("example.com", "/")
+        );
+        assert!(split_url("https://example.com/").is_err());
+        assert!(check_url("http://example.com/index.json").is_ok());
+        let https = check_url("https://example.com/index.json").unwrap_err();
+        assert!(https.to_string().contains("TLS"));
+
+        let index = "http://example.com/packs/index.json";
+        assert_eq!(
+            resolve(index, "mazes.json"),
+            "http://example.com/packs/mazes.json"
+        );
+        assert_eq!(
+            resolve(index, "/mazes.json"),
+            "http://example.com/mazes.json"
+        );
+        assert_eq!(
+            resolve(index, "http://other.org/mazes.json"),
+            "http://other.org/mazes.json"
+        );
+    }
+
+    #[test]
+    fn unlock_order() {
+        let pack: Pack = serde_json::from_str(
+            r#"{ "name": "caves", "url": "caves.json", "unlock_order": ["deep", "gone", "mouth"] }"#,
+        )
+        .unwrap();
+        assert!(pack.is_campaign());
+
+        let mut boards: Vec<Board> = ["mouth", "deep", "side"]
+            .map(|name| Board::new(name.to_string(), 10, vec![]))
+            .into();
+        pack.set_levels(&mut boards);
+        let levels: Vec<_> = boards
+            .iter()
+            .map(|board| board.get_campaign().map(|level| level.get_level()))
+            .collect();
+        assert_eq!(levels, [Some(2), Some(1), None]);
+        assert_eq!(boards[0].get_campaign().unwrap().get_clear_score(), 10);
+    }
+
+    #[test]
+    fn responses() {
+        let ok = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n[]";
+        assert_eq!(body(ok).unwrap(), "[]");
+
+        let missing = "HTTP/1.0 404 Not Found\r\n\r\nnope";
+        assert!(body(missing).unwrap_err().to_string().contains("404"));
+        assert!(body("garbage").is_err());
+    }
+}
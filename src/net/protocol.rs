@@ -0,0 +1,84 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::core::{ArenaDelta, ArenaSnapshot, Board, Direction, Versus};
+
+pub const DEFAULT_PORT: u16 = 7777;
+/// Longer lines end the connection, so a peer cannot make the other side buffer without end.
+const MAX_LINE: u64 = 1 << 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ClientMessage {
+    /// Spectators get the live state but no snake, and their inputs are ignored.
+    Join {
+        spectator: bool,
+    },
+    Input(Direction),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ServerMessage {
+    Welcome {
+        board: Box<Board>,
+        player: Option<usize>,
+    },
+    /// The whole arena, sent when a round starts and every few ticks so late joiners can sync.
+    State(ArenaSnapshot),
+    /// Changes since the previous `State` or `Delta`.
+    Delta(ArenaDelta),
+    /// Sent when a versus match starts and after every round.
+    Versus(Versus),
+    GameOver,
+    /// Sent instead of `Welcome` to a player when every snake is taken.
+    Full,
+}
+
+/// Messages travel as one JSON document per line.
+pub async fn send<W, T>(writer: &mut W, message: &T) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
+
+/// Returns `None` once the peer closes the connection.
+pub async fn receive<R, T>(reader: &mut R) -> io::Result<Option<T>>
+where
+    R: AsyncBufRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut line = String::new();
+    let read = reader.take(MAX_LINE).read_line(&mut line).await?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if read as u64 == MAX_LINE && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message too long",
+        ));
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+#[cfg(test)]
+mod test_protocol {
+    use super::{receive, ClientMessage, MAX_LINE};
+
+    #[tokio::test]
+    async fn refuses_endless_lines() {
+        let mut lines = "{\"Join\":{\"spectator\":true}}\n".as_bytes();
+        let message: Option<ClientMessage> = receive(&mut lines).await.unwrap();
+        assert!(matches!(
+            message,
+            Some(ClientMessage::Join { spectator: true })
+        ));
+
+        let endless = vec![b' '; MAX_LINE as usize + 10];
+        let message = receive::<_, ClientMessage>(&mut endless.as_slice()).await;
+        assert_eq!(message.unwrap_err().to_string(), "message too long");
+    }
+}
@@ -0,0 +1,135 @@
+use tokio::time::{self, Duration, Instant, Interval, MissedTickBehavior};
+
+use super::{ClientMessage, Host, ServerMessage};
+use crate::core::{Arena, ArenaMode, ArenaSnapshot, Board, Direction, Versus};
+
+const TICK: Duration = Duration::from_millis(80);
+const ROUND_PAUSE: Duration = Duration::from_millis(1500);
+const SNAKE_LENGTH: u16 = 3;
+const KEYFRAME_TICKS: u32 = 25;
+
+/// The authoritative side of a networked game: applies inputs, moves the arena and broadcasts it.
+pub struct Session {
+    arena: Arena,
+    versus: Option<Versus>,
+    sent: ArenaSnapshot,
+    ticks: u32,
+    /// The next versus round starts once this passes; ticks until then only wait.
+    paused_until: Option<Instant>,
+}
+
+impl Session {
+    /// Starts the match, or ends it for everyone at once when the board has no room for
+    /// every snake.
+    pub fn start(
+        host: &Host,
+        board: Board,
+        mode: ArenaMode,
+        rounds: u8,
+        players: usize,
+    ) -> Option<Self> {
+        let Some(arena) = Arena::new(board, mode, players, SNAKE_LENGTH) else {
+            host.broadcast(ServerMessage::GameOver);
+            return None;
+        };
+        let versus = (mode == ArenaMode::Versus).then(|| Versus::new(rounds, players));
+
+        if let Some(versus) = &versus {
+            host.broadcast(ServerMessage::Versus(versus.clone()));
+        }
+        let sent = arena.snapshot();
+        host.broadcast(ServerMessage::State(sent.clone()));
+
+        Some(Self {
+            arena,
+            versus,
+            sent,
+            ticks: 0,
+            paused_until: None,
+        })
+    }
+
+    pub fn get_arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    pub fn get_versus(&self) -> Option<&Versus> {
+        self.versus.as_ref()
+    }
+
+    /// Ticks at the game's pace, for the caller to run [`Session::tick`] on while it waits for
+    /// inputs in between. A tick that comes late does not make the next ones come early.
+    pub fn interval() -> Interval {
+        let mut interval = time::interval(TICK);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    }
+
+    pub fn rotation(&mut self, player: usize, direction: Direction) {
+        self.arena.rotation(player, direction);
+    }
+
+    /// Applies a message from a remote player as soon as it arrives.
+    pub fn handle(&mut self, player: usize, message: ClientMessage) {
+        if self.paused_until.is_some() {
+            // Turns made between rounds do not carry over into the next one.
+            return;
+        }
+        if let ClientMessage::Input(direction) = message {
+            self.arena.rotation(player, direction);
+        }
+    }
+
+    /// Plays one tick with the inputs received so far; returns whether the match goes on.
+    pub fn tick(&mut self, host: &mut Host) -> bool {
+        while let Some((player, message)) = host.try_recv() {
+            self.handle(player, message);
+        }
+
+        if let Some(until) = self.paused_until {
+            if Instant::now() >= until {
+                self.paused_until = None;
+                self.arena.reset();
+                self.publish(host, true);
+            }
+            return true;
+        }
+
+        let going_on = self.arena.walk();
+        self.ticks += 1;
+        self.publish(host, self.ticks.is_multiple_of(KEYFRAME_TICKS));
+
+        if going_on {
+            return true;
+        }
+
+        match &mut self.versus {
+            Some(versus) => {
+                versus.finish_round(self.arena.get_survivor());
+                host.broadcast(ServerMessage::Versus(versus.clone()));
+                if versus.is_over() {
+                    host.broadcast(ServerMessage::GameOver);
+                    return false;
+                }
+
+                self.paused_until = Some(Instant::now() + ROUND_PAUSE);
+                true
+            }
+            None => {
+                host.broadcast(ServerMessage::GameOver);
+                false
+            }
+        }
+    }
+
+    /// Sends the whole arena when `keyframe` is set and only the changes otherwise.
+    fn publish(&mut self, host: &Host, keyframe: bool) {
+        let snapshot = self.arena.snapshot();
+        if keyframe {
+            host.broadcast(ServerMessage::State(snapshot.clone()));
+        } else {
+            host.broadcast_delta(self.arena.delta(&self.sent), snapshot.clone());
+        }
+        self.sent = snapshot;
+    }
+}
@@ -0,0 +1,58 @@
+use std::future::Future;
+
+use tokio::{
+    io,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Network work running on the tokio runtime, its result handed back through a channel so
+/// a screen can keep drawing and reading keys until it is in.
+pub struct Task<T> {
+    result: oneshot::Receiver<io::Result<T>>,
+}
+
+impl<T: Send + 'static> Task<T> {
+    /// Starts `work`; it must be called from within the runtime.
+    pub fn spawn(work: impl Future<Output = io::Result<T>> + Send + 'static) -> Self {
+        let (sender, result) = oneshot::channel();
+        tokio::spawn(async move {
+            // Nobody is waiting any more when the screen was left before the work was done.
+            let _ = sender.send(work.await);
+        });
+        Self { result }
+    }
+
+    /// The result once the work is done, `None` while it is still going.
+    pub fn try_take(&mut self) -> Option<io::Result<T>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err(io::Error::other("the network task stopped"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_task {
+    use tokio::{
+        io,
+        time::{sleep, Duration},
+    };
+
+    use super::Task;
+
+    #[tokio::test]
+    async fn hands_back_the_result() {
+        let mut task = Task::spawn(async {
+            sleep(Duration::from_millis(20)).await;
+            Ok(7)
+        });
+        assert!(task.try_take().is_none());
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(task.try_take().unwrap().unwrap(), 7);
+
+        let mut failed: Task<()> = Task::spawn(async { Err(io::Error::other("no route")) });
+        sleep(Duration::from_millis(50)).await;
+        assert!(failed.try_take().unwrap().is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Just enough of RFC 6455 for bots and other programs: the opening handshake, unfragmented
+//! or fragmented text frames and pings. Payloads are the same JSON messages the TCP clients use.
+
+use tokio::{
+    io::{self, AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::mpsc,
+};
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Limits a whole message, however many frames it comes in.
+const MAX_MESSAGE: u64 = 1 << 20;
+const MAX_CONTROL_PAYLOAD: u64 = 125;
+const MAX_HEADER_LINE: u64 = 8 << 10;
+const MAX_HEADERS: usize = 100;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Reads an HTTP request and returns its `Sec-WebSocket-Key`, `None` for a plain page request.
+pub async fn read_request<R>(reader: &mut R) -> io::Result<Option<String>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut key = None;
+    for _ in 0..MAX_HEADERS {
+        let mut line = String::new();
+        if (&mut *reader)
+            .take(MAX_HEADER_LINE)
+            .read_line(&mut line)
+            .await?
+            == 0
+        {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if !line.ends_with('\n') {
+            return Err(invalid("request line too long"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(key);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+    Err(invalid("too many request headers"))
+}
+
+/// Completes the opening handshake for a request carrying `key`.
+pub async fn accept<W>(writer: &mut W, key: &str) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    );
+    writer.write_all(response.as_bytes()).await
+}
+
+/// Answers a plain HTTP request, as the host only speaks WebSocket over HTTP.
+pub async fn refuse<W>(writer: &mut W) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let response = "HTTP/1.1 426 Upgrade Required\r\nUpgrade: websocket\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    writer.write_all(response.as_bytes()).await
+}
+
+/// Reads the next text message, `None` once the peer closes the connection. Pings may come
+/// between the frames of a message; their payloads go to `pongs` for the writing side to answer.
+pub async fn receive<R>(
+    reader: &mut R,
+    pongs: &mpsc::UnboundedSender<Vec<u8>>,
+) -> io::Result<Option<String>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut message = Vec::new();
+    loop {
+        let mut header = [0u8; 2];
+        if reader.read_exact(&mut header).await.is_err() {
+            return Ok(None);
+        }
+
+        let fin = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0f;
+        let masked = header[1] & 0x80 != 0;
+        let len = match header[1] & 0x7f {
+            126 => reader.read_u16().await? as u64,
+            127 => reader.read_u64().await?,
+            len => len as u64,
+        };
+        if opcode >= OPCODE_CLOSE && len > MAX_CONTROL_PAYLOAD {
+            return Err(invalid("control frame too large"));
+        }
+        if message.len() as u64 + len > MAX_MESSAGE {
+            return Err(invalid("message too large"));
+        }
+
+        let mut mask = [0u8; 4];
+        if masked {
+            reader.read_exact(&mut mask).await?;
+        }
+        let mut payload = vec![0u8; len as usize];
+        reader.read_exact(&mut payload).await?;
+        if masked {
+            payload
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, byte)| *byte ^= mask[i % 4]);
+        }
+
+        match opcode {
+            OPCODE_CLOSE => return Ok(None),
+            OPCODE_PING => {
+                let _ = pongs.send(payload);
+                continue;
+            }
+            OPCODE_TEXT | OPCODE_CONTINUATION => message.extend(payload),
+            // Binary frames are not part of the protocol.
+            _ => continue,
+        }
+
+        if fin {
+            return String::from_utf8(message)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+    }
+}
+
+/// Answers a ping with the same payload.
+pub async fn pong<W>(writer: &mut W, payload: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    write_frame(writer, OPCODE_PONG, payload).await
+}
+
+/// Sends `text` as a single unmasked text frame, as servers do.
+pub async fn send<W>(writer: &mut W, text: &str) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    write_frame(writer, OPCODE_TEXT, text.as_bytes()).await
+}
+
+async fn write_frame<W>(writer: &mut W, opcode: u8, payload: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+    }
+    frame.extend(payload);
+    writer.write_all(&frame).await
+}
+
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{GUID}").as_bytes()))
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend((data.len() as u64 * 8).to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([
+                chunk[4 * i],
+                chunk[4 * i + 1],
+                chunk[4 * i + 2],
+                chunk[4 * i + 3],
+            ]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (h, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (i, word) in h.iter().enumerate() {
+        digest[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test_websocket {
+    use tokio::sync::mpsc;
+
+    use super::{accept_key, read_request, receive, send, MAX_HEADER_LINE, MAX_MESSAGE};
+
+    fn frame(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![(fin as u8) << 7 | opcode, 0x80 | 126];
+        frame.extend((payload.len() as u16).to_be_bytes());
+        frame.extend([0; 4]);
+        frame.extend(payload);
+        frame
+    }
+
+    #[test]
+    fn accept_key_from_rfc() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[tokio::test]
+    async fn masked_text_round_trip() {
+        let mask = [1u8, 2, 3, 4];
+        let mut frame = vec![0x81, 0x80 | 5];
+        frame.extend(mask);
+        frame.extend(b"hello".iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+
+        let (pongs, _) = mpsc::unbounded_channel();
+        assert_eq!(
+            receive(&mut frame.as_slice(), &pongs).await.unwrap(),
+            Some("hello".to_string())
+        );
+
+        let mut sent = Vec::new();
+        send(&mut sent, "hello").await.unwrap();
+        assert_eq!(sent, [&[0x81, 5][..], b"hello"].concat());
+    }
+
+    #[tokio::test]
+    async fn pings_between_fragments_are_handed_on() {
+        let frames = [
+            frame(false, 0x1, b"hel"),
+            frame(true, 0x9, b"are you there"),
+            frame(true, 0x0, b"lo"),
+        ]
+        .concat();
+
+        let (pongs, mut pings) = mpsc::unbounded_channel();
+        assert_eq!(
+            receive(&mut frames.as_slice(), &pongs).await.unwrap(),
+            Some("hello".to_string())
+        );
+        assert_eq!(pings.try_recv().unwrap(), b"are you there");
+    }
+
+    #[tokio::test]
+    async fn caps_messages_and_requests() {
+        let chunk = vec![b'a'; u16::MAX as usize];
+        let fragments = (MAX_MESSAGE as usize / chunk.len() + 1) as u8;
+        let frames: Vec<u8> = (0..=fragments)
+            .flat_map(|i| frame(i == fragments, if i == 0 { 0x1 } else { 0x0 }, &chunk))
+            .collect();
+        let (pongs, _) = mpsc::unbounded_channel();
+        let error = receive(&mut frames.as_slice(), &pongs).await.unwrap_err();
+        assert_eq!(error.to_string(), "message too large");
+
+        let request = format!(
+            "GET / HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_HEADER_LINE as usize)
+        );
+        assert!(read_request(&mut request.as_bytes()).await.is_err());
+
+        let request = "GET / HTTP/1.1\r\nSec-WebSocket-Key: abc\r\n\r\n";
+        assert_eq!(
+            read_request(&mut request.as_bytes()).await.unwrap(),
+            Some("abc".to_string())
+        );
+    }
+}
@@ -1,94 +1,506 @@
+mod cast;
 mod create_board;
+mod debug;
+mod effects;
+mod events;
 mod game;
 mod game_over;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod heatmap;
+mod i18n;
+mod import_boards;
+mod kitty;
+#[cfg(feature = "net")]
+mod lan;
+mod main_menu;
+mod modal;
+mod objective_over;
+mod open_replay;
+#[cfg(feature = "packs")]
+mod packs;
+#[cfg(feature = "presence")]
+mod presence;
+mod puzzle_over;
 mod scoreboard;
+mod script;
 mod select_board;
+mod settings;
+mod size_guard;
+mod sound;
+mod spotlight;
+mod store_error;
+mod terminal_query;
+mod text_input;
+mod theme;
+mod toast;
 
-use crate::core::{Board, Game};
+use snake_game::core::{
+    append_game, check_files, take_store_errors, Board, Boards, CampaignProgress, Difficulty, Game,
+    Mode, RecentBoards, Replay, SavedGame, ScoreCategory, Settings, Tutorial,
+};
 
-use std::io;
+use std::{
+    io,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use create_board::CreateBoardTui;
 use game::GameTui;
 use game_over::GameOverTui;
+use heatmap::HeatmapTui;
+use i18n::{t, tf};
+use import_boards::ImportBoardsTui;
+#[cfg(feature = "net")]
+use lan::LanTui;
+use main_menu::{MainMenuTui, MainMenuTuiResult};
+use objective_over::ObjectiveOverTui;
+use open_replay::OpenReplayTui;
+#[cfg(feature = "packs")]
+use packs::PacksTui;
+use puzzle_over::PuzzleOverTui;
 use ratatui::DefaultTerminal;
 use scoreboard::ScoreboardTui;
+pub use script::Script;
 use select_board::{SelectBoardTui, SelectBoardTuiResult};
+use settings::SettingsTui;
+use size_guard::{SizeGuardTui, MIN_HEIGHT, MIN_WIDTH};
+use store_error::StoreErrorTui;
+
+/// What the selected board is going to be used for.
+#[derive(Clone, Copy)]
+enum Purpose {
+    Play,
+    /// A race to the target length set in the settings.
+    Race,
+    Zen,
+    Hardcore,
+    /// Clearing a board covered in pellets.
+    Pellets,
+    /// A board that grows walls with the score.
+    Maze,
+    #[cfg(feature = "net")]
+    Host,
+}
 
 enum State {
-    SelectBoard,
+    MainMenu,
+    SelectBoard(Purpose),
     CreateBoard,
-    PlayGame(Board),
-    GameOver(u16, String),
+    EditBoard(Board),
+    ImportBoards,
+    PlayGame(Board, Mode),
+    /// Carries on the game saved for later.
+    ContinueGame,
+    Tutorial,
+    Attract,
+    #[cfg(feature = "net")]
+    HostGame(Board),
+    #[cfg(feature = "net")]
+    JoinGame,
+    #[cfg(feature = "net")]
+    WatchGame,
+    OpenReplay,
+    WatchReplay(Replay),
+    /// The score, `None` for a race that did not reach its target.
+    GameOver(Option<u16>, String, Replay),
+    PuzzleOver(Option<u16>, String),
+    /// The time an objective was met in, `None` when the timer ran out.
+    ObjectiveOver(Option<u16>, String),
     Scoreboard,
+    BoardScores(String),
+    DeathHeatmap(Board),
+    #[cfg(feature = "packs")]
+    BoardPacks,
+    Settings,
 }
 
 struct App {
     state: State,
+    cheats: bool,
     exit: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(cheats: bool) -> Self {
+        let state = if Settings::new().is_tutorial_done() {
+            State::MainMenu
+        } else {
+            State::Tutorial
+        };
+
         Self {
-            state: State::SelectBoard,
+            state,
+            cheats,
             exit: false,
         }
     }
 
     pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         while !self.exit {
+            check_files();
+            if !StoreErrorTui::new(take_store_errors()).run(terminal)? {
+                break;
+            }
+            if !SizeGuardTui::new(MIN_WIDTH, MIN_HEIGHT).run(terminal)? {
+                break;
+            }
             self.state = match &self.state {
-                State::SelectBoard => {
-                    let mut select_board_tui = SelectBoardTui::new();
+                State::MainMenu => {
+                    let mut main_menu_tui = MainMenuTui::new();
 
-                    match select_board_tui.run(terminal)? {
-                        SelectBoardTuiResult::Board(board) => State::PlayGame(board),
-                        SelectBoardTuiResult::Exit => {
+                    match main_menu_tui.run(terminal)? {
+                        MainMenuTuiResult::Continue => State::ContinueGame,
+                        MainMenuTuiResult::Play => State::SelectBoard(Purpose::Play),
+                        MainMenuTuiResult::Race => State::SelectBoard(Purpose::Race),
+                        MainMenuTuiResult::Zen => State::SelectBoard(Purpose::Zen),
+                        MainMenuTuiResult::Hardcore => State::SelectBoard(Purpose::Hardcore),
+                        MainMenuTuiResult::Pellets => State::SelectBoard(Purpose::Pellets),
+                        MainMenuTuiResult::Maze => State::SelectBoard(Purpose::Maze),
+                        MainMenuTuiResult::Tutorial => State::Tutorial,
+                        MainMenuTuiResult::WatchReplay => State::OpenReplay,
+                        MainMenuTuiResult::Attract => State::Attract,
+                        #[cfg(feature = "net")]
+                        MainMenuTuiResult::HostGame => State::SelectBoard(Purpose::Host),
+                        #[cfg(feature = "net")]
+                        MainMenuTuiResult::JoinGame => State::JoinGame,
+                        #[cfg(feature = "net")]
+                        MainMenuTuiResult::WatchGame => State::WatchGame,
+                        MainMenuTuiResult::ScoreBoards => State::Scoreboard,
+                        #[cfg(feature = "packs")]
+                        MainMenuTuiResult::BoardPacks => State::BoardPacks,
+                        MainMenuTuiResult::Settings => State::Settings,
+                        MainMenuTuiResult::Exit => {
                             self.exit = true;
-                            State::SelectBoard
+                            State::MainMenu
                         }
+                    }
+                }
+                State::SelectBoard(purpose) => {
+                    let mode = match purpose {
+                        Purpose::Race => Mode::TargetLength(Settings::new().get_target_length()),
+                        Purpose::Zen => Mode::Zen,
+                        Purpose::Hardcore => Mode::Hardcore,
+                        Purpose::Pellets => Mode::Pellets,
+                        Purpose::Maze => Mode::Maze,
+                        Purpose::Play => Mode::Classic,
+                        #[cfg(feature = "net")]
+                        Purpose::Host => Mode::Classic,
+                    };
+                    let mut select_board_tui = SelectBoardTui::new().with_mode(mode);
+
+                    match select_board_tui.run(terminal)? {
+                        SelectBoardTuiResult::Board(board) => match purpose {
+                            Purpose::Play
+                            | Purpose::Race
+                            | Purpose::Zen
+                            | Purpose::Hardcore
+                            | Purpose::Pellets
+                            | Purpose::Maze => State::PlayGame(board, mode),
+                            #[cfg(feature = "net")]
+                            Purpose::Host => State::HostGame(board),
+                        },
+                        SelectBoardTuiResult::Exit => State::MainMenu,
                         SelectBoardTuiResult::CreateBoard => State::CreateBoard,
+                        SelectBoardTuiResult::EditBoard(board) => State::EditBoard(board),
+                        SelectBoardTuiResult::ImportBoards => State::ImportBoards,
                         SelectBoardTuiResult::ScoreBoards => State::Scoreboard,
+                        SelectBoardTuiResult::BoardScores(name) => State::BoardScores(name),
+                        SelectBoardTuiResult::DeathHeatmap(board) => State::DeathHeatmap(board),
+                        SelectBoardTuiResult::Settings => State::Settings,
                     }
                 }
                 State::CreateBoard => {
                     let mut create_board_tui = CreateBoardTui::new();
                     create_board_tui.run(terminal)?;
-                    State::SelectBoard
+                    State::SelectBoard(Purpose::Play)
+                }
+                State::EditBoard(board) => {
+                    let mut create_board_tui = CreateBoardTui::edit(board.clone());
+                    create_board_tui.run(terminal)?;
+                    State::SelectBoard(Purpose::Play)
+                }
+                State::ImportBoards => {
+                    ImportBoardsTui::new().run(terminal)?;
+                    State::SelectBoard(Purpose::Play)
+                }
+                State::PlayGame(board, mode) => {
+                    let settings = Settings::new();
+                    let mut game =
+                        Game::with_seed(board, 3, script::seed().unwrap_or_else(rand::random));
+                    let apples = board
+                        .get_rules()
+                        .get_apples()
+                        .unwrap_or(settings.get_apples());
+                    game.set_food_count(apples as usize);
+                    game.set_shifting_walls(settings.get_shifting_walls() as u16);
+                    game.set_moving_food(settings.is_moving_food_enabled());
+                    game.set_mice(settings.is_mice_enabled());
+                    match mode {
+                        Mode::TargetLength(target) => game.set_target_length(*target),
+                        Mode::Hardcore => game.set_hardcore(true),
+                        Mode::Classic | Mode::Zen | Mode::Pellets | Mode::Maze => {}
+                    }
+                    if settings.is_roaming_snakes_enabled() && board.get_puzzle().is_none() {
+                        game.set_hazards(Difficulty::estimate(board).get_hazards());
+                    }
+                    if *mode == Mode::Pellets {
+                        game.start_pellets();
+                    }
+                    if *mode == Mode::Maze {
+                        game.start_maze();
+                    }
+
+                    RecentBoards::new().add(board.get_name().to_string(), now());
+
+                    let mut game_tui = GameTui::new(game, self.cheats).with_saving(*mode);
+                    if settings.is_adaptive_speed_enabled() {
+                        game_tui = game_tui.with_adaptive_speed();
+                    }
+                    self.play(terminal, game_tui, board, *mode).await?
+                }
+                State::ContinueGame => match SavedGame::load() {
+                    Some(saved) => {
+                        let mode = saved.get_mode();
+                        let mut game_tui = GameTui::new(saved.get_snapshot().resume(), self.cheats)
+                            .with_saving(mode)
+                            .with_elapsed(Duration::from_millis(saved.get_elapsed_ms()));
+                        if Settings::new().is_adaptive_speed_enabled() {
+                            game_tui = game_tui.with_adaptive_speed();
+                        }
+                        let board = saved.get_snapshot().get_board();
+                        self.play(terminal, game_tui, board, mode).await?
+                    }
+                    None => State::MainMenu,
+                },
+                State::Tutorial => {
+                    let board = Tutorial::board();
+                    let mut game_tui = GameTui::new(Game::new(&board, 3), false).with_tutorial();
+                    game_tui.run(terminal).await?;
+
+                    Settings::new().set_tutorial_done();
+                    State::MainMenu
                 }
-                State::PlayGame(board) => {
-                    let mut game_tui = GameTui::new(Game::new(board, 3));
-                    let score = game_tui.run(terminal).await?;
-                    State::GameOver(score, board.get_name().to_string())
+                #[cfg(feature = "net")]
+                State::HostGame(board) => {
+                    let mut lan_tui = LanTui::host(board.clone()).await;
+                    lan_tui.run(terminal).await?;
+                    State::MainMenu
                 }
-                State::GameOver(score, board_name) => {
-                    let game_over_tui = GameOverTui::new(board_name.to_owned(), *score);
+                #[cfg(feature = "net")]
+                State::JoinGame => {
+                    let mut lan_tui = LanTui::join();
+                    lan_tui.run(terminal).await?;
+                    State::MainMenu
+                }
+                #[cfg(feature = "net")]
+                State::WatchGame => {
+                    let mut lan_tui = LanTui::watch();
+                    lan_tui.run(terminal).await?;
+                    State::MainMenu
+                }
+                State::Attract => {
+                    let board = Boards::new().get(0).cloned().unwrap_or_default();
+                    let mut game_tui = GameTui::new(Game::new(&board, 3), false).with_demo();
+                    game_tui.run(terminal).await?;
+                    State::MainMenu
+                }
+                State::OpenReplay => {
+                    let mut open_replay_tui = OpenReplayTui::new();
+                    match open_replay_tui.run(terminal)? {
+                        Some(replay) => State::WatchReplay(replay),
+                        None => State::MainMenu,
+                    }
+                }
+                State::WatchReplay(replay) => {
+                    let board = replay.get_board().clone();
+                    let mut game_tui =
+                        GameTui::new(replay.start(&board), false).with_replay(replay.clone());
+                    game_tui.run(terminal).await?;
+                    State::MainMenu
+                }
+                State::GameOver(score, board_name, replay) => {
+                    let mut game_over_tui =
+                        GameOverTui::new(board_name.to_owned(), *score, replay.clone());
                     game_over_tui.run(terminal).await?;
+                    let rules = replay.get_rules();
+                    if game_over_tui.is_restarting() {
+                        let mode = ScoreCategory::from_rules(&rules).get_mode();
+                        State::PlayGame(replay.get_board().clone(), mode)
+                    } else {
+                        if let Some((category, score)) = game_over_tui.get_new_record() {
+                            ScoreboardTui::new()
+                                .with_board(board_name)
+                                .with_new_record(category, score)
+                                .run(terminal)?;
+                        }
+                        match rules.target_length {
+                            _ if rules.hardcore => State::SelectBoard(Purpose::Hardcore),
+                            _ if rules.pellets => State::SelectBoard(Purpose::Pellets),
+                            _ if rules.maze => State::SelectBoard(Purpose::Maze),
+                            Some(_) => State::SelectBoard(Purpose::Race),
+                            None => State::SelectBoard(Purpose::Play),
+                        }
+                    }
+                }
+                State::PuzzleOver(moves, board_name) => {
+                    let puzzle_over_tui = PuzzleOverTui::new(board_name.to_owned(), *moves);
+                    puzzle_over_tui.run(terminal).await?;
+
+                    State::SelectBoard(Purpose::Play)
+                }
+                State::ObjectiveOver(time, board_name) => {
+                    let objective_over_tui = ObjectiveOverTui::new(board_name.to_owned(), *time);
+                    objective_over_tui.run(terminal).await?;
 
-                    State::SelectBoard
+                    State::SelectBoard(Purpose::Play)
                 }
                 State::Scoreboard => {
                     let mut scoreboard = ScoreboardTui::new();
                     scoreboard.run(terminal)?;
-                    State::SelectBoard
+                    State::MainMenu
+                }
+                State::BoardScores(board_name) => {
+                    let mut scoreboard = ScoreboardTui::new().with_board(board_name);
+                    scoreboard.run(terminal)?;
+                    State::SelectBoard(Purpose::Play)
+                }
+                State::DeathHeatmap(board) => {
+                    HeatmapTui::new(board.clone()).run(terminal)?;
+                    State::SelectBoard(Purpose::Play)
+                }
+                #[cfg(feature = "packs")]
+                State::BoardPacks => {
+                    PacksTui::new().run(terminal)?;
+                    State::MainMenu
+                }
+                State::Settings => {
+                    let mut settings = SettingsTui::new();
+                    settings.run(terminal)?;
+                    State::MainMenu
                 }
             };
         }
 
+        // The last screen may have failed to save on its way out.
+        StoreErrorTui::new(take_store_errors()).run(terminal)?;
         terminal.clear()?;
 
         Ok(())
     }
 }
 
+impl App {
+    /// Plays `game_tui` on `board` to its end and logs it; what comes next depends on how it
+    /// ended. A game saved for later goes back to the main menu, any other clears the save.
+    async fn play(
+        &self,
+        terminal: &mut DefaultTerminal,
+        mut game_tui: GameTui<'_>,
+        board: &Board,
+        mode: Mode,
+    ) -> io::Result<State> {
+        let purpose = match mode {
+            Mode::TargetLength(_) => Purpose::Race,
+            Mode::Zen => Purpose::Zen,
+            Mode::Hardcore => Purpose::Hardcore,
+            Mode::Pellets => Purpose::Pellets,
+            Mode::Maze => Purpose::Maze,
+            Mode::Classic => Purpose::Play,
+        };
+
+        if mode == Mode::Zen {
+            game_tui = game_tui.with_zen();
+        }
+        #[cfg(feature = "presence")]
+        if Settings::new().is_discord_presence_enabled() {
+            game_tui = game_tui.with_presence(board.get_name(), mode);
+        }
+        let points = game_tui.run(terminal).await?;
+        if game_tui.is_suspended() {
+            toast::push(t("app.game_saved"));
+            return Ok(State::MainMenu);
+        }
+        if let Err(e) = SavedGame::clear() {
+            toast::push(tf("app.clear_failed", &[("error", &e)]));
+        }
+        if game_tui.is_restarting() {
+            // The game left behind is dropped, neither logged nor scored.
+            return Ok(State::PlayGame(board.clone(), mode));
+        }
+
+        let score = match mode {
+            Mode::TargetLength(_) | Mode::Pellets => game_tui.get_finish_time(),
+            Mode::Classic | Mode::Hardcore | Mode::Maze => Some(points),
+            Mode::Zen => None,
+        };
+        if !self.cheats && mode != Mode::Zen {
+            let record = game_tui.get_record(board.get_name(), mode, now());
+            if let Err(e) = append_game(&record) {
+                toast::push(tf("app.log_failed", &[("error", &e)]));
+            }
+            if let Some(level) = board.get_campaign() {
+                if CampaignProgress::new().record(level, points) {
+                    toast::push(tf(
+                        "app.level_cleared",
+                        &[("level", &level.get_level()), ("pack", &level.get_pack())],
+                    ));
+                }
+            }
+        }
+        Ok(if self.cheats || mode == Mode::Zen {
+            // Cheated and zen runs never reach the scoreboard.
+            State::SelectBoard(purpose)
+        } else if board.get_puzzle().is_some() {
+            State::PuzzleOver(game_tui.get_cleared_moves(), board.get_name().to_string())
+        } else if board.get_objective().is_some() {
+            // Objectives are won or lost, kept apart from the endless scores.
+            State::ObjectiveOver(game_tui.get_objective_time(), board.get_name().to_string())
+        } else {
+            State::GameOver(score, board.get_name().to_string(), game_tui.get_replay())
+        })
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 pub struct Tui {}
 
 impl Tui {
-    pub async fn render() -> Result<(), std::io::Error> {
+    /// `cheats` turns on the level-testing cheats for single-player games. With a `script`,
+    /// its keys are played instead of the keyboard's and the state of the last game is
+    /// printed once it is over.
+    pub async fn render(cheats: bool, script: Option<Script>) -> Result<(), std::io::Error> {
+        #[cfg(feature = "gamepad")]
+        if script.is_none() {
+            gamepad::start(snake_game::core::Settings::new().get_gamepad_mapping());
+        }
+
+        i18n::load(Settings::new().get_language());
         let mut terminal = ratatui::init();
-        let app_result = App::new().run(&mut terminal).await;
+        theme::detect();
+        kitty::detect();
+        let scripted = script.is_some();
+        match script {
+            Some(script) => events::start_script(script),
+            None => events::start(),
+        }
+        let app_result = App::new(cheats).run(&mut terminal).await;
         ratatui::restore();
+        let finished = match &app_result {
+            Ok(()) => true,
+            Err(e) => script::is_end(e),
+        };
+        if scripted && finished {
+            println!("{}", script::take_state());
+            return Ok(());
+        }
         app_result
     }
 }
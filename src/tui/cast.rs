@@ -0,0 +1,265 @@
+//! Writes the frames of a game to an asciinema v2 `.cast` file as they are drawn. Each frame
+//! is only what changed since the one before, as the escape codes a terminal would get. A
+//! single frame can also be saved whole, as plain text and with its colors. Both go to the
+//! data directory, named after the time they were taken.
+
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Position,
+    style::{Color, Modifier},
+    text::Span,
+};
+
+/// Where recorded games are written.
+pub const CAST_DIR: &str = "./src/casts";
+/// Where saved frames are written.
+pub const SNAPSHOT_DIR: &str = "./src/snapshots";
+
+pub struct CastRecorder {
+    file: BufWriter<File>,
+    started: Instant,
+    /// The frame written last, to send only the cells that changed.
+    last: Buffer,
+}
+
+impl CastRecorder {
+    /// Starts `game-<seconds>.cast` in `CAST_DIR` for a terminal of `width`x`height`,
+    /// returning it with its path.
+    pub fn start(width: u16, height: u16) -> io::Result<(Self, String)> {
+        let (file, path) = create_unique(CAST_DIR, "game", "cast")?;
+        Ok((Self::new(file, width, height)?, format!("{path}.cast")))
+    }
+
+    fn new(file: File, width: u16, height: u16) -> io::Result<Self> {
+        let mut file = BufWriter::new(file);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "env": { "TERM": "xterm-256color" },
+        });
+        writeln!(file, "{header}")?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+            last: Buffer::default(),
+        })
+    }
+
+    /// Adds `frame`, skipped when nothing changed. A frame of another size is sent whole.
+    pub fn record(&mut self, frame: &Buffer) -> io::Result<()> {
+        let mut output = String::new();
+        if frame.area != self.last.area {
+            output.push_str("\x1b[0m\x1b[2J");
+            self.last = Buffer::empty(frame.area);
+        }
+
+        let mut cursor = None;
+        let mut style = None;
+        for (x, y, cell) in self.last.diff(frame) {
+            if cursor != Some((x, y)) {
+                let _ = write!(output, "\x1b[{};{}H", y + 1, x + 1);
+            }
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                output.push_str(&sgr(cell));
+                style = Some(cell_style);
+            }
+            output.push_str(cell.symbol());
+            cursor = Some((x + Span::raw(cell.symbol()).width().max(1) as u16, y));
+        }
+        if output.is_empty() {
+            return Ok(());
+        }
+
+        output.push_str("\x1b[0m");
+        self.last = frame.clone();
+        let time = self.started.elapsed().as_secs_f64();
+        let event = serde_json::json!([time, "o", output]);
+        writeln!(self.file, "{event}")
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writes `frame` to `SNAPSHOT_DIR` as `frame-<seconds>.txt`, plain text, and
+/// `frame-<seconds>.ans`, with the escape codes of its colors for `cat` to show, returning
+/// the path of the latter.
+pub fn save_frame(frame: &Buffer) -> io::Result<String> {
+    let (mut file, path) = create_unique(SNAPSHOT_DIR, "frame", "ans")?;
+    file.write_all(frame_text(frame, true).as_bytes())?;
+    fs::write(format!("{path}.txt"), frame_text(frame, false))?;
+    Ok(format!("{path}.ans"))
+}
+
+/// Creates `<dir>/<stem>-<seconds>.<extension>`, with `-2`, `-3` and so on after the
+/// seconds when one was made in the same second, so none is written over. Returns the file
+/// with its path, short of the extension.
+fn create_unique(dir: &str, stem: &str, extension: &str) -> io::Result<(File, String)> {
+    fs::create_dir_all(dir)?;
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let mut path = format!("{dir}/{stem}-{seconds}");
+    for number in 2.. {
+        match File::create_new(format!("{path}.{extension}")) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                path = format!("{dir}/{stem}-{seconds}-{number}");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("the numbers ran out")
+}
+
+/// The rows of `frame`, with the colors as escape codes when `ansi` is set, otherwise
+/// without the spaces at the end of each row.
+fn frame_text(frame: &Buffer, ansi: bool) -> String {
+    let area = frame.area;
+    let mut output = String::new();
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        let mut style = None;
+        let mut x = area.left();
+        while x < area.right() {
+            let Some(cell) = frame.cell(Position::new(x, y)) else {
+                break;
+            };
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if ansi && style != Some(cell_style) {
+                row.push_str(&sgr(cell));
+                style = Some(cell_style);
+            }
+            row.push_str(cell.symbol());
+            x += Span::raw(cell.symbol()).width().max(1) as u16;
+        }
+        if ansi {
+            row.push_str("\x1b[0m");
+        } else {
+            row.truncate(row.trim_end().len());
+        }
+        output.push_str(&row);
+        output.push('\n');
+    }
+    output
+}
+
+/// The escape code that draws in the cell's colors and modifiers.
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (modifier, code) in modifiers {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(color(cell.fg, false));
+    codes.extend(color(cell.bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let basic = |code: u8| Some((code + offset).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(index) => Some(format!("{};5;{index}", 38 + offset)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", 38 + offset)),
+    }
+}
+
+#[cfg(test)]
+mod test_cast {
+    use std::{env, fs, process};
+
+    use ratatui::buffer::Buffer;
+    use serde_json::Value;
+
+    use super::{create_unique, CastRecorder};
+
+    fn temp_dir(name: &str) -> String {
+        let dir = env::temp_dir().join(format!("snake-cast-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn writes_a_header_then_the_changed_cells() {
+        let dir = temp_dir("events");
+        let (file, path) = create_unique(&dir, "game", "cast").unwrap();
+        let mut cast = CastRecorder::new(file, 4, 2).unwrap();
+        let frame = Buffer::with_lines(["ab  ", "    "]);
+        cast.record(&frame).unwrap();
+        cast.record(&frame).unwrap();
+        let mut next = frame.clone();
+        next[(1, 1)].set_symbol("c");
+        cast.record(&next).unwrap();
+        cast.finish().unwrap();
+
+        let text = fs::read_to_string(format!("{path}.cast")).unwrap();
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3, "an unchanged frame is skipped");
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 4);
+        assert_eq!(lines[0]["height"], 2);
+
+        for event in &lines[1..] {
+            assert!(event[0].as_f64().unwrap() >= 0.0);
+            assert_eq!(event[1], "o");
+        }
+        let first = lines[1][2].as_str().unwrap();
+        assert!(first.starts_with("\x1b[0m\x1b[2J\x1b[1;1H"));
+        assert!(first.contains("ab"));
+        assert_eq!(lines[2][2], "\x1b[2;2H\x1b[0mc\x1b[0m");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn names_never_clash() {
+        let dir = temp_dir("names");
+        let (_, first) = create_unique(&dir, "frame", "ans").unwrap();
+        let (_, second) = create_unique(&dir, "frame", "ans").unwrap();
+        assert_ne!(first, second);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
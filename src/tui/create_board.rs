@@ -1,33 +1,67 @@
-use std::io;
+use std::io::{self, stdout};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
+use crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
-    style::Stylize,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Style, Stylize},
     symbols::border,
-    text::{Line, Text},
-    widgets::{Block, Borders, Paragraph, Widget},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
     DefaultTerminal, Frame,
 };
 
-use crate::core::{Board, Boards, Direction, Wall};
+use super::{
+    events,
+    i18n::{t, tf},
+    text_input::TextInput,
+    theme::{self, BoardFrame},
+    toast::{self, Toasts},
+};
+use snake_game::core::{
+    Board, BoardCheck, Boards, CellKind, Direction, FoodZone, Prefab, Prefabs, Settings,
+    SpawnStatus, Wall, WallColor, WallMaterial,
+};
+
+const PUZZLE_MOVES_STEP: u16 = 5;
+const MIN_BOARD_SIZE: u16 = 5;
+const MAX_BOARD_SIZE: u16 = 200;
+const MAX_NAME_LEN: usize = 30;
+/// Columns of the side panel listing what the checks found.
+const CHECKS_WIDTH: u16 = 34;
+/// Cells between the lines of the grid overlay.
+const GRID_STEP: i16 = 5;
 
 enum State {
     Size,
     Name,
     Wall,
+    PrefabName,
 }
 pub struct CreateBoardTui {
-    name: String,
+    name: TextInput,
     board: Board,
+    size_input: TextInput,
     size: u16,
     exit: bool,
     finish: bool,
     wall: Wall,
     state: State,
     boards: Boards,
+    prefabs: Prefabs,
+    /// Index into the built-in then custom prefabs of the one being stamped.
+    prefab_index: Option<usize>,
+    prefab: Option<Prefab>,
+    selection: Option<Wall>,
+    prefab_name: TextInput,
+    /// The saved name of the board being edited, `None` when creating a new one.
+    editing: Option<String>,
+    /// Draws guide lines every `GRID_STEP` cells and through the middle, with rulers.
+    grid: bool,
+    frame: BoardFrame,
     error: String,
 }
 
@@ -40,84 +74,229 @@ impl Default for CreateBoardTui {
 impl CreateBoardTui {
     pub fn new() -> Self {
         Self {
-            name: "".to_string(),
+            name: TextInput::new(MAX_NAME_LEN).with_validator(not_empty),
             board: Board::new("".to_string(), 0, vec![]),
+            size_input: TextInput::new(3)
+                .with_filter(|c| c.is_ascii_digit())
+                .with_validator(board_size),
             size: 0,
             exit: false,
             finish: false,
             wall: Wall::new(0, 0),
             state: State::Size,
             boards: Boards::new(),
+            prefabs: Prefabs::new(),
+            prefab_index: None,
+            prefab: None,
+            selection: None,
+            prefab_name: TextInput::new(MAX_NAME_LEN).with_validator(not_empty),
+            editing: None,
+            grid: false,
+            frame: BoardFrame::new(&Settings::new()),
             error: "".to_string(),
         }
     }
 
+    /// Opens a saved board in the wall editor; saving it replaces the old version.
+    pub fn edit(board: Board) -> Self {
+        Self {
+            name: TextInput::new(MAX_NAME_LEN)
+                .with_validator(not_empty)
+                .with_value(board.get_name()),
+            size: board.get_size(),
+            state: State::Wall,
+            editing: Some(board.get_name().to_string()),
+            board,
+            ..Self::new()
+        }
+    }
+
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<(String, Board)> {
+        execute!(stdout(), EnableBracketedPaste)?;
+        let result = self.edit_loop(terminal);
+        execute!(stdout(), DisableBracketedPaste)?;
+        result
+    }
+
+    fn edit_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<(String, Board)> {
         while !(self.exit || self.finish) {
             terminal.draw(|frame| self.draw(frame))?;
 
             self.handle_events()?;
         }
 
-        Ok((self.name.clone(), self.board.clone()))
+        Ok((self.name.get_value(), self.board.clone()))
     }
 
     fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
     }
 
     fn key_event_put_wall(&mut self, key_event: KeyEvent) {
+        self.error.clear();
         match key_event.code {
             KeyCode::Char('q') => self.exit = true,
             KeyCode::Char('j') | KeyCode::Down => self.select_down(),
             KeyCode::Char('k') | KeyCode::Up => self.select_up(),
             KeyCode::Char('h') | KeyCode::Left => self.select_left(),
             KeyCode::Char('l') | KeyCode::Right => self.select_right(),
+            KeyCode::Char(' ') if self.prefab.is_some() => self.stamp(),
             KeyCode::Char(' ') => self.toggle_wall(),
-            KeyCode::Enter => self.state = State::Name,
+            KeyCode::Char('p') => self.next_prefab(),
+            KeyCode::Char('r') => self.prefab = self.prefab.as_ref().map(Prefab::rotated),
+            KeyCode::Char('f') => self.prefab = self.prefab.as_ref().map(Prefab::flipped),
+            KeyCode::Char('v') if self.selection.is_none() => {
+                self.selection = Some(self.wall.clone())
+            }
+            KeyCode::Char('y') if self.selection.is_some() => {
+                self.error.clear();
+                self.state = State::PrefabName;
+            }
+            KeyCode::Char('t') => {
+                if let Some(anchor) = &self.selection {
+                    let color = self.next_paint(anchor);
+                    self.board.paint_walls(anchor, &self.wall, color);
+                }
+            }
+            KeyCode::Char('z') => {
+                if let Some(anchor) = self.selection.take() {
+                    // Overlapping zones add up, so marking a spot twice doubles its weight.
+                    self.board
+                        .add_food_zone(FoodZone::new(&anchor, &self.wall, 1));
+                }
+            }
+            KeyCode::Char('x') => self.board.clear_food_zones(),
+            KeyCode::Char('g') => self.grid = !self.grid,
+            KeyCode::Char('o') => self.turn_spawn(),
+            KeyCode::Char('a') => {
+                if let Err(e) = self.board.toggle_puzzle_food(self.wall.clone()) {
+                    self.error = e.to_string();
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Err(e) = self.board.toggle_checkpoint(self.wall.clone()) {
+                    self.error = e.to_string();
+                }
+            }
+            KeyCode::Char('m') => {
+                let style = self.board.get_wall_style();
+                let material = style.get_material().next();
+                self.board.set_wall_style(style.with_material(material));
+            }
+            KeyCode::Char('+') => self.change_puzzle_moves(PUZZLE_MOVES_STEP as i16),
+            KeyCode::Char('-') => self.change_puzzle_moves(-(PUZZLE_MOVES_STEP as i16)),
+            KeyCode::Esc => {
+                self.prefab_index = None;
+                self.prefab = None;
+                self.selection = None;
+            }
+            KeyCode::Enter => self.enter_name(),
             _ => {}
         }
     }
 
-    fn key_event_put_size(&mut self, key_event: KeyEvent) {
+    /// Asks for the name of the board, offering a generated one for a new board.
+    fn enter_name(&mut self) {
+        if self.name.get_value().is_empty() {
+            self.reroll_name();
+        }
+        self.state = State::Name;
+    }
+
+    fn reroll_name(&mut self) {
+        let name = self.boards.generate_name(&mut rand::thread_rng());
+        self.name.set_value(&name);
+        self.error.clear();
+    }
+
+    /// Says right away when the typed name belongs to another board.
+    fn check_name(&mut self) {
+        let name = self.name.get_value();
+        let own = self
+            .editing
+            .as_deref()
+            .is_some_and(|old| old.trim().to_lowercase() == name.trim().to_lowercase());
+        if !own && self.boards.contains(&name) {
+            self.error = tf("create_board.name_exists", &[("name", &name.trim())]);
+        }
+    }
+
+    fn key_event_put_prefab_name(&mut self, key_event: KeyEvent) {
         match key_event.code {
-            KeyCode::Char('q') => self.exit = true,
-            KeyCode::Char(c) if c.is_numeric() => {
-                self.size = self.size * 10 + c.to_digit(10).unwrap() as u16
-            }
-            KeyCode::Backspace => self.size /= 10,
-            KeyCode::Enter => {
-                self.board = Board::new("".to_string(), self.size, vec![]);
-                self.state = State::Wall;
+            KeyCode::Esc => self.state = State::Wall,
+            KeyCode::Enter => self.store_prefab(),
+            _ => {
+                self.error.clear();
+                self.prefab_name.key_event(key_event);
             }
+        }
+    }
 
-            _ => {}
+    fn key_event_put_size(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Enter => match self.size_input.validate() {
+                Ok(size) => {
+                    self.size = size.parse().unwrap();
+                    self.board = Board::new("".to_string(), self.size, vec![]);
+                    self.state = State::Wall;
+                }
+                Err(e) => self.error = e,
+            },
+            _ => {
+                self.error.clear();
+                self.size_input.key_event(key_event);
+            }
         }
     }
 
     fn key_event_put_name(&mut self, key_event: KeyEvent) {
         match key_event.code {
-            KeyCode::Char(c) => self.name.push(c),
-            KeyCode::Backspace => {
-                self.name.pop();
-            }
             KeyCode::Enter => self.store(),
-            _ => {}
+            KeyCode::Tab => self.reroll_name(),
+            _ => {
+                self.error.clear();
+                self.name.key_event(key_event);
+                self.check_name();
+            }
         }
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => match self.state {
-                State::Wall => self.key_event_put_wall(key_event),
-                State::Size => self.key_event_put_size(key_event),
-                State::Name => self.key_event_put_name(key_event),
-            },
+        match events::read()? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                match self.state {
+                    State::Wall => self.key_event_put_wall(key_event),
+                    State::Size => self.key_event_put_size(key_event),
+                    State::Name => self.key_event_put_name(key_event),
+                    State::PrefabName => self.key_event_put_prefab_name(key_event),
+                }
+            }
+            Some(Event::Paste(text)) => self.paste(&text),
             _ => {}
         };
         Ok(())
     }
 
+    /// A grid pasted on the board becomes walls from the cursor on; pasted text goes to the
+    /// field being typed in.
+    fn paste(&mut self, text: &str) {
+        self.error.clear();
+        match self.state {
+            State::Wall => match self.board.paste(text, &self.wall) {
+                0 => self.error = t("create_board.paste_empty"),
+                added => toast::push(tf("create_board.pasted", &[("walls", &added)])),
+            },
+            State::Size => self.size_input.paste(text.trim()),
+            State::Name => {
+                self.name.paste(text.trim());
+                self.check_name();
+            }
+            State::PrefabName => self.prefab_name.paste(text.trim()),
+        }
+    }
+
     fn select_down(&mut self) {
         self.wall = self.wall.get_neighbor(&Direction::Down, self.size);
     }
@@ -143,30 +322,296 @@ impl CreateBoardTui {
         }
     }
 
+    fn change_puzzle_moves(&mut self, by: i16) {
+        if let Some(puzzle) = self.board.get_puzzle() {
+            let max_moves = puzzle.get_max_moves().saturating_add_signed(by);
+            self.board.set_puzzle_moves(max_moves);
+        }
+    }
+
+    /// Puts the spawn under the cursor heading right, or turns an existing one clockwise until
+    /// it has faced every way and is removed.
+    fn turn_spawn(&mut self) {
+        let direction = match self.board.get_spawn() {
+            Some((spawn, Direction::Up)) if *spawn == self.wall => {
+                self.board.clear_spawn();
+                return;
+            }
+            Some((spawn, direction)) if *spawn == self.wall => direction.clockwise(),
+            _ => Direction::Right,
+        };
+
+        if let Err(e) = self.board.set_spawn(self.wall.clone(), direction) {
+            self.error = e.to_string();
+        }
+    }
+
+    fn prefab_choices(&self) -> Vec<Prefab> {
+        let mut choices = Prefab::builtins(self.size);
+        choices.extend(self.prefabs.get_all().iter().cloned());
+        choices
+    }
+
+    /// Cycles through the prefabs and back to plain wall editing.
+    fn next_prefab(&mut self) {
+        let choices = self.prefab_choices();
+        self.prefab_index = match self.prefab_index {
+            None => Some(0),
+            Some(index) if index + 1 < choices.len() => Some(index + 1),
+            Some(_) => None,
+        };
+        self.prefab = self.prefab_index.map(|index| choices[index].clone());
+    }
+
+    /// The color after the one of the selection's first wall, no color coming after the last.
+    fn next_paint(&self, anchor: &Wall) -> Option<WallColor> {
+        let current = self
+            .board
+            .get_walls_between(anchor, &self.wall)
+            .first()
+            .and_then(|wall| self.board.get_wall_color(wall));
+        match current {
+            None => Some(WallColor::ALL[0]),
+            Some(color) => {
+                let index = WallColor::ALL.iter().position(|c| *c == color).unwrap_or(0);
+                WallColor::ALL.get(index + 1).copied()
+            }
+        }
+    }
+
+    fn stamp(&mut self) {
+        if let Some(prefab) = &self.prefab {
+            self.board.stamp(prefab, &self.wall);
+        }
+    }
+
+    fn store_prefab(&mut self) {
+        let Some(anchor) = &self.selection else {
+            return;
+        };
+        let name = match self.prefab_name.validate() {
+            Ok(name) => name,
+            Err(e) => {
+                self.error = e;
+                return;
+            }
+        };
+
+        let walls = self.board.get_walls_between(anchor, &self.wall);
+        if walls.is_empty() {
+            self.error = t("create_board.selection_empty");
+            return;
+        }
+
+        match self.prefabs.add(Prefab::new(name.clone(), walls)) {
+            Ok(_) => {
+                toast::push(tf("create_board.prefab_saved", &[("name", &name)]));
+                self.selection = None;
+                self.prefab_name.clear();
+                self.state = State::Wall;
+            }
+            Err(e) => self.error = e.to_string(),
+        }
+    }
+
     fn store(&mut self) {
-        self.board = self.board.copy_with_new_name(self.name.clone());
-        match self.boards.add(self.name.clone(), self.board.clone()) {
-            Ok(_) => self.finish = true,
-            Err(e) => self.error = e,
+        let name = match self.name.validate() {
+            Ok(name) => name.trim().to_string(),
+            Err(e) => {
+                self.error = e;
+                return;
+            }
+        };
+
+        self.board = self.board.copy_with_new_name(name.clone());
+        let stored = match &self.editing {
+            Some(old_name) => self.boards.replace(old_name, self.board.clone()),
+            None => self.boards.add(name, self.board.clone()),
+        };
+        match stored {
+            Ok(_) => {
+                toast::push(tf(
+                    "create_board.saved",
+                    &[("name", &self.board.get_name())],
+                ));
+                self.finish = true;
+            }
+            Err(e) => self.error = e.to_string(),
         }
     }
 }
 
 impl CreateBoardTui {
     fn render_put_wall(&self, area: Rect, buf: &mut Buffer) {
-        let mut selected_board = self.board.get_table();
-        selected_board[(self.wall.get_x() + 1) as usize][(self.wall.get_y() + 1) as usize] =
-            if selected_board[(self.wall.get_x() + 1) as usize][(self.wall.get_y() + 1) as usize]
-                == " "
+        let [area, checks_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(CHECKS_WIDTH)]).areas(area);
+        let check = BoardCheck::new(&self.board);
+        self.render_checks(&check, checks_area, buf);
+        let mut unreachable = vec![vec![false; self.size as usize]; self.size as usize];
+        for point in check.get_unreachable().iter().flatten() {
+            unreachable[point.get_x() as usize][point.get_y() as usize] = true;
+        }
+
+        let mut selected_board = theme::table(&self.board.get_cells(), &self.frame);
+        let mut mark = |point: &Wall, glyph: &str| {
+            let (x, y) = (point.get_x() + 1, point.get_y() + 1);
+            if let Some(cell) = selected_board
+                .get_mut(x as usize)
+                .and_then(|row| row.get_mut(y as usize))
             {
-                "■".to_string()
-            } else {
-                "▀".to_string()
+                *cell = glyph.to_string();
+            }
+        };
+
+        if !self.board.get_food_zones().is_empty() {
+            for x in 0..self.size as i16 {
+                for y in 0..self.size as i16 {
+                    let point = Wall::new(x, y);
+                    if !self.board.is_wall(&point) && self.board.get_food_weight(&point) > 0 {
+                        mark(&point, "·");
+                    }
+                }
+            }
+        }
+
+        if let Some(anchor) = &self.selection {
+            let rows =
+                anchor.get_x().min(self.wall.get_x())..=anchor.get_x().max(self.wall.get_x());
+            for x in rows {
+                let columns =
+                    anchor.get_y().min(self.wall.get_y())..=anchor.get_y().max(self.wall.get_y());
+                for y in columns {
+                    let point = Wall::new(x, y);
+                    if !self.board.is_wall(&point) {
+                        mark(&point, "░");
+                    }
+                }
+            }
+        }
+
+        if let Some(prefab) = &self.prefab {
+            for cell in prefab.get_cells() {
+                let point = Wall::new(
+                    self.wall.get_x() + cell.get_x(),
+                    self.wall.get_y() + cell.get_y(),
+                );
+                if point.get_x() < self.size as i16 && point.get_y() < self.size as i16 {
+                    mark(&point, "▒");
+                }
+            }
+        }
+
+        if let Some(puzzle) = self.board.get_puzzle() {
+            for food in puzzle.get_food() {
+                mark(food, "●");
+            }
+        }
+
+        if let Some((spawn, direction)) = self.board.get_spawn() {
+            let arrow = match direction {
+                Direction::Up => "↑",
+                Direction::Down => "↓",
+                Direction::Left => "←",
+                _ => "→",
             };
-        let selected_board = selected_board.iter().map(|row| row.join("")).join("\n");
+            mark(spawn, arrow);
+        }
+
+        let cursor = if self.board.is_wall(&self.wall) {
+            "▀"
+        } else {
+            "■"
+        };
+        mark(&self.wall, cursor);
+
+        // Unmarked walls keep the color they were painted, and the cells the snake cannot get
+        // to or else the grid show on empty cells.
+        let cells = self.board.get_cells();
+        let mut selected_board: Text = selected_board
+            .into_iter()
+            .zip(cells)
+            .enumerate()
+            .map(|(x, (row, cells))| {
+                let mut line: Line = row
+                    .into_iter()
+                    .zip(cells)
+                    .enumerate()
+                    .map(|(y, (glyph, cell))| match cell.get_tint() {
+                        Some(tint) if glyph == theme::glyph(&cell) => {
+                            Span::styled(glyph, Style::new().fg(theme::tint_color(tint)))
+                        }
+                        _ if cell.get_kind() == CellKind::Border => {
+                            Span::styled(glyph, self.frame.style())
+                        }
+                        _ if cell.get_kind() != CellKind::Empty || glyph != " " => Span::raw(glyph),
+                        _ if unreachable[x - 1][y - 1] => Span::raw("×").red(),
+                        _ if self.grid => self.grid_span(x as i16 - 1, y as i16 - 1),
+                        _ => Span::raw(glyph),
+                    })
+                    .collect();
+                if self.grid {
+                    line.spans.insert(0, ruler_span(x as i16 - 1, self.size));
+                }
+                line
+            })
+            .collect();
+        if self.grid {
+            let mut ruler = vec![Span::raw("  ")];
+            ruler.extend((0..self.size as i16).map(|y| ruler_span(y, self.size)));
+            ruler.push(Span::raw(" "));
+            selected_board.lines.insert(0, Line::from(ruler));
+        }
+
+        let mut title = tf(
+            "create_board.board_title",
+            &[
+                ("row", &self.wall.get_x()),
+                ("column", &self.wall.get_y()),
+                ("size", &self.size),
+            ],
+        );
+        if let Some(anchor) = &self.selection {
+            title += &tf(
+                "create_board.selection_title",
+                &[
+                    ("rows", &(anchor.get_x().abs_diff(self.wall.get_x()) + 1)),
+                    ("columns", &(anchor.get_y().abs_diff(self.wall.get_y()) + 1)),
+                ],
+            );
+        }
+        if let Some(puzzle) = self.board.get_puzzle() {
+            title += &tf(
+                "create_board.puzzle_title",
+                &[
+                    ("apples", &puzzle.get_food().len()),
+                    ("moves", &puzzle.get_max_moves()),
+                ],
+            );
+        }
+        let material = self.board.get_wall_style().get_material();
+        if material != WallMaterial::default() {
+            title += &tf(
+                "create_board.material_title",
+                &[("material", &material.get_name())],
+            );
+        }
+        if let Some(prefab) = &self.prefab {
+            title += &tf(
+                "create_board.prefab_title",
+                &[("prefab", &prefab.get_name())],
+            );
+        }
+        let error = format!(" {} ", self.error);
+        let instructions = match (&self.prefab, &self.selection) {
+            _ if !self.error.is_empty() => error,
+            (Some(_), _) => t("create_board.prefab_footer"),
+            (None, Some(_)) => t("create_board.selection_footer"),
+            (None, None) => t("create_board.wall_footer"),
+        };
 
         let block = Block::new()
-            .title(Line::raw(" Selected Board ").centered())
+            .title(Line::raw(title).centered())
+            .title_bottom(Line::raw(instructions).centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
@@ -176,11 +621,88 @@ impl CreateBoardTui {
             .render(area, buf);
     }
 
-    fn render_put_size(&self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from("Please enter size of board");
-        let instructions = Line::from("Use Enter to save.");
+    /// Lists the walls and open cells, and warns about closed off areas and a bad spawn.
+    fn render_checks(&self, check: &BoardCheck, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![
+            Line::from(tf("create_board.walls", &[("walls", &check.get_walls())])),
+            Line::from(tf(
+                "create_board.open_cells",
+                &[("cells", &check.get_open_cells())],
+            )),
+            Line::default(),
+        ];
+        lines.push(match check.get_unreachable() {
+            [] => Line::from(t("create_board.all_reachable")).green(),
+            areas => Line::from(tf(
+                "create_board.closed_off",
+                &[
+                    ("areas", &areas.len()),
+                    ("cells", &check.get_unreachable_cells()),
+                ],
+            ))
+            .yellow(),
+        });
+
+        let start = match self.board.get_spawn() {
+            Some(_) => t("create_board.spawn"),
+            None => t("create_board.middle_start"),
+        };
+        lines.push(match check.get_spawn() {
+            SpawnStatus::Clear => {
+                Line::from(tf("create_board.spawn_clear", &[("start", &start)])).green()
+            }
+            SpawnStatus::Blocked => {
+                Line::from(tf("create_board.spawn_blocked", &[("start", &start)])).red()
+            }
+            SpawnStatus::Cramped(free) => Line::from(tf(
+                "create_board.spawn_cramped",
+                &[("start", &start), ("free", &free)],
+            ))
+            .yellow(),
+        });
 
-        Paragraph::new(self.size.to_string())
+        let block = Block::new()
+            .title(Line::raw(t("create_board.checks")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, buf);
+    }
+
+    /// The grid line through the empty cell at row `x` and column `y`, if any: every
+    /// `GRID_STEP` cells in gray, and the middle of the board in blue.
+    fn grid_span(&self, x: i16, y: i16) -> Span<'static> {
+        let size = self.size as i16;
+        let middle = |at: i16| at == (size - 1) / 2 || at == size / 2;
+        let (row_middle, column_middle) = (middle(x), middle(y));
+        let glyph = match (
+            row_middle || x % GRID_STEP == 0,
+            column_middle || y % GRID_STEP == 0,
+        ) {
+            (true, true) => "┼",
+            (true, false) => "─",
+            (false, true) => "│",
+            (false, false) => return Span::raw(" "),
+        };
+        if row_middle || column_middle {
+            Span::raw(glyph).blue()
+        } else {
+            Span::raw(glyph).dark_gray()
+        }
+    }
+
+    fn render_put_prefab_name(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(t("create_board.prefab_name_prompt"));
+        let instructions = Line::from(t("common.enter_to_save_or_back"));
+
+        let text = Text::from(vec![
+            self.prefab_name.line(),
+            Line::from(self.error.clone()).red(),
+        ]);
+
+        Paragraph::new(text)
             .block(
                 Block::new()
                     .title(title.centered())
@@ -190,12 +712,12 @@ impl CreateBoardTui {
             .render(area, buf);
     }
 
-    fn render_put_name(&self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from("Please enter name of board");
-        let instructions = Line::from("Use Enter to save.");
+    fn render_put_size(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(t("create_board.size_prompt"));
+        let instructions = Line::from(t("common.enter_to_save"));
 
         let text = Text::from(vec![
-            Line::from(self.name.clone()),
+            self.size_input.line(),
             Line::from(self.error.clone()).red(),
         ]);
 
@@ -208,6 +730,22 @@ impl CreateBoardTui {
             .centered()
             .render(area, buf);
     }
+
+    fn render_put_name(&self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(t("create_board.name_prompt"));
+        let instructions = Line::from(t("create_board.name_footer"));
+
+        let text = Text::from(vec![self.name.line(), Line::from(self.error.clone()).red()]);
+
+        Paragraph::new(text)
+            .block(
+                Block::new()
+                    .title(title.centered())
+                    .title_bottom(instructions.centered()),
+            )
+            .centered()
+            .render(area, buf);
+    }
 }
 
 impl Widget for &mut CreateBoardTui {
@@ -216,6 +754,38 @@ impl Widget for &mut CreateBoardTui {
             State::Wall => self.render_put_wall(area, buf),
             State::Size => self.render_put_size(area, buf),
             State::Name => self.render_put_name(area, buf),
+            State::PrefabName => self.render_put_prefab_name(area, buf),
         }
     }
 }
+
+/// The last digit of row or column `at`, brighter every `GRID_STEP` cells; blank outside
+/// the board.
+fn ruler_span(at: i16, size: u16) -> Span<'static> {
+    if at < 0 || at >= size as i16 {
+        return Span::raw(" ");
+    }
+    let digit = (at % 10).to_string();
+    if at % GRID_STEP == 0 {
+        Span::raw(digit).yellow().bold()
+    } else {
+        Span::raw(digit).dark_gray()
+    }
+}
+
+fn not_empty(name: &str) -> Result<(), String> {
+    match name.trim().is_empty() {
+        true => Err(t("create_board.name_empty")),
+        false => Ok(()),
+    }
+}
+
+fn board_size(size: &str) -> Result<(), String> {
+    match size.parse::<u16>() {
+        Ok(size) if (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size) => Ok(()),
+        _ => Err(tf(
+            "create_board.size_range",
+            &[("min", &MIN_BOARD_SIZE), ("max", &MAX_BOARD_SIZE)],
+        )),
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::VecDeque, time::Duration};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use snake_game::core::{Cell, CellKind, Point};
+
+use super::i18n::{t, tf};
+
+const SAMPLES: usize = 30;
+const OVERLAY_WIDTH: u16 = 26;
+const OVERLAY_HEIGHT: u16 = 9;
+const LEGEND: [(&str, Color); 5] = [
+    ("debug.legend_wall", Color::Red),
+    ("debug.legend_snake", Color::Blue),
+    ("debug.legend_food", Color::Yellow),
+    ("debug.legend_food_zone", Color::Green),
+    ("debug.legend_spawn", Color::Magenta),
+];
+
+/// Timing statistics shown by the F3 overlay, averaged over the last few ticks.
+pub struct DebugStats {
+    visible: bool,
+    spawn_view: bool,
+    ticks: VecDeque<Duration>,
+    renders: VecDeque<Duration>,
+    dropped: u32,
+}
+
+impl DebugStats {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            spawn_view: false,
+            ticks: VecDeque::with_capacity(SAMPLES),
+            renders: VecDeque::with_capacity(SAMPLES),
+            dropped: 0,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn toggle_spawn_view(&mut self) {
+        self.spawn_view = !self.spawn_view;
+    }
+
+    pub fn is_spawn_view(&self) -> bool {
+        self.spawn_view
+    }
+
+    /// Color-codes what occupies each cell and where food or the snake can spawn,
+    /// marking a snake spawn inside a wall with `!`.
+    pub fn spawn_text(
+        cells: &[Vec<Cell>],
+        food_spawn: &[Point],
+        snake_spawn: &[Point],
+    ) -> Text<'static> {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                line.iter()
+                    .enumerate()
+                    .map(|(column, cell)| {
+                        let point = Point::new(row as i16 - 1, column as i16 - 1);
+                        let kind = cell.get_kind();
+                        let background = match kind {
+                            _ if snake_spawn.contains(&point) => Some(Color::Magenta),
+                            CellKind::Wall => Some(Color::Red),
+                            CellKind::Snake | CellKind::SnakeHead => Some(Color::Blue),
+                            CellKind::Food => Some(Color::Yellow),
+                            _ if food_spawn.contains(&point) => Some(Color::Green),
+                            _ => None,
+                        };
+                        let glyph = match kind {
+                            CellKind::Wall if snake_spawn.contains(&point) => "!".to_string(),
+                            _ => cell.get_glyph().to_string(),
+                        };
+                        match background {
+                            Some(color) => {
+                                Span::styled(glyph, Style::new().fg(Color::Black).bg(color))
+                            }
+                            None => Span::raw(glyph),
+                        }
+                    })
+                    .collect::<Line>()
+            })
+            .collect()
+    }
+
+    pub fn render_legend(&self, area: Rect, buf: &mut Buffer) {
+        if !self.spawn_view {
+            return;
+        }
+
+        let names = LEGEND.map(|(key, _)| t(key));
+        let width = names
+            .iter()
+            .map(|name| Span::raw(name).width() as u16)
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let height = LEGEND.len() as u16 + 2;
+        let area = Rect::new(
+            area.right().saturating_sub(width),
+            area.y,
+            width.min(area.width),
+            height.min(area.height),
+        );
+        let lines: Vec<Line> = LEGEND
+            .iter()
+            .zip(&names)
+            .map(|((_, color), name)| {
+                Line::from(vec![
+                    Span::styled(" ", Style::new().bg(*color)),
+                    Span::raw(format!(" {name}")),
+                ])
+            })
+            .collect();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(Block::new().borders(Borders::ALL))
+            .render(area, buf);
+    }
+
+    /// A tick counts as dropped when it took more than half a tick longer than planned.
+    pub fn record_tick(&mut self, took: Duration, planned: Duration) {
+        if took > planned + planned / 2 {
+            self.dropped += 1;
+        }
+        Self::push(&mut self.ticks, took);
+    }
+
+    pub fn record_render(&mut self, took: Duration) {
+        Self::push(&mut self.renders, took);
+    }
+
+    pub fn render(&self, cells: &[Vec<Cell>], area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+
+        let tick = Self::average(&self.ticks);
+        let fps = if tick.is_zero() {
+            0.0
+        } else {
+            1.0 / tick.as_secs_f64()
+        };
+        let count = |kinds: &[CellKind]| {
+            cells
+                .iter()
+                .flatten()
+                .filter(|cell| kinds.contains(&cell.get_kind()))
+                .count()
+        };
+
+        let line = |key: &str, value: String| Line::from(tf(key, &[("value", &value)]));
+        let lines = vec![
+            line(
+                "debug.tick",
+                format!("{:>6.1}", tick.as_secs_f64() * 1000.0),
+            ),
+            line("debug.fps", format!("{fps:>6.1}")),
+            line(
+                "debug.render",
+                format!(
+                    "{:>6.2}",
+                    Self::average(&self.renders).as_secs_f64() * 1000.0
+                ),
+            ),
+            line("debug.dropped", format!("{:>6}", self.dropped)),
+            line(
+                "debug.snake",
+                format!("{:>6}", count(&[CellKind::Snake, CellKind::SnakeHead])),
+            ),
+            line("debug.food", format!("{:>6}", count(&[CellKind::Food]))),
+            line("debug.walls", format!("{:>6}", count(&[CellKind::Wall]))),
+        ];
+
+        let area = Rect::new(
+            area.x,
+            area.y,
+            OVERLAY_WIDTH.min(area.width),
+            OVERLAY_HEIGHT.min(area.height),
+        );
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .style(Style::new().fg(Color::Yellow))
+            .block(Block::new().borders(Borders::ALL).title(t("debug.title")))
+            .render(area, buf);
+    }
+
+    fn push(samples: &mut VecDeque<Duration>, sample: Duration) {
+        if samples.len() == SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    fn average(samples: &VecDeque<Duration>) -> Duration {
+        if samples.is_empty() {
+            return Duration::ZERO;
+        }
+        samples.iter().sum::<Duration>() / samples.len() as u32
+    }
+}
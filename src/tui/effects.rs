@@ -0,0 +1,234 @@
+use rand::Rng;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+};
+
+use snake_game::core::{Cell, CellKind, GameEvent, Point};
+
+const FLASH_TICKS: u16 = 3;
+const POPUP_TICKS: u16 = 8;
+const POPUP_TICKS_PER_ROW: u16 = 2;
+/// Ticks a cell the tail left keeps fading, one glyph each.
+const TRAIL_GLYPHS: [&str; 3] = ["▓", "▒", "░"];
+/// Ticks an opening gate takes to crumble away, one glyph each.
+const GATE_GLYPHS: [&str; 4] = ["▦", "▤", "░", "·"];
+const SHAKE_OFFSETS: [(i16, i16); 6] = [(0, 0), (1, 0), (-1, 1), (1, 0), (-1, 1), (1, 0)];
+
+enum Kind {
+    Flash,
+    Popup(String),
+    Trail,
+    Gate,
+}
+
+struct Effect {
+    kind: Kind,
+    point: Point,
+    age: u16,
+    ttl: u16,
+}
+
+/// Short-lived visuals drawn on top of the board, advanced once per game tick.
+pub struct Effects {
+    effects: Vec<Effect>,
+    screen_shake: bool,
+    /// Cells the tail leaves fade out behind the snake.
+    trail: bool,
+    shake: usize,
+}
+
+impl Effects {
+    pub fn new(screen_shake: bool, trail: bool) -> Self {
+        Self {
+            effects: Vec::new(),
+            screen_shake,
+            trail,
+            shake: 0,
+        }
+    }
+
+    pub fn push(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::Ate { at, points } => {
+                self.add(Kind::Flash, at, FLASH_TICKS);
+                self.add(Kind::Popup(format!("+{points}")), at, POPUP_TICKS);
+            }
+            GameEvent::Checkpoint { at } => self.add(Kind::Flash, at, FLASH_TICKS),
+            GameEvent::Respawned => self.shake(),
+            GameEvent::GateOpened { cells } => {
+                for cell in cells {
+                    self.add(Kind::Gate, cell, GATE_GLYPHS.len() as u16);
+                }
+            }
+            GameEvent::Bumped => {}
+        }
+    }
+
+    /// Fades out the cell at `left` that the tail just moved off.
+    pub fn push_trail(&mut self, left: &Point) {
+        if self.trail {
+            self.add(Kind::Trail, left, TRAIL_GLYPHS.len() as u16);
+        }
+    }
+
+    pub fn shake(&mut self) {
+        if self.screen_shake {
+            self.shake = SHAKE_OFFSETS.len() - 1;
+        }
+    }
+
+    pub fn is_shaking(&self) -> bool {
+        self.shake > 0
+    }
+
+    /// Column and row offset the board should be drawn at this frame.
+    pub fn offset(&self) -> (i16, i16) {
+        SHAKE_OFFSETS[self.shake]
+    }
+
+    pub fn tick(&mut self) {
+        self.shake = self.shake.saturating_sub(1);
+        self.effects.iter_mut().for_each(|effect| effect.age += 1);
+        self.effects.retain(|effect| effect.age < effect.ttl);
+    }
+
+    /// Draws the effects over a board rendered at `board_area`, border included; the trail
+    /// and opening gates only show on the `cells` still empty.
+    pub fn render(&self, board_area: Rect, cells: &[Vec<Cell>], buf: &mut Buffer) {
+        for effect in &self.effects {
+            match &effect.kind {
+                Kind::Trail | Kind::Gate => {
+                    let (x, y) = (effect.point.get_x() + 1, effect.point.get_y() + 1);
+                    let empty = cells
+                        .get(x as usize)
+                        .and_then(|row| row.get(y as usize))
+                        .is_some_and(|cell| cell.get_kind() == CellKind::Empty);
+                    let (glyph, color) = match effect.kind {
+                        Kind::Gate => (GATE_GLYPHS[effect.age as usize], Color::Magenta),
+                        _ => (TRAIL_GLYPHS[effect.age as usize], Color::DarkGray),
+                    };
+                    match Self::cell_position(board_area, &effect.point, 0) {
+                        Some(position) if empty => {
+                            buf[position]
+                                .set_symbol(glyph)
+                                .set_style(Style::new().fg(color));
+                        }
+                        _ => {}
+                    }
+                }
+                Kind::Flash => {
+                    let style = Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                    if let Some(position) = Self::cell_position(board_area, &effect.point, 0) {
+                        buf[position].set_style(style);
+                    }
+                }
+                Kind::Popup(text) => {
+                    let rise = effect.age / POPUP_TICKS_PER_ROW + 1;
+                    let style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+                    if let Some(position) = Self::cell_position(board_area, &effect.point, rise) {
+                        let width = board_area.right().saturating_sub(position.x);
+                        buf.set_stringn(position.x, position.y, text, width as usize, style);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, kind: Kind, point: &Point, ttl: u16) {
+        self.effects.push(Effect {
+            kind,
+            point: point.clone(),
+            age: 0,
+            ttl,
+        });
+    }
+
+    fn cell_position(board_area: Rect, point: &Point, rise: u16) -> Option<Position> {
+        let x = board_area.x + 1 + point.get_y() as u16;
+        let y = (board_area.y + 1 + point.get_x() as u16).checked_sub(rise)?;
+        let position = Position::new(x, y);
+
+        board_area.contains(position).then_some(position)
+    }
+}
+
+const CONFETTI_GLYPHS: [char; 6] = ['*', '+', '•', '○', '✦', '·'];
+const CONFETTI_COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Yellow,
+    Color::Green,
+    Color::Cyan,
+    Color::Magenta,
+    Color::LightBlue,
+];
+/// One piece for every this many cells of the screen.
+const CONFETTI_SPREAD: u16 = 40;
+
+struct Piece {
+    x: u16,
+    /// Rows fallen so far, in quarters of a row.
+    fall: u16,
+    speed: u16,
+    glyph: char,
+    color: Color,
+}
+
+/// Pieces of confetti falling down the screen, started again at the top once they reach the
+/// bottom. Advanced once per frame and drawn only on blank cells, so text stays readable.
+pub struct Confetti {
+    pieces: Vec<Piece>,
+    height: u16,
+}
+
+impl Confetti {
+    pub fn new() -> Self {
+        Self {
+            pieces: Vec::new(),
+            height: 0,
+        }
+    }
+
+    pub fn tick(&mut self, area: Rect) {
+        let mut rng = rand::thread_rng();
+        if self.height != area.height || self.pieces.is_empty() {
+            let count = (area.area() / CONFETTI_SPREAD as u32).max(1);
+            self.height = area.height;
+            self.pieces = (0..count)
+                .map(|_| {
+                    let fall = rng.gen_range(0..area.height.max(1) * 4);
+                    Self::piece(&mut rng, area, fall)
+                })
+                .collect();
+        }
+        for piece in &mut self.pieces {
+            piece.fall += piece.speed;
+            if piece.fall / 4 >= area.height {
+                *piece = Self::piece(&mut rng, area, 0);
+            }
+        }
+    }
+
+    fn piece(rng: &mut impl Rng, area: Rect, fall: u16) -> Piece {
+        Piece {
+            x: rng.gen_range(0..area.width.max(1)),
+            fall,
+            speed: rng.gen_range(1..=3),
+            glyph: CONFETTI_GLYPHS[rng.gen_range(0..CONFETTI_GLYPHS.len())],
+            color: CONFETTI_COLORS[rng.gen_range(0..CONFETTI_COLORS.len())],
+        }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        for piece in &self.pieces {
+            let position = Position::new(area.x + piece.x, area.y + piece.fall / 4);
+            if !area.contains(position) || buf[position].symbol() != " " {
+                continue;
+            }
+            buf[position]
+                .set_char(piece.glyph)
+                .set_style(Style::new().fg(piece.color));
+        }
+    }
+}
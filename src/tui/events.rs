@@ -0,0 +1,149 @@
+//! Single source of input events for every screen, so keyboard and gamepad behave alike.
+//! Events are read on a thread of their own and queued, so a slow frame or tick never
+//! keeps a key from being taken in.
+
+use std::{
+    io,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{self, Event};
+
+#[cfg(feature = "gamepad")]
+use super::gamepad;
+use super::{script::Script, toast};
+
+#[cfg(feature = "gamepad")]
+const GAMEPAD_POLL: Duration = Duration::from_millis(10);
+
+/// How long a menu waits for input before drawing its next frame.
+pub const MENU_FRAME: Duration = Duration::from_millis(50);
+
+/// The events read so far, with the one `poll` has already taken off the queue.
+struct Input {
+    source: Source,
+    peeked: Option<Event>,
+}
+
+/// Where the events come from.
+enum Source {
+    /// The keyboard and gamepad, read on a thread of their own.
+    Devices(Receiver<io::Result<Event>>),
+    /// A script, whose keys come on the ticks of the screen waiting for them.
+    Script(Script),
+}
+
+static INPUT: OnceLock<Mutex<Input>> = OnceLock::new();
+
+/// Starts reading events. Terminal queries that read stdin themselves must be done by then.
+pub fn start() {
+    input();
+}
+
+/// Takes the events from `script` instead of the keyboard from now on.
+pub fn start_script(script: Script) {
+    INPUT.get_or_init(|| {
+        script.start();
+        Mutex::new(Input {
+            source: Source::Script(script),
+            peeked: None,
+        })
+    });
+}
+
+fn input() -> &'static Mutex<Input> {
+    INPUT.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let event = read_input();
+            let failed = event.is_err();
+            if sender.send(event).is_err() || failed {
+                break;
+            }
+        });
+        Mutex::new(Input {
+            source: Source::Devices(receiver),
+            peeked: None,
+        })
+    })
+}
+
+#[cfg(not(feature = "gamepad"))]
+fn read_input() -> io::Result<Event> {
+    event::read()
+}
+
+#[cfg(feature = "gamepad")]
+fn read_input() -> io::Result<Event> {
+    loop {
+        if let Some(event) = gamepad::next_event() {
+            return Ok(event);
+        }
+        if event::poll(GAMEPAD_POLL)? {
+            return event::read();
+        }
+    }
+}
+
+/// Whether an event comes within `timeout`; the next `read` returns it at once.
+pub fn poll(timeout: Duration) -> io::Result<bool> {
+    let mut input = input().lock().unwrap();
+    if input.peeked.is_some() {
+        return Ok(true);
+    }
+    let event = match &mut input.source {
+        Source::Devices(receiver) => match receiver.recv_timeout(timeout) {
+            Ok(event) => Some(event?),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => return Err(stopped()),
+        },
+        Source::Script(script) => script.next_key(!timeout.is_zero())?.map(Event::Key),
+    };
+    input.peeked = event;
+    Ok(input.peeked.is_some())
+}
+
+fn take() -> io::Result<Event> {
+    let mut input = input().lock().unwrap();
+    if let Some(event) = input.peeked.take() {
+        return Ok(event);
+    }
+    match &mut input.source {
+        Source::Devices(receiver) => receiver.recv().map_err(|_| stopped())?,
+        Source::Script(script) => loop {
+            if let Some(key) = script.next_key(true)? {
+                return Ok(Event::Key(key));
+            }
+        },
+    }
+}
+
+fn stopped() -> io::Error {
+    io::Error::other("input is no longer read")
+}
+
+/// The next event within `budget`, `None` when there was none, so a screen can draw again
+/// while it is idle.
+pub fn next(budget: Duration) -> io::Result<Option<Event>> {
+    if poll(budget)? {
+        take().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Waits for the next event; while a toast is up it also wakes with `None` when the toast
+/// expires, so the screen draws again and clears it away.
+pub fn read() -> io::Result<Option<Event>> {
+    if let Some(expiry) = toast::next_expiry() {
+        if !poll(expiry.saturating_duration_since(Instant::now()))? {
+            return Ok(None);
+        }
+    }
+    take().map(Some)
+}
@@ -1,113 +1,956 @@
-use crate::core::{Direction, Game};
+#[cfg(feature = "presence")]
+use super::presence;
+use super::{
+    cast::{self, CastRecorder},
+    debug::DebugStats,
+    effects::Effects,
+    events,
+    i18n::{t, tf},
+    kitty, script,
+    size_guard::SizeGuardTui,
+    sound::{Sound, SoundPlayer},
+    spotlight,
+    theme::Palette,
+    toast::{self, Toasts},
+};
+use snake_game::core::{
+    steer, AdaptiveSpeed, Difficulty, Direction, Game, GameEvent, GameRecord, Mode, Point, Replay,
+    SavedGame, Settings, Tutorial, FOOD_SCORE, NORMAL_SPEED,
+};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
+use crossterm::{
+    event::{
+        DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+        KeyCode, KeyEvent, KeyEventKind, KeyboardEnhancementFlags, MouseButton, MouseEvent,
+        MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+    terminal::supports_keyboard_enhancement,
+};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    text::Line,
-    widgets::{Block, Paragraph, Widget},
+    style::{Color, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
     DefaultTerminal, Frame,
 };
-use std::{io, time};
+use std::{
+    cell::Cell,
+    io::{self, stdout},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::time::{sleep, Duration};
 
+const TICK: Duration = Duration::from_millis(80);
+const SHAKE_FRAME: Duration = Duration::from_millis(40);
+const BOOST_GAUGE_WIDTH: usize = 10;
+/// Without key-release events a held key is only visible through its auto-repeat presses.
+const BOOST_HOLD_TIMEOUT: Duration = Duration::from_millis(300);
+/// How long a finished tutorial or a filled board stays on screen.
+const WIN_PAUSE: Duration = Duration::from_millis(2000);
+/// The countdown before a game paused by a focus loss carries on.
+const RESUME_COUNTDOWN: Duration = Duration::from_secs(3);
+/// The score line above the board and the instructions below it.
+const HUD_ROWS: u16 = 2;
+/// An objective's countdown turns red with this little time left.
+const COUNTDOWN_WARNING: Duration = Duration::from_secs(10);
+
 pub struct GameTui<'a> {
     game: Game<'a>,
+    effects: Effects,
+    sound: SoundPlayer,
+    palette: Palette,
+    /// Lights only the cells around the head.
+    spotlight: bool,
+    /// Draws the board with the Kitty graphics protocol when there is room.
+    pixels: bool,
+    /// Writes the frames to an asciinema cast, set from the settings when the game starts.
+    record: bool,
+    cast: Option<CastRecorder>,
+    /// Set by `F2` to save the next frame drawn to a file.
+    save_frame: bool,
+    /// The time between two steps, which the board may change; boosting halves it.
+    tick: Duration,
+    /// The speed set with `+` and `-`, in percent of the board's.
+    speed: u16,
+    /// Bounds how far the speed may be changed.
+    difficulty: Difficulty,
+    /// Eases the speed up and down with how the game goes, when the setting is on.
+    adaptive: Option<AdaptiveSpeed>,
+    debug: DebugStats,
+    cheats: bool,
+    /// The name of the player's snake from the settings, shown in the HUD.
+    snake_name: String,
+    tutorial: Option<Tutorial>,
+    /// Set while playing a replay back, whose inputs drive the snake instead of the keys.
+    replay: Option<Replay>,
+    /// A demo game steered by the autopilot until any key is pressed.
+    demo: bool,
+    /// The mode the game is saved under when it is paused, `None` for games that are not saved.
+    saving: Option<Mode>,
+    /// Set when the player saved the game to carry it on later and left.
+    suspended: bool,
+    /// Set when the player left the paused game for a new one on the same board.
+    restart: bool,
+    /// What is shown on the player's Discord profile while the game goes on, with the score
+    /// last shown.
+    #[cfg(feature = "presence")]
+    presence: Option<(String, u64, Option<u16>)>,
+    /// Where the board was last drawn, to map mouse clicks to cells.
+    drawn_board: Cell<Rect>,
+    /// Whether the last frame drew the board as an image, two columns per cell.
+    drawn_pixels: Cell<bool>,
+    release_events: bool,
+    boost_held: bool,
+    boost_until: Instant,
+    /// Game time played so far, the sum of the ticks without pauses.
+    elapsed: Duration,
     stop: bool,
+    /// Set when the game was paused because the terminal lost focus.
+    focus_paused: bool,
+    /// When a game paused by a focus loss carries on, once the focus is back.
+    resume_at: Option<Instant>,
+    /// Holds the game while the terminal is too small for the board, until it is resized.
+    too_small: bool,
     exit: bool,
 }
 
 impl<'a> GameTui<'a> {
-    pub fn new(game: Game<'a>) -> Self {
+    /// With `cheats` the snake cannot die, a left click teleports it and a right click places food.
+    pub fn new(mut game: Game<'a>, cheats: bool) -> Self {
+        let settings = Settings::new();
+        game.set_no_death(cheats);
+        let rules = *game.get_board_rules();
+        let difficulty = Difficulty::estimate(game.get_board());
+
         Self {
             game,
+            effects: Effects::new(
+                settings.is_screen_shake_enabled(),
+                settings.is_trail_enabled(),
+            ),
+            sound: SoundPlayer::new(&settings),
+            palette: Palette::new(&settings),
+            spotlight: settings.is_spotlight_enabled() || rules.is_fog(),
+            pixels: settings.is_pixel_board_enabled() && kitty::is_supported(),
+            record: settings.is_recording_casts(),
+            cast: None,
+            save_frame: false,
+            tick: rules.get_tick_millis().map_or(TICK, Duration::from_millis),
+            speed: NORMAL_SPEED,
+            difficulty,
+            adaptive: None,
+            debug: DebugStats::new(),
+            cheats,
+            snake_name: settings.get_snake_name().to_string(),
+            tutorial: None,
+            replay: None,
+            demo: false,
+            saving: None,
+            suspended: false,
+            restart: false,
+            #[cfg(feature = "presence")]
+            presence: None,
+            drawn_board: Cell::new(Rect::default()),
+            drawn_pixels: Cell::new(false),
+            release_events: false,
+            boost_held: false,
+            boost_until: Instant::now(),
+            elapsed: Duration::ZERO,
             stop: false,
+            focus_paused: false,
+            resume_at: None,
+            too_small: false,
             exit: false,
         }
     }
 
+    /// Guides the player through the tutorial steps; the snake cannot die meanwhile.
+    pub fn with_tutorial(mut self) -> Self {
+        self.game.set_no_death(true);
+        self.tutorial = Some(Tutorial::new());
+        self
+    }
+
+    /// Plays `replay` back on a game started from it.
+    pub fn with_replay(mut self, replay: Replay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    /// Zen mode: no death, no score and soft colors.
+    pub fn with_zen(mut self) -> Self {
+        self.game.set_zen(true);
+        self.palette = self.palette.soft();
+        self
+    }
+
+    /// Lets the autopilot play silently behind a "press any key" banner.
+    pub fn with_demo(mut self) -> Self {
+        self.sound = SoundPlayer::silent();
+        self.demo = true;
+        self
+    }
+
+    /// Saves the game under `mode` whenever it is paused, so it can be carried on from the main
+    /// menu. Cheated and hardcore games are never saved.
+    pub fn with_saving(mut self, mode: Mode) -> Self {
+        if !self.cheats && !self.game.is_hardcore() {
+            self.saving = Some(mode);
+        }
+        self
+    }
+
+    /// Shows the game on the player's Discord profile: the mode, the board and the score.
+    #[cfg(feature = "presence")]
+    pub fn with_presence(mut self, board: &str, mode: Mode) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+            .saturating_sub(self.elapsed.as_secs());
+        let details = tf(
+            "game.presence_details",
+            &[("mode", &mode.get_name()), ("board", &board)],
+        );
+        self.presence = Some((details, started, None));
+        self
+    }
+
+    #[cfg(feature = "presence")]
+    fn update_presence(&mut self) {
+        let score = self.game.get_score();
+        if let Some((details, started, shown)) = &mut self.presence {
+            if *shown != Some(score) {
+                *shown = Some(score);
+                presence::set(
+                    details.clone(),
+                    tf("game.presence_score", &[("score", &score)]),
+                    *started,
+                );
+            }
+        }
+    }
+
+    /// Lets the speed follow how the game goes; hardcore games keep their top speed. The
+    /// game is scored apart once the speed moves.
+    pub fn with_adaptive_speed(mut self) -> Self {
+        if !self.game.is_hardcore() {
+            self.adaptive = Some(AdaptiveSpeed::new());
+        }
+        self
+    }
+
+    /// Carries on the clock of a saved game.
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Whether the player saved the game and left it for later.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Whether the player left the game from the pause menu to start it over.
+    pub fn is_restarting(&self) -> bool {
+        self.restart
+    }
+
+    /// Only games the player plays, not replays, demos or the tutorial, can be started over.
+    fn can_restart(&self) -> bool {
+        self.replay.is_none() && self.tutorial.is_none() && !self.demo
+    }
+
+    pub fn get_replay(&self) -> Replay {
+        self.game.get_replay()
+    }
+
     pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<u16> {
+        self.release_events = supports_keyboard_enhancement().unwrap_or(false);
+        if self.release_events {
+            execute!(
+                stdout(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+        }
+
+        if self.cheats {
+            execute!(stdout(), EnableMouseCapture)?;
+        }
+        execute!(stdout(), EnableFocusChange)?;
+        if self.record && !self.demo {
+            self.start_cast(terminal)?;
+        }
+
+        let result = self.game_loop(terminal).await;
+        #[cfg(feature = "presence")]
+        if self.presence.is_some() {
+            presence::clear();
+        }
+
+        // The terminal is restored even when the recording could not be finished.
+        let cast_result = self.cast.take().map_or(Ok(()), |cast| cast.finish());
+
+        if self.release_events {
+            execute!(stdout(), PopKeyboardEnhancementFlags)?;
+        }
+        execute!(stdout(), DisableFocusChange)?;
+        if self.cheats {
+            execute!(stdout(), DisableMouseCapture)?;
+        }
+        if self.pixels {
+            kitty::clear()?;
+        }
+
+        cast_result?;
+        result
+    }
+
+    async fn game_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<u16> {
+        let mut planned = self.tick;
+        let mut last_tick = Instant::now();
+        let size = terminal.size()?;
+        self.too_small = !self
+            .size_guard()
+            .fits(Rect::new(0, 0, size.width, size.height));
+
         while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.exit = !self.game.walk();
+            let now = Instant::now();
+            self.debug.record_tick(now - last_tick, planned);
+            last_tick = now;
+
+            self.frame(terminal)?;
+            self.debug.record_render(now.elapsed());
+            match &self.replay {
+                Some(replay) if self.game.get_tick() >= replay.get_ticks() => break,
+                Some(replay) => replay.apply(&mut self.game),
+                None if self.demo => {
+                    if let Some(direction) = steer(&self.game) {
+                        self.game.rotation(direction);
+                    }
+                }
+                None => self
+                    .game
+                    .set_boost(self.boost_held || Instant::now() < self.boost_until),
+            }
+            let tail = self.game.get_tail().cloned();
+            let mut alive = self.game.walk();
+            self.elapsed += planned;
+            if alive && self.get_time_left() == Some(Duration::ZERO) {
+                self.game.run_out_of_time();
+                alive = false;
+            }
+            script::record(&self.game);
+            #[cfg(feature = "presence")]
+            self.update_presence();
+
+            self.effects.tick();
+            if let Some(tail) = tail.filter(|tail| alive && self.game.get_tail() != Some(tail)) {
+                self.effects.push_trail(&tail);
+            }
+            let events = self.game.take_events();
+            for event in &events {
+                self.effects.push(event);
+                match event {
+                    GameEvent::Ate { points, .. } if *points > FOOD_SCORE => {
+                        self.sound.play(Sound::PowerUp)
+                    }
+                    GameEvent::Ate { .. } => self.sound.play(Sound::Eat),
+                    GameEvent::Respawned => self.sound.play(Sound::Death),
+                    GameEvent::GateOpened { .. } => self.sound.play(Sound::Gate),
+                    GameEvent::Checkpoint { .. } | GameEvent::Bumped => {}
+                }
+            }
+
+            if let Some(adaptive) = &mut self.adaptive {
+                adaptive.update(&self.game, &events);
+                if adaptive.get_speed() != NORMAL_SPEED {
+                    self.game.set_speed_adjusted();
+                }
+            }
+
+            if let Some(tutorial) = &mut self.tutorial {
+                tutorial.update(&self.game, &events);
+                if tutorial.is_done() {
+                    self.sound.play(Sound::NewRecord);
+                    self.frame(terminal)?;
+                    sleep(WIN_PAUSE).await;
+                    break;
+                }
+            }
+
+            if !alive && (self.game.is_board_filled() || self.game.is_objective_met()) {
+                self.sound.play(Sound::NewRecord);
+                self.frame(terminal)?;
+                sleep(WIN_PAUSE).await;
+                break;
+            }
+            if !alive && self.game.is_puzzle_cleared() {
+                self.sound.play(Sound::NewRecord);
+                break;
+            }
+            if !alive {
+                self.sound.play(Sound::Death);
+                self.effects.shake();
+                self.play_out_shake(terminal).await?;
+                break;
+            }
 
-            if event::poll(time::Duration::from_millis(10))? {
+            let mut timeout = Duration::from_millis(10);
+            while events::poll(timeout)? {
                 self.handle_events()?;
+                timeout = Duration::ZERO;
             }
 
-            sleep(Duration::from_millis(80)).await;
+            let tick = self.tick * NORMAL_SPEED as u32 / self.get_speed() as u32;
+            planned = if self.game.is_boosting() || self.game.is_hardcore() {
+                tick / 2
+            } else {
+                tick
+            };
+            sleep(planned).await;
 
-            while self.stop && !self.exit {
+            while (self.stop || self.too_small) && !self.exit {
+                self.frame(terminal)?;
                 self.handle_events()?;
+                if self.resume_at.is_some_and(|at| Instant::now() >= at) {
+                    self.resume();
+                }
+                last_tick = Instant::now();
             }
         }
 
         Ok(self.game.get_score())
     }
 
+    /// The time a race was won or the pellets were cleared in, in tenths of a second, `None`
+    /// when neither happened.
+    pub fn get_finish_time(&self) -> Option<u16> {
+        (self.game.is_target_reached() || self.game.is_pellets_cleared())
+            .then(|| (self.elapsed.as_millis() / 100).min(u16::MAX as u128) as u16)
+    }
+
+    /// The game as it is written to the stats log, ended at `at` seconds since the epoch.
+    pub fn get_record(&self, board: &str, mode: Mode, at: u64) -> GameRecord {
+        let duration_ms = self.elapsed.as_millis().min(u64::MAX as u128) as u64;
+        GameRecord::new(&self.game, board, mode, duration_ms, at)
+    }
+
+    /// The time an objective was met in, in tenths of a second, `None` when it was not.
+    pub fn get_objective_time(&self) -> Option<u16> {
+        self.game
+            .is_objective_met()
+            .then(|| (self.elapsed.as_millis() / 100).min(u16::MAX as u128) as u16)
+    }
+
+    /// What is left of an objective's timer, `None` on boards without one.
+    fn get_time_left(&self) -> Option<Duration> {
+        let objective = self.game.get_objective()?;
+        Some(Duration::from_secs(objective.get_seconds() as u64).saturating_sub(self.elapsed))
+    }
+
+    /// The moves a puzzle was cleared in, `None` when it was not.
+    pub fn get_cleared_moves(&self) -> Option<u16> {
+        self.game.is_puzzle_cleared().then(|| self.game.get_moves())
+    }
+
+    async fn play_out_shake(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while self.effects.is_shaking() {
+            self.frame(terminal)?;
+            self.effects.tick();
+            sleep(SHAKE_FRAME).await;
+        }
+        Ok(())
+    }
+
+    fn start_cast(&mut self, terminal: &DefaultTerminal) -> io::Result<()> {
+        let size = terminal.size()?;
+        match CastRecorder::start(size.width, size.height) {
+            Ok((cast, path)) => {
+                self.cast = Some(cast);
+                toast::push(tf("game.recording", &[("path", &path)]));
+            }
+            Err(e) => toast::push(tf(
+                "game.recording_failed",
+                &[("path", &cast::CAST_DIR), ("error", &e)],
+            )),
+        }
+        Ok(())
+    }
+
+    /// Draws a frame, then the board image over it when the board is drawn as pixels.
+    fn frame(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let completed = terminal.draw(|frame| self.draw(frame))?;
+        if let Some(cast) = &mut self.cast {
+            cast.record(completed.buffer)?;
+        }
+        if self.save_frame {
+            self.save_frame = false;
+            match cast::save_frame(completed.buffer) {
+                Ok(path) => toast::push(tf("game.frame_saved", &[("path", &path)])),
+                Err(e) => toast::push(tf("game.frame_failed", &[("error", &e)])),
+            }
+        }
+        if self.drawn_pixels.get() {
+            let cells = self.game.get_cells();
+            let text = self.board_text(&cells);
+            kitty::draw(
+                &cells,
+                &text,
+                self.drawn_board.get(),
+                self.palette.is_light(),
+            )?;
+        } else if self.pixels {
+            kitty::clear()?;
+        }
+        Ok(())
+    }
+
+    fn board_text(&self, cells: &[Vec<snake_game::core::Cell>]) -> Text<'static> {
+        if self.debug.is_spawn_view() {
+            DebugStats::spawn_text(
+                cells,
+                &self.game.get_food_spawn_cells(),
+                &self.game.get_snake_spawn_cells(),
+            )
+        } else if self.spotlight && self.tutorial.is_none() && !self.demo {
+            spotlight::apply(cells, self.palette.text(cells))
+        } else {
+            self.palette.text(cells)
+        }
+    }
+
     fn draw(&self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    /// The board and the HUD around it.
+    fn size_guard(&self) -> SizeGuardTui {
+        let table_len = self.game.get_cells().len() as u16;
+        SizeGuardTui::new(table_len, table_len + HUD_ROWS)
+    }
+
+    fn score_line(&self) -> Line<'static> {
+        let gauge = (self.game.get_boost_gauge() * BOOST_GAUGE_WIDTH as f64).round() as usize;
+        let puzzle = match self.game.get_moves_left() {
+            Some(moves) => tf(
+                "game.hud_puzzle",
+                &[("apples", &self.game.get_food_left()), ("moves", &moves)],
+            ),
+            None => "".to_string(),
+        };
+        let objective = match self.game.get_objective() {
+            Some(objective) => tf(
+                "game.hud_objective",
+                &[
+                    ("eaten", &self.game.get_apples_eaten()),
+                    ("quota", &objective.get_apples()),
+                ],
+            ),
+            None => "".to_string(),
+        };
+        let gate = match self.game.get_gate_progress() {
+            Some((eaten, quota)) => tf("game.hud_gate", &[("eaten", &eaten), ("quota", &quota)]),
+            None => "".to_string(),
+        };
+        let score = self.game.get_score();
+        let seconds = format!("{:.1}", self.elapsed.as_secs_f64());
+        let score = match self.game.get_target_length() {
+            _ if self.game.is_zen() => t("game.hud_zen"),
+            _ if self.game.is_hardcore() => tf("game.hud_hardcore", &[("score", &score)]),
+            Some(target) => tf(
+                "game.hud_length",
+                &[
+                    ("length", &self.game.get_length()),
+                    ("target", &target),
+                    ("seconds", &seconds),
+                ],
+            ),
+            None => match self.game.get_pellets_left() {
+                Some(left) => tf(
+                    "game.hud_pellets",
+                    &[("left", &left), ("seconds", &seconds)],
+                ),
+                None => match self.game.get_grown_walls() {
+                    Some(walls) => tf("game.hud_walls", &[("score", &score), ("walls", &walls)]),
+                    None => tf("game.hud_score", &[("score", &score)]),
+                },
+            },
+        };
+        let lives = match self.game.has_checkpoints() && !self.game.is_hardcore() {
+            true => tf("game.hud_lives", &[("lives", &self.game.get_lives())]),
+            false => "".to_string(),
+        };
+        let mouse = match self.game.get_mouse_ticks_left() {
+            Some(ticks) => tf("game.hud_mouse", &[("ticks", &ticks)]),
+            None => "".to_string(),
+        };
+        let speed = match self.game.is_speed_adjusted() {
+            true => tf("game.hud_speed", &[("speed", &self.get_speed())]),
+            false => "".to_string(),
+        };
+        let name = match self.snake_name.as_str() {
+            _ if self.demo || self.replay.is_some() => String::new(),
+            "" => String::new(),
+            name => format!("{name} | "),
+        };
+        let cheats = match self.cheats {
+            true => t("game.hud_cheats"),
+            false => "".to_string(),
+        };
+        let boost = tf(
+            "game.hud_boost",
+            &[(
+                "gauge",
+                &format!(
+                    "{}{}",
+                    "▰".repeat(gauge),
+                    "▱".repeat(BOOST_GAUGE_WIDTH - gauge)
+                ),
+            )],
+        );
+        let text =
+            format!("{cheats}{name}{puzzle}{objective}{gate}{score}{lives}{mouse}{speed}{boost}");
+        match self.get_time_left() {
+            Some(left) => {
+                let color = match left < COUNTDOWN_WARNING {
+                    true => Color::Red,
+                    false => Color::Yellow,
+                };
+                Line::from(vec![
+                    format!(" ⏱ {:.1}s ", left.as_secs_f64())
+                        .bold()
+                        .fg(color)
+                        .reversed(),
+                    Span::raw(format!(" {text}")),
+                ])
+            }
+            None => Line::from(text),
+        }
+    }
+
+    fn is_boost_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char(' ') || key_direction(code) == Some(self.game.get_direction())
+    }
+
+    fn hold_boost(&mut self) {
+        if self.release_events {
+            self.boost_held = true;
+        } else {
+            self.boost_until = Instant::now() + BOOST_HOLD_TIMEOUT;
+        }
     }
 
     fn key_event_play_mode(&mut self, key_event: KeyEvent) {
+        if self.demo {
+            self.exit = true;
+            return;
+        }
+        if self.replay.is_some() {
+            return self.key_event_replay_mode(key_event);
+        }
+        if self.is_boost_key(key_event.code) {
+            self.hold_boost();
+        }
+
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
-            KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
-                self.game.rotation(Direction::Up)
+            // A hardcore game cannot be paused, nor slowed down.
+            KeyCode::Esc if self.game.is_hardcore() => {}
+            KeyCode::Esc => self.pause(),
+            KeyCode::Char('+') | KeyCode::Char('=') if !self.game.is_hardcore() => {
+                self.nudge_speed(true)
             }
-            KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') => {
-                self.game.rotation(Direction::Down)
-            }
-            KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('h') => {
-                self.game.rotation(Direction::Left)
-            }
-            KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => {
-                self.game.rotation(Direction::Right)
+            KeyCode::Char('-') if !self.game.is_hardcore() => self.nudge_speed(false),
+            KeyCode::F(2) => self.save_frame = true,
+            KeyCode::F(3) => self.debug.toggle(),
+            KeyCode::F(4) => self.debug.toggle_spawn_view(),
+            code => {
+                if let Some(direction) = key_direction(code) {
+                    self.game.rotation(direction)
+                }
             }
+        }
+    }
+
+    /// Changes the speed a step, within what the board's difficulty allows; the game is
+    /// scored apart from then on.
+    fn nudge_speed(&mut self, faster: bool) {
+        let speed = self.difficulty.nudge_speed(self.speed, faster);
+        if speed != self.speed {
+            self.speed = speed;
+            self.game.set_speed_adjusted();
+        }
+    }
+
+    /// The speed in percent of the board's, the one set with `+` and `-` scaled by the
+    /// adaptive speed.
+    fn get_speed(&self) -> u16 {
+        let adaptive = self
+            .adaptive
+            .as_ref()
+            .map_or(NORMAL_SPEED, |a| a.get_speed());
+        (self.speed as u32 * adaptive as u32 / NORMAL_SPEED as u32) as u16
+    }
+
+    fn key_event_release(&mut self, key_event: KeyEvent) {
+        if key_event.code == KeyCode::Char(' ') || key_direction(key_event.code).is_some() {
+            self.boost_held = false;
+        }
+    }
+
+    /// Only quitting, pausing, saving the frame and the debug views work while a replay steers.
+    fn key_event_replay_mode(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
             KeyCode::Esc => self.stop = true,
+            KeyCode::F(2) => self.save_frame = true,
+            KeyCode::F(3) => self.debug.toggle(),
+            KeyCode::F(4) => self.debug.toggle_spawn_view(),
             _ => {}
         }
     }
+
     fn key_event_stop_mode(&mut self, key_event: KeyEvent) {
+        if self.demo {
+            self.exit = true;
+            return;
+        }
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
-            KeyCode::Esc => self.stop = false,
+            KeyCode::Esc => self.resume(),
+            KeyCode::Char('r') | KeyCode::Char('R') if self.can_restart() => {
+                self.restart = true;
+                self.exit = true;
+            }
+            KeyCode::F(2) => self.save_frame = true,
+            KeyCode::Char('s') | KeyCode::Char('S') if self.saving.is_some() => {
+                self.save();
+                self.suspended = true;
+                self.exit = true;
+            }
             _ => {}
         }
     }
 
+    fn pause(&mut self) {
+        self.stop = true;
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Some(mode) = self.saving {
+            let elapsed_ms = self.elapsed.as_millis().min(u64::MAX as u128) as u64;
+            let at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            SavedGame::new(&self.game, mode, elapsed_ms, at).save();
+        }
+    }
+
+    fn resume(&mut self) {
+        self.stop = false;
+        self.focus_paused = false;
+        self.resume_at = None;
+    }
+
+    /// Pauses a running game when the terminal loses focus and counts down to carry on
+    /// once it is back. A hardcore game cannot be paused, not even this way.
+    fn focus_changed(&mut self, focused: bool) {
+        if !focused && !self.stop && !self.demo && !self.game.is_hardcore() {
+            self.pause();
+            self.focus_paused = true;
+            self.resume_at = None;
+        } else if focused && self.focus_paused {
+            self.focus_paused = false;
+            self.resume_at = Some(Instant::now() + RESUME_COUNTDOWN);
+        }
+    }
+
+    fn mouse_event(&mut self, mouse_event: MouseEvent) {
+        let board = self.drawn_board.get();
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        if !board.contains((column, row).into()) {
+            return;
+        }
+        let column = match self.drawn_pixels.get() {
+            true => (column - board.x) / 2,
+            false => column - board.x,
+        };
+        let cell = Point::new((row - board.y) as i16 - 1, column as i16 - 1);
+
+        // A click on a cell that is not free is simply ignored.
+        let _ = match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.game.teleport(cell),
+            MouseEventKind::Down(MouseButton::Right) => self.game.place_food(cell),
+            _ => Ok(()),
+        };
+    }
+
     fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                if !self.stop {
+        // Waking up now and then keeps the pause screen's countdown and toasts moving.
+        let Some(event) = events::next(events::MENU_FRAME)? else {
+            return Ok(());
+        };
+        if let Event::FocusLost | Event::FocusGained = event {
+            self.focus_changed(event == Event::FocusGained);
+            return Ok(());
+        }
+        if let Event::Resize(width, height) = event {
+            // The next draw lays everything out again; a board cut off by the new size waits.
+            self.too_small = !self.size_guard().fits(Rect::new(0, 0, width, height));
+            return Ok(());
+        }
+        if let Event::Mouse(mouse_event) = event {
+            if self.cheats {
+                self.mouse_event(mouse_event);
+            }
+            return Ok(());
+        }
+
+        if let Event::Key(key_event) = event {
+            match key_event.kind {
+                KeyEventKind::Press if !self.stop && !self.too_small => {
                     self.key_event_play_mode(key_event)
-                } else {
-                    self.key_event_stop_mode(key_event)
                 }
+                KeyEventKind::Press => self.key_event_stop_mode(key_event),
+                KeyEventKind::Repeat
+                    if !self.stop && self.replay.is_none() && self.is_boost_key(key_event.code) =>
+                {
+                    self.hold_boost()
+                }
+                KeyEventKind::Release => self.key_event_release(key_event),
+                _ => {}
             }
-            _ => {}
-        };
+        }
         Ok(())
     }
 }
 
 impl Widget for &GameTui<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from(format!("Your score {}", self.game.get_score()));
-        let instructions =
-            Line::from("Use 🠀 🠂 🠁 🠃 or h j k l to move, esc to stop/play, q/Q to quit game.");
-
-        let table = self.game.get_table();
-        let text = table.iter().map(|row| row.join("")).join("\n");
-
-        Paragraph::new(text)
-            .block(
-                Block::new()
-                    .title(title.centered())
-                    .title_bottom(instructions.centered()),
-            )
-            .centered()
-            .render(area, buf);
+        let size_guard = self.size_guard();
+        if !size_guard.fits(area) {
+            return size_guard.render(area, buf);
+        }
+
+        let title = match &self.tutorial {
+            Some(tutorial) => Line::from(tf(
+                "game.tutorial_title",
+                &[
+                    ("step", &tutorial.get_step_number()),
+                    ("steps", &Tutorial::STEPS),
+                    ("prompt", &tutorial.get_step().get_prompt()),
+                ],
+            )),
+            None => self.score_line(),
+        };
+        let title = match &self.replay {
+            Some(replay) => Line::from(tf(
+                "game.replay_title",
+                &[
+                    ("board", &replay.get_board().get_name()),
+                    ("score", &replay.get_score()),
+                    ("title", &title),
+                ],
+            )),
+            None => title,
+        };
+        let instructions = Line::from(match self.game.is_hardcore() && self.replay.is_none() {
+            true => t("game.hardcore_footer"),
+            false => t("game.footer"),
+        });
+
+        let cells = self.game.get_cells();
+        let table_len = cells.len() as u16;
+
+        let block = Block::new()
+            .title(title.centered())
+            .title_bottom(instructions.centered());
+        let inner = block.inner(area);
+        let pixels = self.pixels
+            && !self.debug.is_spawn_view()
+            && inner.width >= table_len * 2
+            && inner.height >= table_len;
+        let board_area = match pixels {
+            true => sized_board_area(inner, table_len * 2, table_len, self.effects.offset()),
+            false => board_area(inner, table_len, self.effects.offset()),
+        };
+        self.drawn_board.set(board_area);
+        self.drawn_pixels.set(pixels);
+        block.render(area, buf);
+
+        if !pixels {
+            Paragraph::new(self.board_text(&cells)).render(board_area, buf);
+        }
+        self.effects.render(board_area, &cells, buf);
+        if self.demo {
+            render_banner(&t("game.demo"), board_area, buf);
+        } else if self.game.is_board_filled() {
+            render_banner(&t("game.board_filled"), board_area, buf);
+        } else if let Some(at) = self.resume_at {
+            let left = at.saturating_duration_since(Instant::now());
+            let seconds = left.as_millis().div_ceil(1000).max(1);
+            render_banner(
+                &tf("game.resuming", &[("seconds", &seconds)]),
+                board_area,
+                buf,
+            );
+        } else if self.stop {
+            let banner = match self.saving {
+                Some(_) => t("game.paused_saving"),
+                None if self.can_restart() => t("game.paused_restart"),
+                None => t("game.paused"),
+            };
+            render_banner(&banner, board_area, buf);
+        }
+        self.debug.render(&cells, area, buf);
+        self.debug.render_legend(area, buf);
     }
 }
+
+fn render_banner(text: &str, area: Rect, buf: &mut Buffer) {
+    let width = (text.len() as u16 + 4).min(area.width);
+    let height = 3.min(area.height);
+    let banner = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    Clear.render(banner, buf);
+    Paragraph::new(text)
+        .centered()
+        .block(Block::new().borders(Borders::ALL))
+        .render(banner, buf);
+}
+
+pub fn key_direction(code: KeyCode) -> Option<Direction> {
+    match code {
+        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => Some(Direction::Up),
+        KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') => Some(Direction::Down),
+        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('h') => Some(Direction::Left),
+        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+/// Where a square table of `table_len` cells is drawn inside `area`, nudged by `offset`.
+pub fn board_area(area: Rect, table_len: u16, offset: (i16, i16)) -> Rect {
+    sized_board_area(area, table_len, table_len, offset)
+}
+
+fn sized_board_area(area: Rect, width: u16, height: u16, (dx, dy): (i16, i16)) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    let x = (area.x + (area.width - width) / 2).saturating_add_signed(dx);
+    let y = area.y.saturating_add_signed(dy);
+
+    Rect::new(x, y, width, height).clamp(area)
+}
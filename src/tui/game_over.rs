@@ -1,70 +1,253 @@
-use std::{io, time::Duration};
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
+use super::{
+    effects::Confetti,
+    events,
+    i18n::{t, tf},
+    sound::{Sound, SoundPlayer},
+    text_input::TextInput,
+    toast::Toasts,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    text::Line,
+    style::{Color, Stylize},
+    text::{Line, Span, Text},
     widgets::{Block, Paragraph, Widget},
     DefaultTerminal, Frame,
 };
-use tokio::time::sleep;
-
-use crate::core::Scoreboard;
+use snake_game::core::{Error, Replay, ScoreCategory, Scoreboard, Settings, MAX_SNAKE_NAME_LEN};
 
-#[derive(Debug)]
 pub struct GameOverTui {
-    score: u16,
+    /// `None` for a race that did not reach its target, which is not recorded.
+    score: Option<u16>,
     scoreboard: Scoreboard,
     board_name: String,
+    category: ScoreCategory,
+    replay: Replay,
+    /// Where the replay was exported to, or why it could not be.
+    exported: Option<Result<String, Error>>,
+    /// The board record the score beat, which is celebrated.
+    beaten: Option<u16>,
+    confetti: Confetti,
+    /// The name the score is saved under, the snake's name from the settings at first.
+    name: String,
+    /// Set while the name of the score is being changed.
+    naming: Option<TextInput>,
+    /// Set when the player asked for another game on the same board right away.
+    restart: bool,
 }
 
 impl GameOverTui {
-    pub fn new(board_name: String, score: u16) -> Self {
+    pub fn new(board_name: String, score: Option<u16>, replay: Replay) -> Self {
+        let category = ScoreCategory::from_rules(&replay.get_rules());
+        let settings = Settings::new();
+        let name = settings.get_snake_name().to_string();
         let mut scoreboard = Scoreboard::new();
-        scoreboard.add(board_name.clone(), score);
+        let mut beaten = None;
+        if let Some(score) = score {
+            beaten = scoreboard
+                .get_best(&board_name, category)
+                .filter(|record| category.beats(score, *record));
+            scoreboard.add(board_name.clone(), category, score);
+            scoreboard.name_last(&board_name, &name);
+
+            if scoreboard.get_best(&board_name, category) == Some(score) {
+                SoundPlayer::new(&settings).play(Sound::NewRecord);
+            }
+        }
 
         Self {
             score,
             scoreboard,
             board_name,
+            category,
+            replay,
+            exported: None,
+            beaten,
+            confetti: Confetti::new(),
+            name,
+            naming: None,
+            restart: false,
         }
     }
 
-    pub async fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        terminal.draw(|frame| self.draw(frame))?;
+    /// The category and score of the new board record, when the run set one.
+    pub fn get_new_record(&self) -> Option<(ScoreCategory, u16)> {
+        self.beaten?;
+        Some((self.category, self.score?))
+    }
 
-        sleep(Duration::from_millis(3000)).await;
+    /// Whether `r` was pressed to play the board again in the same mode.
+    pub fn is_restarting(&self) -> bool {
+        self.restart
+    }
+
+    /// Waits for a key; `e` exports the replay, `n` renames the score, `r` restarts and any
+    /// other key continues.
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
 
-        Ok(())
+            if let Some(Event::Key(key_event)) = events::next(events::MENU_FRAME)? {
+                match key_event.code {
+                    _ if key_event.kind != KeyEventKind::Press => {}
+                    _ if self.naming.is_some() => self.naming_key(key_event),
+                    KeyCode::Char('n') | KeyCode::Char('N') if self.score.is_some() => {
+                        let input = TextInput::new(MAX_SNAKE_NAME_LEN).with_value(&self.name);
+                        self.naming = Some(input);
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') if self.exported.is_none() => {
+                        self.export()
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        self.restart = true;
+                        return Ok(());
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        }
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        frame.render_widget(self, frame.area());
+    fn naming_key(&mut self, key_event: KeyEvent) {
+        let Some(input) = &mut self.naming else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc => self.naming = None,
+            KeyCode::Enter => {
+                self.name = input.get_value().trim().to_string();
+                self.scoreboard.name_last(&self.board_name, &self.name);
+                self.naming = None;
+            }
+            _ => {
+                input.key_event(key_event);
+            }
+        }
+    }
+
+    fn export(&mut self) {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let board: String = self
+            .board_name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let path = format!("replay-{board}-{}-{seconds}.json", self.replay.get_score());
+
+        self.exported = Some(self.replay.save(&path).map(|_| path));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        if self.beaten.is_some() {
+            self.confetti.tick(frame.area());
+        }
+        frame.render_widget(&*self, frame.area());
+        frame.render_widget(Toasts, frame.area());
     }
 }
 
 impl Widget for &GameOverTui {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from("Game Over :(!");
+        let title = Line::from(t("game_over.title"));
 
-        let scores = self.scoreboard.get(&self.board_name).unwrap();
+        let result = match self.score {
+            Some(score) => {
+                let best = self
+                    .scoreboard
+                    .get_best(&self.board_name, self.category)
+                    .unwrap_or(score);
+                let suffix = match (score == best, self.beaten) {
+                    (true, Some(_)) => String::new(),
+                    (true, None) => t("game_over.best"),
+                    (false, _) => tf(
+                        "game_over.best_is",
+                        &[("best", &self.category.format_score(best))],
+                    ),
+                };
+                let key = match self.category.get_mode().is_timed() {
+                    true => "game_over.your_time",
+                    false => "game_over.your_score",
+                };
+                let result = tf(
+                    key,
+                    &[
+                        ("score", &self.category.format_score(score)),
+                        ("board", &self.board_name),
+                        ("category", &self.category.get_name()),
+                    ],
+                );
+                format!("{result}\n{suffix}")
+            }
+            None => tf(
+                "game_over.unfinished",
+                &[
+                    ("board", &self.board_name),
+                    ("category", &self.category.get_name()),
+                ],
+            ),
+        };
+        let mut text = Text::default();
+        if let (Some(score), Some(record)) = (self.score, self.beaten) {
+            text.push_line(
+                Line::from(t("game_over.new_record"))
+                    .bold()
+                    .fg(Color::Yellow),
+            );
+            text.push_line(Line::default());
+            text.extend(Text::from(result));
+            text.push_line(Line::from(vec![
+                Span::raw(self.category.format_gain(score, record))
+                    .bold()
+                    .fg(Color::Black)
+                    .bg(Color::Green),
+                Span::raw(tf(
+                    "game_over.gain",
+                    &[("record", &self.category.format_score(record))],
+                )),
+            ]));
+        } else {
+            text.extend(Text::from(result));
+        }
+
+        if self.score.is_some() {
+            text.push_line(Line::default());
+            text.push_line(match &self.naming {
+                Some(input) => {
+                    let mut line = input.line();
+                    line.spans.insert(0, Span::raw(t("game_over.name_prompt")));
+                    line
+                }
+                None if self.name.is_empty() => Line::from(t("game_over.unnamed")).italic(),
+                None => Line::from(tf("game_over.saved_as", &[("name", &self.name)])).italic(),
+            });
+        }
 
-        let suffix = match self.score == scores[0] {
-            true => "The best record.".to_string(),
-            false => format!("The best record is {}", scores[0]),
+        let instructions = match &self.exported {
+            _ if self.naming.is_some() => Line::from(t("common.naming_footer")),
+            None if self.beaten.is_some() => Line::from(t("game_over.export_record")),
+            None => Line::from(t("game_over.export")),
+            Some(Ok(path)) => Line::from(tf("game_over.exported", &[("path", path)])),
+            Some(Err(e)) => Line::from(tf("game_over.export_failed", &[("error", e)])),
         };
-        let text = format!(
-            "Your score is {} in the {} board.\n{}",
-            self.score, self.board_name, suffix
-        );
 
         Paragraph::new(text)
             .block(
                 Block::new()
                     .border_type(ratatui::widgets::BorderType::Rounded)
-                    .title(title.centered()),
+                    .title(title.centered())
+                    .title_bottom(instructions.centered()),
             )
             .centered()
             .render(area, buf);
+        self.confetti.render(area, buf);
     }
 }
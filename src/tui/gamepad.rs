@@ -0,0 +1,160 @@
+//! Gamepad input through the Linux joystick API (`/dev/input/js*`), which needs no library
+//! and reports buttons and axes the same way for every controller the kernel knows. Other
+//! platforms have no such devices, so there the game simply gets no gamepad input; covering
+//! them would take a cross-platform input library such as gilrs.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::Read,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, Once,
+    },
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+
+use snake_game::core::{GamepadAction, GamepadMapping};
+
+const DEVICES: [&str; 4] = [
+    "/dev/input/js0",
+    "/dev/input/js1",
+    "/dev/input/js2",
+    "/dev/input/js3",
+];
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const AXIS_THRESHOLD: i16 = i16::MAX / 2;
+
+const JS_EVENT_BUTTON: u8 = 0x01;
+const JS_EVENT_AXIS: u8 = 0x02;
+const JS_EVENT_INIT: u8 = 0x80;
+
+static START: Once = Once::new();
+static MAPPING: Mutex<Option<GamepadMapping>> = Mutex::new(None);
+static EVENTS: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+static CAPTURED: Mutex<Option<u8>> = Mutex::new(None);
+
+/// Starts reading the first Linux joystick device on a background thread, reconnecting on unplug.
+pub fn start(mapping: &GamepadMapping) {
+    set_mapping(mapping);
+    START.call_once(|| {
+        thread::spawn(|| loop {
+            if let Some(file) = DEVICES.iter().find_map(|path| File::open(path).ok()) {
+                read_device(file);
+            }
+            thread::sleep(RECONNECT_DELAY);
+        });
+    });
+}
+
+pub fn set_mapping(mapping: &GamepadMapping) {
+    *MAPPING.lock().unwrap() = Some(*mapping);
+}
+
+pub fn next_event() -> Option<Event> {
+    EVENTS.lock().unwrap().pop_front()
+}
+
+/// Routes the next button press to [`take_captured`] instead of the event queue.
+pub fn start_capture() {
+    *CAPTURED.lock().unwrap() = None;
+    CAPTURING.store(true, Ordering::SeqCst);
+}
+
+pub fn cancel_capture() {
+    CAPTURING.store(false, Ordering::SeqCst);
+}
+
+pub fn take_captured() -> Option<u8> {
+    CAPTURED.lock().unwrap().take()
+}
+
+fn read_device(mut file: File) {
+    let mut axes = [0i8; 256];
+    let mut event = [0u8; 8];
+
+    while file.read_exact(&mut event).is_ok() {
+        let value = i16::from_le_bytes([event[4], event[5]]);
+        let kind = event[6];
+        let number = event[7];
+
+        if kind & JS_EVENT_INIT != 0 {
+            continue;
+        }
+
+        if kind & JS_EVENT_BUTTON != 0 {
+            on_button(number, value != 0);
+        } else if kind & JS_EVENT_AXIS != 0 {
+            let state = if value > AXIS_THRESHOLD {
+                1
+            } else if value < -AXIS_THRESHOLD {
+                -1
+            } else {
+                0
+            };
+
+            if state != axes[number as usize] {
+                axes[number as usize] = state;
+                on_axis(number, state);
+            }
+        }
+    }
+}
+
+fn on_button(number: u8, pressed: bool) {
+    if CAPTURING.load(Ordering::SeqCst) {
+        if pressed {
+            CAPTURING.store(false, Ordering::SeqCst);
+            *CAPTURED.lock().unwrap() = Some(number);
+        }
+        return;
+    }
+
+    let action = MAPPING
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|mapping| mapping.get_action(number));
+
+    let code = match action {
+        Some(GamepadAction::Confirm) => KeyCode::Enter,
+        // Esc backs out of menus and pauses a game, where q would give it up.
+        Some(GamepadAction::Back) => KeyCode::Esc,
+        Some(GamepadAction::Pause) => KeyCode::Esc,
+        Some(GamepadAction::Boost) => KeyCode::Char(' '),
+        None => return,
+    };
+
+    let kind = if pressed {
+        KeyEventKind::Press
+    } else {
+        KeyEventKind::Release
+    };
+    push(code, kind);
+}
+
+/// Axes 0/1 are the left stick and 6/7 the d-pad hat on common controllers; triggers are ignored.
+fn on_axis(number: u8, state: i8) {
+    let code = match (number, state) {
+        (0 | 6, -1) => KeyCode::Left,
+        (0 | 6, 1) => KeyCode::Right,
+        (1 | 7, -1) => KeyCode::Up,
+        (1 | 7, 1) => KeyCode::Down,
+        _ => return,
+    };
+    push(code, KeyEventKind::Press);
+}
+
+fn push(code: KeyCode, kind: KeyEventKind) {
+    let key_event = KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+        kind,
+        state: KeyEventState::NONE,
+    };
+    EVENTS.lock().unwrap().push_back(Event::Key(key_event));
+}
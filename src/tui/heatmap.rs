@@ -0,0 +1,167 @@
+use std::io;
+
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    select_board::preview,
+    theme::BoardFrame,
+    toast::Toasts,
+};
+use snake_game::core::{death_heatmap, read_games, Board, Point, Settings};
+
+/// The cells listed beside the board, the deadliest first.
+const LISTED_CELLS: usize = 10;
+
+/// Where the games on a board ended, drawn over the board.
+pub struct HeatmapTui {
+    board: Board,
+    heatmap: Vec<(Point, usize)>,
+    games: usize,
+    frame: BoardFrame,
+    exit: bool,
+}
+
+impl HeatmapTui {
+    pub fn new(board: Board) -> Self {
+        let games: Vec<_> = read_games()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|game| game.get_board() == board.get_name())
+            .collect();
+        Self {
+            heatmap: death_heatmap(&games, board.get_name()),
+            games: games.len(),
+            board,
+            frame: BoardFrame::new(&Settings::new()),
+            exit: false,
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Some(Event::Key(key)) = events::next(events::MENU_FRAME)? {
+                if key.kind == KeyEventKind::Press {
+                    self.exit = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(&*self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn render_board(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(tf("heatmap.title", &[("board", &self.board.get_name())])).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(preview(&self.board, inner.width, inner.height, &self.frame))
+            .centered()
+            .render(inner, buf);
+        overlay(&self.board, &self.heatmap, inner, buf);
+    }
+
+    fn render_cells(&self, area: Rect, buf: &mut Buffer) {
+        let deaths: usize = self.heatmap.iter().map(|(_, count)| count).sum();
+        let mut lines = vec![
+            Line::from(tf(
+                "heatmap.summary",
+                &[("deaths", &deaths), ("games", &self.games)],
+            )),
+            Line::default(),
+        ];
+        let max = self.heatmap.first().map_or(0, |(_, count)| *count);
+        lines.extend(
+            self.heatmap
+                .iter()
+                .take(LISTED_CELLS)
+                .map(|(point, count)| {
+                    Line::from(tf(
+                        "heatmap.cell",
+                        &[
+                            ("row", &point.get_x()),
+                            ("column", &point.get_y()),
+                            ("deaths", count),
+                        ],
+                    ))
+                    .fg(heat_color(*count, max))
+                }),
+        );
+        if self.heatmap.is_empty() {
+            lines.push(Line::from(t("heatmap.empty")).italic());
+        }
+
+        let block = Block::new()
+            .title(Line::raw(t("heatmap.cells")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+impl Widget for &HeatmapTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [main_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let [board_area, cells_area] =
+            Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .areas(main_area);
+
+        self.render_board(board_area, buf);
+        self.render_cells(cells_area, buf);
+        Paragraph::new(t("heatmap.footer"))
+            .centered()
+            .render(footer_area, buf);
+    }
+}
+
+/// Colors the cells of `heatmap` on `board` as drawn centered in `area` by `preview`, from
+/// yellow for the fewest deaths to red for the most. Nothing is drawn when the board was
+/// downscaled to fit.
+pub fn overlay(board: &Board, heatmap: &[(Point, usize)], area: Rect, buf: &mut Buffer) {
+    let table_len = board.get_size() + 2;
+    if table_len > area.width || table_len > area.height {
+        return;
+    }
+    let left = area.x + (area.width - table_len) / 2 + 1;
+    let top = area.y + 1;
+    let max = heatmap.first().map_or(0, |(_, count)| *count);
+    for (point, count) in heatmap {
+        let (Ok(row), Ok(column)) = (u16::try_from(point.get_x()), u16::try_from(point.get_y()))
+        else {
+            continue;
+        };
+        if row >= board.get_size() || column >= board.get_size() {
+            continue;
+        }
+        if let Some(cell) = buf.cell_mut(Position::new(left + column, top + row)) {
+            cell.set_bg(heat_color(*count, max)).set_fg(Color::Black);
+        }
+    }
+}
+
+/// Hotter for cells with more of the `max` deaths.
+fn heat_color(count: usize, max: usize) -> Color {
+    match count * 3 {
+        share if share > max * 2 => Color::Red,
+        share if share > max => Color::LightRed,
+        _ => Color::Yellow,
+    }
+}
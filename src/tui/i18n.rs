@@ -0,0 +1,30 @@
+//! The interface text in the language of the settings, shared by every screen.
+
+use std::{fmt::Display, sync::RwLock};
+
+use snake_game::core::{Messages, Settings};
+
+static MESSAGES: RwLock<Option<Messages>> = RwLock::new(None);
+
+/// Makes the interface speak `language` from the next frame on.
+pub fn load(language: &str) {
+    *MESSAGES.write().unwrap() = Some(Messages::load(language));
+}
+
+/// Starts with the language of the settings when nothing was loaded yet.
+fn with<T>(read: impl FnOnce(&Messages) -> T) -> T {
+    if MESSAGES.read().unwrap().is_none() {
+        load(Settings::new().get_language());
+    }
+    read(MESSAGES.read().unwrap().as_ref().unwrap())
+}
+
+/// The text of `key`.
+pub fn t(key: &str) -> String {
+    with(|messages| messages.get(key).to_string())
+}
+
+/// The text of `key` with its `{name}` placeholders filled from `args`.
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    with(|messages| messages.format(key, args))
+}
@@ -0,0 +1,190 @@
+use std::io;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Text},
+    widgets::{Block, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    modal::{Modal, ModalResult},
+    text_input::TextInput,
+    toast::{self, Toasts},
+};
+use snake_game::core::{Board, Boards, Collision};
+
+const MAX_PATH_LEN: usize = 255;
+
+/// What answering the open modal leads to.
+enum Pending {
+    Alert,
+    /// The first board of the queue has a name that is already taken.
+    Collision,
+}
+
+/// Merges another boards file into mine, asking what to do with each name that is taken.
+pub struct ImportBoardsTui {
+    path: TextInput,
+    modal: Option<(Modal, Pending)>,
+    boards: Boards,
+    /// The boards of the file that are not merged yet.
+    queue: Vec<Board>,
+    imported: usize,
+    skipped: usize,
+    exit: bool,
+}
+
+impl Default for ImportBoardsTui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportBoardsTui {
+    pub fn new() -> Self {
+        Self {
+            path: TextInput::new(MAX_PATH_LEN),
+            modal: None,
+            boards: Boards::new(),
+            queue: Vec::new(),
+            imported: 0,
+            skipped: 0,
+            exit: false,
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_events()?;
+        }
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn open(&mut self) {
+        match Boards::read_file(self.path.get_value().trim()) {
+            Ok(boards) => {
+                self.queue = boards;
+                self.merge_next();
+            }
+            Err(e) => {
+                self.modal = Some((
+                    Modal::alert("Could not import the boards", &e.to_string()),
+                    Pending::Alert,
+                ))
+            }
+        }
+    }
+
+    /// Merges the queued boards until one needs a decision, then asks for it.
+    fn merge_next(&mut self) {
+        while let Some(board) = self.queue.first() {
+            if self.boards.contains(board.get_name()) {
+                let name = board.get_name().to_string();
+                let options = vec![
+                    tf(
+                        "import_boards.import_as",
+                        &[("name", &self.boards.get_free_name(&name))],
+                    ),
+                    t("import_boards.skip"),
+                    t("import_boards.replace"),
+                ];
+                let message = tf("import_boards.name_taken", &[("name", &name)]);
+                self.modal = Some((
+                    Modal::choice(&t("import_boards.name_taken_title"), &message, options),
+                    Pending::Collision,
+                ));
+                return;
+            }
+            self.settle(Collision::Skip);
+        }
+
+        toast::push(match self.skipped {
+            0 => tf("import_boards.imported", &[("imported", &self.imported)]),
+            skipped => tf(
+                "import_boards.imported_skipped",
+                &[("imported", &self.imported), ("skipped", &skipped)],
+            ),
+        });
+        self.exit = true;
+    }
+
+    fn settle(&mut self, collision: Collision) {
+        let board = self.queue.remove(0);
+        match self.boards.merge(board, collision) {
+            Some(_) => self.imported += 1,
+            None => self.skipped += 1,
+        }
+    }
+
+    fn modal_answered(&mut self, pending: Pending, result: ModalResult) {
+        if let Pending::Collision = pending {
+            let collision = match result {
+                ModalResult::Chosen(0) => Collision::Rename,
+                ModalResult::Chosen(2) => Collision::Overwrite,
+                _ => Collision::Skip,
+            };
+            self.settle(collision);
+            self.merge_next();
+        }
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => self.exit = true,
+            KeyCode::Enter => self.open(),
+            _ => {
+                self.path.key_event(key_event);
+            }
+        }
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                match &mut self.modal {
+                    Some((modal, _)) => {
+                        if let Some(result) = modal.key_event(key_event) {
+                            let (_, pending) = self.modal.take().unwrap();
+                            self.modal_answered(pending, result);
+                        }
+                    }
+                    None => self.key_event(key_event),
+                }
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+impl Widget for &ImportBoardsTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(t("import_boards.prompt"));
+        let instructions = Line::from(t("import_boards.footer"));
+
+        let text = Text::from(self.path.line());
+
+        Paragraph::new(text)
+            .block(
+                Block::new()
+                    .title(title.centered())
+                    .title_bottom(instructions.centered()),
+            )
+            .centered()
+            .render(area, buf);
+        if let Some((modal, _)) = &self.modal {
+            modal.render(area, buf);
+        }
+    }
+}
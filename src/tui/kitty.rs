@@ -0,0 +1,180 @@
+//! Draws the board as an image with the Kitty graphics protocol, one square of pixels per
+//! cell, on terminals that support it.
+
+use std::{
+    io::{self, Write},
+    sync::OnceLock,
+};
+
+use ratatui::{
+    layout::Rect,
+    style::Color,
+    text::{Span, Text},
+};
+
+use super::terminal_query;
+use snake_game::core::{Cell, CellKind};
+
+/// Pixels per side of a cell; the terminal scales the image to the cells it covers.
+const CELL_PIXELS: usize = 4;
+const IMAGE_ID: u32 = 1;
+/// The payload of one escape sequence, as the protocol asks.
+const CHUNK: usize = 4096;
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+static SUPPORTED: OnceLock<bool> = OnceLock::new();
+
+/// Asks the terminal whether it understands the graphics protocol.
+pub fn detect() {
+    SUPPORTED.get_or_init(|| {
+        terminal_query::ask(b"\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\")
+            .is_some_and(|reply| reply.contains("\x1b_Gi=31;OK"))
+    });
+}
+
+pub fn is_supported() -> bool {
+    SUPPORTED.get().copied().unwrap_or(false)
+}
+
+/// Draws `text`, a span per cell of `cells`, as an image over `area`, beneath any text drawn
+/// there. `light` tells the colors left to the terminal apart.
+pub fn draw(cells: &[Vec<Cell>], text: &Text, area: Rect, light: bool) -> io::Result<()> {
+    let (ink, paper) = match light {
+        true => ([30, 30, 30], [240, 240, 240]),
+        false => ([220, 220, 220], [16, 16, 16]),
+    };
+    let rows = cells.len();
+    let columns = cells.first().map_or(0, Vec::len);
+    let (width, height) = (columns * CELL_PIXELS, rows * CELL_PIXELS);
+
+    let mut pixels = vec![0; width * height * 3];
+    for (x, line) in text.lines.iter().enumerate().take(rows) {
+        for (y, span) in line.spans.iter().enumerate().take(columns) {
+            let color = color_of(&cells[x][y], span).map_or(
+                match cells[x][y].get_kind() {
+                    CellKind::Empty => paper,
+                    _ if span.content.trim().is_empty() => paper,
+                    _ => ink,
+                },
+                rgb,
+            );
+            for row in x * CELL_PIXELS..(x + 1) * CELL_PIXELS {
+                for column in y * CELL_PIXELS..(y + 1) * CELL_PIXELS {
+                    let at = (row * width + column) * 3;
+                    pixels[at..at + 3].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    let payload = base64(&pixels);
+    let mut out = io::stdout().lock();
+    write!(out, "\x1b[{};{}H", area.y + 1, area.x + 1)?;
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(CHUNK).collect();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = (index + 1 < chunks.len()) as u8;
+        if index == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=24,s={width},v={height},i={IMAGE_ID},p=1,c={},r={},z=-1,C=1,q=2,m={more};",
+                area.width, area.height
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={more};")?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+    out.flush()
+}
+
+/// Takes the board image off the screen.
+pub fn clear() -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    write!(out, "\x1b_Ga=d,d=I,i={IMAGE_ID},q=2\x1b\\")?;
+    out.flush()
+}
+
+/// The color a cell is drawn in: the background of empty or blank cells, else the glyph's.
+fn color_of(cell: &Cell, span: &Span) -> Option<Color> {
+    if cell.get_kind() == CellKind::Empty || span.content.trim().is_empty() {
+        span.style.bg
+    } else {
+        span.style.fg.or(span.style.bg)
+    }
+    .filter(|color| *color != Color::Reset)
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(index) => indexed(index),
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 49, 49],
+        Color::Green => [13, 188, 121],
+        Color::Yellow => [229, 229, 16],
+        Color::Blue => [36, 114, 200],
+        Color::Magenta => [188, 63, 188],
+        Color::Cyan => [17, 168, 205],
+        Color::Gray => [204, 204, 204],
+        Color::DarkGray => [102, 102, 102],
+        Color::LightRed => [241, 76, 76],
+        Color::LightGreen => [35, 209, 139],
+        Color::LightYellow => [245, 245, 67],
+        Color::LightBlue => [59, 142, 234],
+        Color::LightMagenta => [214, 112, 214],
+        Color::LightCyan => [41, 184, 219],
+        Color::White | Color::Reset => [229, 229, 229],
+    }
+}
+
+/// A color of the 256-color palette: the 16 named ones, a 6x6x6 cube and a gray ramp.
+fn indexed(index: u8) -> [u8; 3] {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    match index {
+        0..=15 => rgb(NAMED[index as usize]),
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            [level(index / 36), level(index / 6 % 6), level(index % 6)]
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            [gray, gray, gray]
+        }
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for (index, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if index <= chunk.len() {
+                out.push(BASE64[(value >> shift & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
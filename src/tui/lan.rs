@@ -0,0 +1,549 @@
+use std::io;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use itertools::Itertools;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+use tokio::time::{sleep, Duration, Interval};
+
+use super::{
+    events,
+    game::{board_area, key_direction},
+    i18n::{t, tf},
+    text_input::TextInput,
+    theme::Palette,
+    toast::Toasts,
+};
+use snake_game::{
+    core::{Arena, ArenaMode, Board, Settings, Versus},
+    net::{Client, ClientMessage, Host, ServerMessage, Session, Task, DEFAULT_PORT},
+};
+
+/// How often keys are read and the screen redrawn between ticks.
+const INPUT_POLL: Duration = Duration::from_millis(20);
+const LOBBY_POLL: Duration = Duration::from_millis(50);
+const HOST_PLAYER: usize = 0;
+const PLAYERS: usize = 2;
+const MAX_ADDRESS_LEN: usize = 64;
+const DEFAULT_ROUNDS: u8 = 3;
+const MAX_ROUNDS: u8 = 9;
+
+enum Connection {
+    Host(Host),
+    Client(Box<Client>),
+}
+
+enum Phase {
+    Address,
+    /// Joining the host at the address, in the background.
+    Connecting(Task<Client>),
+    Lobby,
+    Playing,
+    Finished,
+    Failed(String),
+}
+
+/// Two-player game over the network, either hosting the authoritative arena or joining one.
+pub struct LanTui {
+    phase: Phase,
+    address: TextInput,
+    connection: Option<Connection>,
+    board: Option<Board>,
+    mode: ArenaMode,
+    rounds: u8,
+    session: Option<Session>,
+    /// Paces the session's ticks while the host's keys are read in between.
+    ticks: Option<Interval>,
+    arena: Option<Arena>,
+    versus: Option<Versus>,
+    player: Option<usize>,
+    spectator: bool,
+    palette: Palette,
+    exit: bool,
+}
+
+impl LanTui {
+    pub async fn host(board: Board) -> Self {
+        let mut lan = Self::new(Phase::Lobby);
+
+        match Host::bind(DEFAULT_PORT, board.clone(), HOST_PLAYER + 1, PLAYERS).await {
+            Ok(host) => {
+                lan.address.set_value(&host.get_address().to_string());
+                lan.board = Some(board);
+                lan.connection = Some(Connection::Host(host));
+            }
+            Err(e) => lan.phase = Phase::Failed(tf("lan.host_failed", &[("error", &e)])),
+        }
+
+        lan
+    }
+
+    pub fn join() -> Self {
+        Self::new(Phase::Address)
+    }
+
+    /// Joins a hosted game only to watch it.
+    pub fn watch() -> Self {
+        Self {
+            spectator: true,
+            ..Self::new(Phase::Address)
+        }
+    }
+
+    fn new(phase: Phase) -> Self {
+        Self {
+            phase,
+            address: TextInput::new(MAX_ADDRESS_LEN)
+                .with_filter(|c| c.is_ascii_alphanumeric() || ".:-[]".contains(c)),
+            connection: None,
+            board: None,
+            mode: ArenaMode::FreeForAll,
+            rounds: DEFAULT_ROUNDS,
+            session: None,
+            ticks: None,
+            arena: None,
+            versus: None,
+            player: Some(HOST_PLAYER),
+            spectator: false,
+            palette: Palette::new(&Settings::new()),
+            exit: false,
+        }
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            match self.phase {
+                Phase::Address => self.enter_address()?,
+                Phase::Connecting(_) => self.wait_for_connection()?,
+                Phase::Lobby => self.wait_for_player()?,
+                Phase::Playing => self.tick().await?,
+                Phase::Finished | Phase::Failed(_) => self.wait_for_exit()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn read_key() -> io::Result<Option<KeyEvent>> {
+        match events::read()? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                Ok(Some(key_event))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn enter_address(&mut self) -> io::Result<()> {
+        if let Some(key_event) = Self::read_key()? {
+            match key_event.code {
+                KeyCode::Esc => self.exit = true,
+                KeyCode::Enter => {
+                    let address = self.address.get_value();
+                    let spectator = self.spectator;
+                    self.phase = Phase::Connecting(Task::spawn(async move {
+                        Client::connect(&address, spectator).await
+                    }));
+                }
+                _ => {
+                    self.address.key_event(key_event);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn wait_for_connection(&mut self) -> io::Result<()> {
+        if events::poll(LOBBY_POLL)?
+            && Self::read_key()?.is_some_and(|key| key.code == KeyCode::Esc)
+        {
+            self.phase = Phase::Address;
+            return Ok(());
+        }
+
+        let Phase::Connecting(task) = &mut self.phase else {
+            return Ok(());
+        };
+        match task.try_take() {
+            Some(Ok(client)) => {
+                self.player = client.get_player();
+                self.connection = Some(Connection::Client(Box::new(client)));
+                self.phase = Phase::Lobby;
+            }
+            Some(Err(e)) => self.phase = Phase::Failed(tf("lan.join_failed", &[("error", &e)])),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// The host starts as soon as someone joins; a client waits for the first state.
+    fn wait_for_player(&mut self) -> io::Result<()> {
+        if events::poll(LOBBY_POLL)? {
+            if let Some(key_event) = Self::read_key()? {
+                self.key_event_lobby(key_event);
+            }
+        }
+
+        match &mut self.connection {
+            Some(Connection::Host(host)) => {
+                if let Some((_, ClientMessage::Join { .. })) = host.try_recv() {
+                    let board = self.board.take().unwrap();
+                    self.session = Session::start(host, board, self.mode, self.rounds, PLAYERS);
+                    self.phase = match self.session {
+                        Some(_) => Phase::Playing,
+                        None => Phase::Failed(t("lan.no_room")),
+                    };
+                }
+            }
+            Some(Connection::Client(_)) => self.receive_states(),
+            None => {}
+        }
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> io::Result<()> {
+        while events::poll(Duration::ZERO)? {
+            if let Some(key_event) = Self::read_key()? {
+                self.key_event_play_mode(key_event);
+            }
+        }
+
+        match &mut self.connection {
+            Some(Connection::Host(host)) => {
+                let session = self.session.as_mut().unwrap();
+                let ticks = self.ticks.get_or_insert_with(Session::interval);
+                tokio::select! {
+                    _ = ticks.tick() => {
+                        if !session.tick(host) {
+                            self.phase = Phase::Finished;
+                        }
+                    }
+                    _ = sleep(INPUT_POLL) => {}
+                }
+            }
+            Some(Connection::Client(_)) => {
+                self.receive_states();
+                sleep(INPUT_POLL).await;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn receive_states(&mut self) {
+        let Some(Connection::Client(client)) = &mut self.connection else {
+            return;
+        };
+
+        loop {
+            match client.try_recv() {
+                Ok(Some(ServerMessage::State(snapshot))) => {
+                    match &mut self.arena {
+                        Some(arena) => arena.restore(snapshot),
+                        None => {
+                            self.arena =
+                                Some(Arena::from_snapshot(client.get_board().clone(), snapshot))
+                        }
+                    }
+                    if matches!(self.phase, Phase::Lobby) {
+                        self.phase = Phase::Playing;
+                    }
+                }
+                Ok(Some(ServerMessage::Delta(delta))) => {
+                    if let Some(arena) = &mut self.arena {
+                        arena.apply(delta);
+                    }
+                }
+                Ok(Some(ServerMessage::Versus(versus))) => self.versus = Some(versus),
+                Ok(Some(ServerMessage::GameOver)) => self.phase = Phase::Finished,
+                Ok(Some(ServerMessage::Welcome { .. } | ServerMessage::Full)) => {}
+                Ok(None) => return,
+                Err(_) => {
+                    if !matches!(self.phase, Phase::Finished) {
+                        self.phase = Phase::Failed(t("lan.connection_lost"));
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    fn key_event_lobby(&mut self, key_event: KeyEvent) {
+        let hosting = matches!(self.connection, Some(Connection::Host(_)));
+
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Char('m') | KeyCode::Char('M') if hosting => self.mode = self.mode.next(),
+            KeyCode::Char('f') | KeyCode::Char('F') if hosting => {
+                if let ArenaMode::Coop { friendly_fire } = &mut self.mode {
+                    *friendly_fire = !*friendly_fire;
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') if hosting => {
+                self.rounds = self.rounds.saturating_sub(1).max(1)
+            }
+            KeyCode::Right | KeyCode::Char('l') if hosting => {
+                self.rounds = (self.rounds + 1).min(MAX_ROUNDS)
+            }
+            _ => {}
+        }
+    }
+
+    fn key_event_play_mode(&mut self, key_event: KeyEvent) {
+        if matches!(key_event.code, KeyCode::Char('q') | KeyCode::Char('Q')) {
+            self.exit = true;
+            return;
+        }
+
+        if let Some(direction) = key_direction(key_event.code) {
+            match &self.connection {
+                Some(Connection::Host(_)) => {
+                    if let Some(session) = &mut self.session {
+                        session.rotation(HOST_PLAYER, direction);
+                    }
+                }
+                Some(Connection::Client(client)) if !self.spectator => {
+                    client.send(ClientMessage::Input(direction))
+                }
+                Some(Connection::Client(_)) => {}
+                None => {}
+            }
+        }
+    }
+
+    fn wait_for_exit(&mut self) -> io::Result<()> {
+        if Self::read_key()?.is_some() {
+            self.exit = true;
+        }
+        Ok(())
+    }
+
+    /// The arena being shown, simulated here when hosting or mirrored from the host otherwise.
+    fn arena(&self) -> Option<&Arena> {
+        self.session
+            .as_ref()
+            .map(Session::get_arena)
+            .or(self.arena.as_ref())
+    }
+
+    fn versus(&self) -> Option<&Versus> {
+        self.session
+            .as_ref()
+            .and_then(Session::get_versus)
+            .or(self.versus.as_ref())
+    }
+
+    fn player_name(&self, index: usize) -> String {
+        match self.player {
+            Some(me) if me == index => t("lan.you"),
+            Some(_) => t("lan.rival"),
+            None => tf("lan.player", &[("number", &(index + 1))]),
+        }
+    }
+
+    fn scores(&self) -> String {
+        let Some(arena) = self.arena() else {
+            return String::new();
+        };
+
+        let scores = arena
+            .get_players()
+            .iter()
+            .enumerate()
+            .map(|(index, player)| {
+                let name = self.player_name(index);
+                let mark = if player.is_alive() { "" } else { " ✗" };
+                match self.versus() {
+                    Some(versus) => format!("{name} {}★{mark}", versus.get_wins()[index]),
+                    None => format!("{name} {}{mark}", player.get_score()),
+                }
+            })
+            .join(" | ");
+
+        match (self.versus(), arena.get_mode()) {
+            (Some(versus), _) => tf(
+                "lan.round_scores",
+                &[
+                    ("round", &versus.get_round()),
+                    ("rounds", &versus.get_rounds()),
+                    ("scores", &scores),
+                ],
+            ),
+            (None, ArenaMode::Coop { .. }) => tf(
+                "lan.team_scores",
+                &[("score", &arena.get_shared_score()), ("scores", &scores)],
+            ),
+            (None, _) => scores,
+        }
+    }
+
+    fn summary(&self, versus: &Versus) -> Text<'static> {
+        let mut lines: Vec<Line> = versus
+            .get_winners()
+            .iter()
+            .enumerate()
+            .map(|(round, winner)| {
+                let winner = match winner {
+                    Some(player) => self.player_name(*player),
+                    None => t("lan.draw"),
+                };
+                Line::from(tf(
+                    "lan.round_winner",
+                    &[("round", &(round + 1)), ("winner", &winner)],
+                ))
+            })
+            .collect();
+
+        let champion = match versus.get_champion() {
+            Some(player) if Some(player) == self.player => t("lan.match_won"),
+            Some(player) => tf("lan.match_won_by", &[("player", &self.player_name(player))]),
+            None => t("lan.match_drawn"),
+        };
+        lines.push(Line::default());
+        lines.push(Line::from(champion).bold());
+
+        Text::from(lines)
+    }
+
+    fn result(&self) -> String {
+        if let Some(arena) = self.arena() {
+            if let ArenaMode::Coop { .. } = arena.get_mode() {
+                return tf("lan.team_score", &[("score", &arena.get_shared_score())]);
+            }
+        }
+
+        let (Some(arena), Some(me)) = (self.arena(), self.player) else {
+            return String::new();
+        };
+        let players = arena.get_players();
+        let mine = players[me].get_score();
+        let best_rival = players
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != me)
+            .map(|(_, player)| player.get_score())
+            .max()
+            .unwrap_or(0);
+
+        t(match mine.cmp(&best_rival) {
+            std::cmp::Ordering::Greater => "lan.won",
+            std::cmp::Ordering::Less => "lan.lost",
+            std::cmp::Ordering::Equal => "lan.drawn",
+        })
+    }
+
+    fn render_message(title: &str, text: Text, instructions: &str, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(text)
+            .block(
+                Block::new()
+                    .title(Line::from(title).centered())
+                    .title_bottom(Line::from(instructions).centered()),
+            )
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_arena(&self, title: String, instructions: &str, area: Rect, buf: &mut Buffer) {
+        let Some(arena) = self.arena() else {
+            return;
+        };
+
+        let cells = arena.get_cells(self.player);
+        let block = Block::new()
+            .title(Line::from(title).centered())
+            .title_bottom(Line::from(instructions).centered());
+        let board_area = board_area(block.inner(area), cells.len() as u16, (0, 0));
+        block.render(area, buf);
+
+        Paragraph::new(self.palette.text(&cells)).render(board_area, buf);
+    }
+}
+
+impl Widget for &LanTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match &self.phase {
+            Phase::Address => LanTui::render_message(
+                &t("lan.address_prompt"),
+                Text::from(self.address.line()),
+                &t("lan.address_footer"),
+                area,
+                buf,
+            ),
+            Phase::Connecting(_) => LanTui::render_message(
+                &t("lan.connecting"),
+                Text::from(self.address.get_value()),
+                &t("lan.connecting_footer"),
+                area,
+                buf,
+            ),
+            Phase::Lobby => {
+                let text = match &self.connection {
+                    Some(Connection::Host(_)) => {
+                        let mode = match self.mode {
+                            ArenaMode::Versus => tf("lan.versus_mode", &[("rounds", &self.rounds)]),
+                            ArenaMode::Coop {
+                                friendly_fire: true,
+                            } => t("lan.coop_fatal"),
+                            ArenaMode::Coop {
+                                friendly_fire: false,
+                            } => t("lan.coop_pass"),
+                            mode => mode.get_name().to_string(),
+                        };
+                        tf(
+                            "lan.waiting_for_player",
+                            &[("address", &self.address.get_value()), ("mode", &mode)],
+                        )
+                    }
+                    _ if self.spectator => t("lan.waiting_for_game"),
+                    _ => t("lan.waiting_for_host"),
+                };
+                let instructions = match &self.connection {
+                    Some(Connection::Host(_)) => t("lan.host_footer"),
+                    _ => t("lan.lobby_footer"),
+                };
+                LanTui::render_message(&t("lan.title"), Text::from(text), &instructions, area, buf)
+            }
+            Phase::Playing if self.spectator => self.render_arena(
+                tf("lan.watching", &[("scores", &self.scores())]),
+                &t("lan.watch_footer"),
+                area,
+                buf,
+            ),
+            Phase::Playing => self.render_arena(self.scores(), &t("lan.play_footer"), area, buf),
+            Phase::Finished if self.versus().is_some() => LanTui::render_message(
+                &t("lan.summary"),
+                self.summary(self.versus().unwrap()),
+                &t("common.any_key_to_back"),
+                area,
+                buf,
+            ),
+            Phase::Finished => self.render_arena(
+                tf(
+                    "lan.game_over",
+                    &[("result", &self.result()), ("scores", &self.scores())],
+                ),
+                &t("common.any_key_to_back"),
+                area,
+                buf,
+            ),
+            Phase::Failed(error) => LanTui::render_message(
+                &t("lan.title"),
+                Text::from(error.as_str()),
+                &t("common.any_key_to_back"),
+                area,
+                buf,
+            ),
+        }
+    }
+}
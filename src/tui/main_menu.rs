@@ -0,0 +1,269 @@
+use std::{
+    io,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    modal::{Modal, ModalResult},
+    sound::{Sound, SoundPlayer},
+    store_error::age,
+    toast::Toasts,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget,
+    },
+    DefaultTerminal, Frame,
+};
+use snake_game::core::{SavedGame, Settings};
+
+#[derive(Clone, Copy)]
+pub enum MainMenuTuiResult {
+    /// Carry on the saved game.
+    Continue,
+    Play,
+    Race,
+    Zen,
+    Hardcore,
+    Pellets,
+    Maze,
+    Tutorial,
+    WatchReplay,
+    #[cfg(feature = "net")]
+    HostGame,
+    #[cfg(feature = "net")]
+    JoinGame,
+    #[cfg(feature = "net")]
+    WatchGame,
+    ScoreBoards,
+    #[cfg(feature = "packs")]
+    BoardPacks,
+    Settings,
+    /// Nobody touched a key for a while.
+    Attract,
+    Exit,
+}
+
+const ATTRACT_AFTER: Duration = Duration::from_secs(60);
+
+/// The message key of each item, with what choosing it does. Continuing is only offered
+/// when there is a saved game.
+fn items(saved: bool) -> Vec<(&'static str, MainMenuTuiResult)> {
+    let mut items = vec![
+        ("menu.play", MainMenuTuiResult::Play),
+        ("menu.race", MainMenuTuiResult::Race),
+        ("menu.zen", MainMenuTuiResult::Zen),
+        ("menu.hardcore", MainMenuTuiResult::Hardcore),
+        ("menu.pellets", MainMenuTuiResult::Pellets),
+        ("menu.maze", MainMenuTuiResult::Maze),
+        ("menu.tutorial", MainMenuTuiResult::Tutorial),
+        ("menu.watch_replay", MainMenuTuiResult::WatchReplay),
+        #[cfg(feature = "net")]
+        ("menu.host_lan", MainMenuTuiResult::HostGame),
+        #[cfg(feature = "net")]
+        ("menu.join_lan", MainMenuTuiResult::JoinGame),
+        #[cfg(feature = "net")]
+        ("menu.watch_lan", MainMenuTuiResult::WatchGame),
+        ("menu.scoreboards", MainMenuTuiResult::ScoreBoards),
+        #[cfg(feature = "packs")]
+        ("menu.board_packs", MainMenuTuiResult::BoardPacks),
+        ("menu.settings", MainMenuTuiResult::Settings),
+        ("menu.quit", MainMenuTuiResult::Exit),
+    ];
+    if saved {
+        items.insert(0, ("menu.continue", MainMenuTuiResult::Continue));
+    }
+    items
+}
+
+pub struct MainMenuTui {
+    result: Option<MainMenuTuiResult>,
+    state: ListState,
+    sound: SoundPlayer,
+    saved: Option<SavedGame>,
+    /// Asks before carrying on the saved game.
+    modal: Option<Modal>,
+}
+
+impl Default for MainMenuTui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MainMenuTui {
+    pub fn new() -> Self {
+        let mut state = ListState::default();
+        state.select_first();
+
+        Self {
+            result: None,
+            state,
+            sound: SoundPlayer::new(&Settings::new()),
+            saved: SavedGame::load(),
+            modal: None,
+        }
+    }
+
+    /// The chosen item, or `Attract` once the menu has sat idle for a minute.
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<MainMenuTuiResult> {
+        let mut started = Instant::now();
+        loop {
+            if let Some(result) = self.result {
+                return Ok(result);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let idle = started.elapsed();
+            if idle >= ATTRACT_AFTER {
+                return Ok(MainMenuTuiResult::Attract);
+            }
+            if events::poll(events::MENU_FRAME.min(ATTRACT_AFTER - idle))? {
+                self.handle_events()?;
+                started = Instant::now();
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        if let Some(modal) = &mut self.modal {
+            if let Some(result) = modal.key_event(key_event) {
+                self.modal = None;
+                if result == ModalResult::Confirmed {
+                    self.result = Some(MainMenuTuiResult::Continue);
+                }
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Enter => {
+                let chosen = self
+                    .state
+                    .selected()
+                    .and_then(|index| items(self.saved.is_some()).get(index).copied())
+                    .map(|(_, result)| result);
+                match chosen {
+                    Some(MainMenuTuiResult::Continue) => self.confirm_continue(),
+                    chosen => self.result = chosen,
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.result = Some(MainMenuTuiResult::Exit),
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
+            _ => {}
+        }
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        match events::read()? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                self.key_event(key_event)
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn confirm_continue(&mut self) {
+        let Some(saved) = &self.saved else {
+            return;
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let message = tf(
+            "menu.continue_prompt",
+            &[
+                ("board", &saved.get_board_name()),
+                ("score", &saved.get_score()),
+                ("saved", &age(now.saturating_sub(saved.get_at()))),
+            ],
+        );
+        self.modal = Some(Modal::confirm(&t("menu.continue_title"), &message));
+    }
+
+    fn select_next(&mut self) {
+        self.state.select_next();
+        self.sound.play(Sound::MenuMove);
+    }
+
+    fn select_previous(&mut self) {
+        self.state.select_previous();
+        self.sound.play(Sound::MenuMove);
+    }
+
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(t("menu.title"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(t("menu.footer"))
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(t("menu.list")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let items: Vec<_> = items(self.saved.is_some())
+            .into_iter()
+            .map(|(key, _)| ListItem::from(t(key)))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+}
+
+impl Widget for &mut MainMenuTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [_, menu_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(30),
+            Constraint::Fill(1),
+        ])
+        .areas(main_area);
+
+        MainMenuTui::render_header(header_area, buf);
+        MainMenuTui::render_footer(footer_area, buf);
+        self.render_list(menu_area, buf);
+        if let Some(modal) = &self.modal {
+            modal.render(area, buf);
+        }
+    }
+}
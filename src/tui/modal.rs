@@ -0,0 +1,168 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use super::i18n::t;
+
+/// What a modal asks of the player.
+pub enum ModalKind {
+    /// A message to acknowledge.
+    Alert,
+    /// A yes or no question.
+    Confirm,
+    /// One of several options.
+    Choice(Vec<String>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ModalResult {
+    Confirmed,
+    Chosen(usize),
+    /// Closed without agreeing or choosing anything.
+    Dismissed,
+}
+
+/// A centered dialog drawn over a dimmed screen, which takes the keys until it is answered.
+pub struct Modal {
+    kind: ModalKind,
+    title: String,
+    message: String,
+    /// The highlighted button or option; for a confirmation 0 is "Yes".
+    selected: usize,
+}
+
+impl Modal {
+    pub fn alert(title: &str, message: &str) -> Self {
+        Self::new(ModalKind::Alert, title, message)
+    }
+
+    pub fn confirm(title: &str, message: &str) -> Self {
+        Self::new(ModalKind::Confirm, title, message)
+    }
+
+    pub fn choice(title: &str, message: &str, options: Vec<String>) -> Self {
+        Self::new(ModalKind::Choice(options), title, message)
+    }
+
+    fn new(kind: ModalKind, title: &str, message: &str) -> Self {
+        Self {
+            kind,
+            title: title.to_string(),
+            message: message.to_string(),
+            selected: 0,
+        }
+    }
+
+    /// `Some` once the modal is answered and should be closed.
+    pub fn key_event(&mut self, key_event: KeyEvent) -> Option<ModalResult> {
+        match (&self.kind, key_event.code) {
+            (_, KeyCode::Esc) => Some(ModalResult::Dismissed),
+            (ModalKind::Alert, KeyCode::Enter | KeyCode::Char(' ')) => Some(ModalResult::Dismissed),
+            (ModalKind::Confirm, KeyCode::Char('y') | KeyCode::Char('Y')) => {
+                Some(ModalResult::Confirmed)
+            }
+            (ModalKind::Confirm, KeyCode::Char('n') | KeyCode::Char('N')) => {
+                Some(ModalResult::Dismissed)
+            }
+            (
+                ModalKind::Confirm,
+                KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Tab
+                | KeyCode::Char('h')
+                | KeyCode::Char('l'),
+            ) => {
+                self.selected = 1 - self.selected;
+                None
+            }
+            (ModalKind::Confirm, KeyCode::Enter) if self.selected == 0 => {
+                Some(ModalResult::Confirmed)
+            }
+            (ModalKind::Confirm, KeyCode::Enter) => Some(ModalResult::Dismissed),
+            (ModalKind::Choice(options), KeyCode::Down | KeyCode::Char('j')) => {
+                self.selected = (self.selected + 1).min(options.len().saturating_sub(1));
+                None
+            }
+            (ModalKind::Choice(_), KeyCode::Up | KeyCode::Char('k')) => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            (ModalKind::Choice(options), KeyCode::Enter) if !options.is_empty() => {
+                Some(ModalResult::Chosen(self.selected))
+            }
+            _ => None,
+        }
+    }
+
+    fn buttons(&self) -> Vec<Line<'static>> {
+        let highlight = |text: String, selected: bool| {
+            if selected {
+                Span::raw(format!("> {text} <")).bold()
+            } else {
+                Span::raw(format!("  {text}  "))
+            }
+        };
+
+        match &self.kind {
+            ModalKind::Alert => vec![Line::from(highlight(t("common.ok"), true))],
+            ModalKind::Confirm => vec![Line::from(vec![
+                highlight(t("common.yes"), self.selected == 0),
+                Span::raw("   "),
+                highlight(t("common.no"), self.selected == 1),
+            ])],
+            ModalKind::Choice(options) => options
+                .iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    Line::from(highlight(option.clone(), index == self.selected))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Widget for &Modal {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::new().add_modifier(Modifier::DIM));
+
+        let buttons = self.buttons();
+        let content_width = buttons
+            .iter()
+            .map(Line::width)
+            .chain([self.message.chars().count(), self.title.chars().count() + 2])
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (content_width + 4).min(area.width);
+        let message_rows =
+            (self.message.chars().count() as u16).div_ceil(width.saturating_sub(4).max(1));
+        let height = (message_rows + buttons.len() as u16 + 3).min(area.height);
+        let dialog = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        let mut text = Text::from(Line::from(self.message.clone()));
+        text.push_line(Line::default());
+        text.extend(buttons);
+
+        Clear.render(dialog, buf);
+        Paragraph::new(text)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::new()
+                    .title(Line::from(format!(" {} ", self.title)).centered())
+                    .borders(Borders::ALL)
+                    .border_set(border::ROUNDED),
+            )
+            .render(dialog, buf);
+    }
+}
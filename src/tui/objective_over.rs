@@ -0,0 +1,93 @@
+use std::{io, time::Duration};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+use tokio::time::sleep;
+
+use super::{
+    i18n::{t, tf},
+    sound::{Sound, SoundPlayer},
+    toast::Toasts,
+};
+use snake_game::core::{ObjectiveRecord, ObjectiveRecords, Settings};
+
+/// Result screen of a board with an objective; `time` is set, in tenths of a second, when
+/// it was met.
+pub struct ObjectiveOverTui {
+    board_name: String,
+    time: Option<u16>,
+    record: ObjectiveRecord,
+}
+
+impl ObjectiveOverTui {
+    pub fn new(board_name: String, time: Option<u16>) -> Self {
+        let mut records = ObjectiveRecords::new();
+        if records.add(board_name.clone(), time) {
+            SoundPlayer::new(&Settings::new()).play(Sound::NewRecord);
+        }
+
+        Self {
+            record: records.get(&board_name).unwrap_or_default(),
+            board_name,
+            time,
+        }
+    }
+
+    pub async fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        terminal.draw(|frame| self.draw(frame))?;
+
+        sleep(Duration::from_millis(3000)).await;
+
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+}
+
+impl Widget for &ObjectiveOverTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match self.time {
+            Some(_) => Line::from(t("objective_over.won")),
+            None => Line::from(t("objective_over.lost")),
+        };
+
+        let seconds = |tenths: u16| format!("{:.1}", tenths as f64 / 10.0);
+        let board = &self.board_name;
+        let result = match self.time {
+            Some(time) => tf(
+                "objective_over.met",
+                &[("board", board), ("seconds", &seconds(time))],
+            ),
+            None => tf("objective_over.ran_out", &[("board", board)]),
+        };
+        let best = match (self.time, self.record.get_best()) {
+            (Some(time), Some(best)) if time == best => t("objective_over.fastest"),
+            (_, Some(best)) => tf("objective_over.best", &[("seconds", &seconds(best))]),
+            (_, None) => t("objective_over.unbeaten"),
+        };
+        let tally = tf(
+            "objective_over.tally",
+            &[
+                ("wins", &self.record.get_wins()),
+                ("losses", &self.record.get_losses()),
+            ],
+        );
+
+        Paragraph::new(format!("{result}\n{best}\n{tally}"))
+            .block(
+                Block::new()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .title(title.centered()),
+            )
+            .centered()
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,108 @@
+use std::io;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Text},
+    widgets::{Block, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+
+use super::{events, i18n::t, modal::Modal, text_input::TextInput, toast::Toasts};
+use snake_game::core::Replay;
+
+const MAX_PATH_LEN: usize = 255;
+
+/// Asks for the path of a replay file shared by another player.
+pub struct OpenReplayTui {
+    path: TextInput,
+    /// Why the last path could not be opened.
+    error: Option<Modal>,
+    replay: Option<Replay>,
+    exit: bool,
+}
+
+impl Default for OpenReplayTui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenReplayTui {
+    pub fn new() -> Self {
+        Self {
+            path: TextInput::new(MAX_PATH_LEN),
+            error: None,
+            replay: None,
+            exit: false,
+        }
+    }
+
+    /// The loaded replay, `None` when the player backs out.
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<Option<Replay>> {
+        while !self.exit && self.replay.is_none() {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_events()?;
+        }
+        Ok(self.replay.take())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => self.exit = true,
+            KeyCode::Enter => match Replay::load(self.path.get_value().trim()) {
+                Ok(replay) => self.replay = Some(replay),
+                Err(e) => {
+                    self.error = Some(Modal::alert("Could not open the replay", &e.to_string()))
+                }
+            },
+            _ => {
+                self.path.key_event(key_event);
+            }
+        }
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                match &mut self.error {
+                    Some(modal) => {
+                        if modal.key_event(key_event).is_some() {
+                            self.error = None;
+                        }
+                    }
+                    None => self.key_event(key_event),
+                }
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+}
+
+impl Widget for &OpenReplayTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(t("open_replay.prompt"));
+        let instructions = Line::from(t("open_replay.footer"));
+
+        let text = Text::from(self.path.line());
+
+        Paragraph::new(text)
+            .block(
+                Block::new()
+                    .title(title.centered())
+                    .title_bottom(instructions.centered()),
+            )
+            .centered()
+            .render(area, buf);
+        if let Some(modal) = &self.error {
+            modal.render(area, buf);
+        }
+    }
+}
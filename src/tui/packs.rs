@@ -0,0 +1,402 @@
+use std::{collections::HashMap, io};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget, Wrap,
+    },
+    DefaultTerminal, Frame,
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    modal::Modal,
+    select_board::preview,
+    text_input::TextInput,
+    theme::BoardFrame,
+    toast::{self, Toasts},
+};
+use snake_game::{
+    core::{Board, Boards, Collision, Difficulty, Settings},
+    net::{check_url, fetch_index, Pack, Task},
+};
+
+const MAX_URL_LEN: usize = 255;
+
+/// Network work asked for by a key, started by `run` and shown as "Loading" until it is done.
+enum Action {
+    FetchIndex,
+    /// Fetches the boards of the pack at an index of `packs`.
+    Preview(usize),
+    Install(usize),
+}
+
+/// The network work going on in the background.
+enum Loading {
+    Index(Task<Vec<Pack>>),
+    /// The boards of the pack at an index of `packs`, installed when `install` is set.
+    Pack {
+        index: usize,
+        install: bool,
+        task: Task<Vec<Board>>,
+    },
+}
+
+/// Lists the board packs of the index at the configured URL, previews them and installs them.
+pub struct PacksTui {
+    url: TextInput,
+    editing_url: bool,
+    packs: Vec<Pack>,
+    /// The boards of the packs fetched so far, by index in `packs`.
+    boards: HashMap<usize, Vec<Board>>,
+    state: ListState,
+    /// The board of the highlighted pack shown in the preview.
+    board: usize,
+    action: Option<Action>,
+    loading: Option<Loading>,
+    error: Option<Modal>,
+    frame: BoardFrame,
+    exit: bool,
+}
+
+impl Default for PacksTui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacksTui {
+    pub fn new() -> Self {
+        let settings = Settings::new();
+        let url = settings.get_pack_index_url().to_string();
+
+        // Without an index URL there is nothing to fetch until the player enters one.
+        let editing_url = url.is_empty();
+
+        Self {
+            url: TextInput::new(MAX_URL_LEN).with_value(&url),
+            editing_url,
+            packs: Vec::new(),
+            boards: HashMap::new(),
+            state: ListState::default(),
+            board: 0,
+            action: (!editing_url).then_some(Action::FetchIndex),
+            loading: None,
+            error: None,
+            frame: BoardFrame::new(&settings),
+            exit: false,
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.exit {
+            if let Some(action) = self.action.take() {
+                self.act(action);
+            }
+            self.check_loading();
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_events()?;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(&mut *self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn act(&mut self, action: Action) {
+        let url = self.url.get_value().trim().to_string();
+        let (index, install) = match action {
+            Action::FetchIndex => {
+                let task = Task::spawn(async move { fetch_index(&url).await });
+                self.loading = Some(Loading::Index(task));
+                return;
+            }
+            Action::Preview(index) => (index, false),
+            Action::Install(index) => (index, true),
+        };
+        if self.boards.contains_key(&index) {
+            if install {
+                self.install(index);
+            }
+            return;
+        }
+        let pack = self.packs[index].clone();
+        let task = Task::spawn(async move { pack.fetch(&url).await });
+        self.loading = Some(Loading::Pack {
+            index,
+            install,
+            task,
+        });
+    }
+
+    /// Takes in the result of the background work once it is done.
+    fn check_loading(&mut self) {
+        match self.loading.take() {
+            Some(Loading::Index(mut task)) => match task.try_take() {
+                Some(Ok(packs)) => {
+                    self.packs = packs;
+                    self.boards.clear();
+                    self.board = 0;
+                    self.state.select((!self.packs.is_empty()).then_some(0));
+                }
+                Some(Err(e)) => self.fail(&t("packs.index_failed"), e),
+                None => self.loading = Some(Loading::Index(task)),
+            },
+            Some(Loading::Pack {
+                index,
+                install,
+                mut task,
+            }) => match task.try_take() {
+                Some(Ok(boards)) => {
+                    self.boards.insert(index, boards);
+                    if install {
+                        self.install(index);
+                    }
+                }
+                Some(Err(e)) => self.fail(&t("packs.pack_failed"), e),
+                None => {
+                    self.loading = Some(Loading::Pack {
+                        index,
+                        install,
+                        task,
+                    })
+                }
+            },
+            None => {}
+        }
+    }
+
+    /// Adds the pack's boards, keeping mine when a name is taken.
+    fn install(&mut self, index: usize) {
+        let mut boards = Boards::new();
+        let installed = self.boards[&index]
+            .iter()
+            .filter_map(|board| boards.merge(board.clone(), Collision::Rename))
+            .count();
+        toast::push(tf(
+            "packs.installed",
+            &[
+                ("installed", &installed),
+                ("pack", &self.packs[index].get_name()),
+            ],
+        ));
+    }
+
+    fn fail(&mut self, title: &str, error: io::Error) {
+        self.error = Some(Modal::alert(title, &error.to_string()));
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        if self.editing_url {
+            return self.key_event_url(key_event);
+        }
+
+        if self.loading.is_some() {
+            if matches!(
+                key_event.code,
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc
+            ) {
+                self.exit = true;
+            }
+            return;
+        }
+
+        let selected = self.state.selected().filter(|i| *i < self.packs.len());
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Char('u') => self.editing_url = true,
+            KeyCode::Char('r') => self.action = Some(Action::FetchIndex),
+            KeyCode::Char('j') | KeyCode::Down => self.select(1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(-1),
+            KeyCode::Char('l') | KeyCode::Right => self.board += 1,
+            KeyCode::Char('h') | KeyCode::Left => self.board = self.board.saturating_sub(1),
+            KeyCode::Enter => self.action = selected.map(Action::Preview),
+            KeyCode::Char('i') => self.action = selected.map(Action::Install),
+            _ => {}
+        }
+        if let Some(boards) = selected.and_then(|index| self.boards.get(&index)) {
+            self.board = self.board.min(boards.len().saturating_sub(1));
+        }
+    }
+
+    fn key_event_url(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => {
+                let url = self.url.get_value().trim().to_string();
+                if let Err(e) = check_url(&url) {
+                    return self.fail(&t("packs.bad_url"), e);
+                }
+                Settings::new().set_pack_index_url(url);
+                self.editing_url = false;
+                self.action = Some(Action::FetchIndex);
+            }
+            KeyCode::Esc => {
+                self.url.set_value(Settings::new().get_pack_index_url());
+                self.editing_url = false;
+            }
+            _ => {
+                self.url.key_event(key_event);
+            }
+        }
+    }
+
+    fn select(&mut self, step: isize) {
+        if self.packs.is_empty() {
+            return;
+        }
+        let selected = self.state.selected().unwrap_or(0) as isize + step;
+        self.state.select(Some(
+            selected.clamp(0, self.packs.len() as isize - 1) as usize
+        ));
+        self.board = 0;
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                match &mut self.error {
+                    Some(modal) => {
+                        if modal.key_event(key_event).is_some() {
+                            self.error = None;
+                        }
+                    }
+                    None => self.key_event(key_event),
+                }
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn render_url(&self, area: Rect, buf: &mut Buffer) {
+        let mut line = if self.editing_url {
+            self.url.line()
+        } else {
+            Line::from(self.url.get_value())
+        };
+        line.spans.insert(0, t("packs.index").bold());
+        Paragraph::new(line).centered().render(area, buf);
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let keys = if self.loading.is_some() {
+            t("packs.loading_footer")
+        } else if self.editing_url {
+            t("packs.url_footer")
+        } else {
+            t("packs.footer")
+        };
+        Paragraph::new(keys).centered().render(area, buf);
+    }
+
+    fn render_packs(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(t("packs.list")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let items: Vec<_> = self
+            .packs
+            .iter()
+            .map(|pack| ListItem::from(pack.get_name().to_string()))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+
+    fn render_preview(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(t("packs.preview")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(index) = self.state.selected().filter(|i| *i < self.packs.len()) else {
+            return;
+        };
+        let pack = &self.packs[index];
+        let [about_area, board_area] =
+            Layout::vertical([Constraint::Length(5), Constraint::Fill(1)]).areas(inner);
+
+        let mut about = vec![Line::from(pack.get_description().to_string())];
+        if pack.is_campaign() {
+            about.push(Line::from(t("packs.campaign")).italic());
+        }
+        match self.boards.get(&index) {
+            Some(boards) => match boards.get(self.board) {
+                Some(board) => {
+                    about.push(Line::from(tf(
+                        "packs.board",
+                        &[
+                            ("number", &(self.board + 1)),
+                            ("boards", &boards.len()),
+                            ("name", &board.get_name()),
+                        ],
+                    )));
+                    about.push(Line::from(format!(
+                        "{0}x{0}, {1}",
+                        board.get_size(),
+                        Difficulty::estimate(board).get_name()
+                    )));
+                    Paragraph::new(preview(
+                        board,
+                        board_area.width,
+                        board_area.height,
+                        &self.frame,
+                    ))
+                    .alignment(Alignment::Center)
+                    .render(board_area, buf);
+                }
+                None => about.push(Line::from(t("packs.no_boards"))),
+            },
+            None => about.push(Line::from(t("packs.fetch_boards"))),
+        }
+        Paragraph::new(about)
+            .wrap(Wrap { trim: true })
+            .centered()
+            .render(about_area, buf);
+    }
+}
+
+impl Widget for &mut PacksTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, url_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main_area);
+
+        Paragraph::new(t("packs.title"))
+            .bold()
+            .centered()
+            .render(header_area, buf);
+        self.render_url(url_area, buf);
+        self.render_packs(list_area, buf);
+        self.render_preview(preview_area, buf);
+        self.render_footer(footer_area, buf);
+        if let Some(modal) = &self.error {
+            modal.render(area, buf);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Shows the game being played on the player's Discord profile through the local Discord
+//! client's IPC socket. Updates are sent from a thread of their own, at most every few
+//! seconds, and nothing happens when Discord is not running.
+
+use std::{
+    env,
+    io::{self, Read, Write},
+    process,
+    sync::{Mutex, Once, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+
+/// Names the Discord application the activity is shown for, when the game is run or else
+/// when it is built.
+const CLIENT_ID_VARIABLE: &str = "SNAKE_DISCORD_CLIENT_ID";
+const BUILT_CLIENT_ID: Option<&str> = option_env!("SNAKE_DISCORD_CLIENT_ID");
+/// Discord drops updates sent more often than this.
+const UPDATE_EVERY: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(15);
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+
+/// What is shown: the mode and board, the score, and when the game started.
+#[derive(Clone, PartialEq)]
+struct Activity {
+    details: String,
+    state: String,
+    started: u64,
+}
+
+static CLIENT_ID: OnceLock<Option<String>> = OnceLock::new();
+static START: Once = Once::new();
+/// The activity to show, `None` to clear it.
+static ACTIVITY: Mutex<Option<Activity>> = Mutex::new(None);
+
+/// Whether there is a Discord application to show the activity for.
+pub fn is_available() -> bool {
+    client_id().is_some()
+}
+
+fn client_id() -> Option<&'static str> {
+    CLIENT_ID
+        .get_or_init(|| {
+            env::var(CLIENT_ID_VARIABLE)
+                .ok()
+                .filter(|id| !id.trim().is_empty())
+                .or_else(|| BUILT_CLIENT_ID.map(str::to_string))
+        })
+        .as_deref()
+}
+
+/// Shows `details` and `state` from now on, with the time since `started` (seconds since
+/// the Unix epoch).
+pub fn set(details: String, state: String, started: u64) {
+    let Some(client_id) = client_id() else {
+        return;
+    };
+    *ACTIVITY.lock().unwrap() = Some(Activity {
+        details,
+        state,
+        started,
+    });
+    START.call_once(|| {
+        thread::spawn(move || loop {
+            if let Some(mut pipe) = connect(client_id) {
+                // Gone once Discord quits; it is looked for again after a while.
+                let _ = publish(&mut *pipe);
+            }
+            thread::sleep(RECONNECT_DELAY);
+        });
+    });
+}
+
+/// Takes the activity off the profile.
+pub fn clear() {
+    *ACTIVITY.lock().unwrap() = None;
+}
+
+trait Pipe: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Pipe for T {}
+
+/// The first Discord client found, after the handshake.
+fn connect(client_id: &str) -> Option<Box<dyn Pipe>> {
+    let mut pipe = (0..10).find_map(open)?;
+    let handshake = json!({ "v": 1, "client_id": client_id });
+    send(&mut *pipe, OP_HANDSHAKE, &handshake).ok()?;
+    receive(&mut *pipe).ok()?;
+    Some(pipe)
+}
+
+#[cfg(unix)]
+fn open(index: u8) -> Option<Box<dyn Pipe>> {
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .unwrap_or_else(|| "/tmp".to_string());
+    let path = format!("{}/discord-ipc-{index}", dir.trim_end_matches('/'));
+    let stream = std::os::unix::net::UnixStream::connect(path).ok()?;
+    Some(Box::new(stream))
+}
+
+#[cfg(windows)]
+fn open(index: u8) -> Option<Box<dyn Pipe>> {
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(format!(r"\\?\pipe\discord-ipc-{index}"))
+        .ok()?;
+    Some(Box::new(file))
+}
+
+/// Sends every change of the activity until the connection fails.
+fn publish(pipe: &mut dyn Pipe) -> io::Result<()> {
+    let mut sent = None;
+    let mut nonce = 0u64;
+    loop {
+        let activity = ACTIVITY.lock().unwrap().clone();
+        if nonce == 0 || activity != sent {
+            nonce += 1;
+            let command = json!({
+                "cmd": "SET_ACTIVITY",
+                "args": { "pid": process::id(), "activity": activity.as_ref().map(to_json) },
+                "nonce": nonce.to_string(),
+            });
+            send(pipe, OP_FRAME, &command)?;
+            receive(pipe)?;
+            sent = activity;
+        }
+        thread::sleep(UPDATE_EVERY);
+    }
+}
+
+fn to_json(activity: &Activity) -> Value {
+    json!({
+        "details": activity.details,
+        "state": activity.state,
+        "timestamps": { "start": activity.started },
+    })
+}
+
+/// A frame is its opcode and length, both little-endian, then the JSON payload.
+fn send(pipe: &mut dyn Pipe, op: u32, payload: &Value) -> io::Result<()> {
+    let payload = payload.to_string();
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload.as_bytes());
+    pipe.write_all(&frame)?;
+    pipe.flush()
+}
+
+/// Reads the reply to a frame; only whether one came matters.
+fn receive(pipe: &mut dyn Pipe) -> io::Result<()> {
+    let mut header = [0; 8];
+    pipe.read_exact(&mut header)?;
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    io::copy(&mut pipe.take(len as u64), &mut io::sink())?;
+    Ok(())
+}
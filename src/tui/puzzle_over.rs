@@ -0,0 +1,85 @@
+use std::{io, time::Duration};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+    DefaultTerminal, Frame,
+};
+use tokio::time::sleep;
+
+use super::{
+    i18n::{t, tf},
+    sound::{Sound, SoundPlayer},
+    toast::Toasts,
+};
+use snake_game::core::{PuzzleRecords, Settings};
+
+/// Result screen of a puzzle board; `moves` is set when it was cleared.
+pub struct PuzzleOverTui {
+    board_name: String,
+    moves: Option<u16>,
+    best: Option<u16>,
+}
+
+impl PuzzleOverTui {
+    pub fn new(board_name: String, moves: Option<u16>) -> Self {
+        let mut records = PuzzleRecords::new();
+        if let Some(moves) = moves {
+            if records.add(board_name.clone(), moves) {
+                SoundPlayer::new(&Settings::new()).play(Sound::NewRecord);
+            }
+        }
+
+        Self {
+            best: records.get(&board_name),
+            board_name,
+            moves,
+        }
+    }
+
+    pub async fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        terminal.draw(|frame| self.draw(frame))?;
+
+        sleep(Duration::from_millis(3000)).await;
+
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+}
+
+impl Widget for &PuzzleOverTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match self.moves {
+            Some(_) => Line::from(t("puzzle_over.cleared")),
+            None => Line::from(t("puzzle_over.failed")),
+        };
+
+        let result = match self.moves {
+            Some(moves) => tf(
+                "puzzle_over.cleared_in",
+                &[("board", &self.board_name), ("moves", &moves)],
+            ),
+            None => tf("puzzle_over.not_cleared", &[("board", &self.board_name)]),
+        };
+        let best = match (self.moves, self.best) {
+            (Some(moves), Some(best)) if moves == best => t("puzzle_over.best"),
+            (_, Some(best)) => tf("puzzle_over.best_is", &[("moves", &best)]),
+            (_, None) => t("puzzle_over.unbeaten"),
+        };
+
+        Paragraph::new(format!("{result}\n{best}"))
+            .block(
+                Block::new()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .title(title.centered()),
+            )
+            .centered()
+            .render(area, buf);
+    }
+}
@@ -1,26 +1,39 @@
 use std::io;
 
-use crate::core::Scoreboard;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
+use super::{
+    events,
+    i18n::{t, tf},
+    sound::{Sound, SoundPlayer},
+    toast::Toasts,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Modifier, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     symbols::border,
-    text::Line,
+    text::{Line, Text},
     widgets::{
         Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
         Widget,
     },
     DefaultTerminal, Frame,
 };
+use snake_game::core::{Mode, ScoreCategory, Scoreboard, Settings, STREAK_SCORE};
+
+/// Scores best first, each with the name of the snake that made it.
+type NamedScores = Vec<(u16, Option<String>)>;
 
 pub struct ScoreboardTui {
     scoreboard: Scoreboard,
     exit: bool,
     state: ListState,
     board_names: Vec<String>,
+    /// Which of the selected board's categories is shown.
+    category: usize,
+    sound: SoundPlayer,
+    /// A board record just set, marked in its list.
+    new_record: Option<(ScoreCategory, u16)>,
 }
 
 impl Default for ScoreboardTui {
@@ -41,8 +54,29 @@ impl ScoreboardTui {
             state,
             scoreboard,
             board_names,
+            category: 0,
             exit: false,
+            sound: SoundPlayer::new(&Settings::new()),
+            new_record: None,
+        }
+    }
+
+    /// Starts on the scores of `board_name`, when it has any.
+    pub fn with_board(mut self, board_name: &str) -> Self {
+        if let Some(index) = self.board_names.iter().position(|name| name == board_name) {
+            self.state.select(Some(index));
         }
+        self
+    }
+
+    /// Shows `score` among the scores of `category` on the selected board, marked as the
+    /// record it just became.
+    pub fn with_new_record(mut self, category: ScoreCategory, score: u16) -> Self {
+        if let Some(index) = self.categories().iter().position(|c| *c == category) {
+            self.category = index;
+            self.new_record = Some((category, score));
+        }
+        self
     }
 
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
@@ -55,20 +89,23 @@ impl ScoreboardTui {
 
     fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
     }
 
     fn key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
-            KeyCode::Char('q') => self.exit = true,
+            KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => self.pivot(true),
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::BackTab => self.pivot(false),
             _ => {}
         }
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
                 self.key_event(key_event)
             }
             _ => {}
@@ -78,34 +115,59 @@ impl ScoreboardTui {
 
     fn select_next(&mut self) {
         self.state.select_next();
+        self.category = 0;
+        self.sound.play(Sound::MenuMove);
     }
 
     fn select_previous(&mut self) {
         self.state.select_previous();
+        self.category = 0;
+        self.sound.play(Sound::MenuMove);
     }
 
-    fn selected_board(&self) -> Vec<u16> {
-        let index = self.state.selected().unwrap();
-        let board_name = &self.board_names[index];
-        self.scoreboard.get(board_name).unwrap().clone()
+    /// Moves to the next or previous category of the selected board, wrapping around.
+    fn pivot(&mut self, forward: bool) {
+        let count = self.categories().len();
+        if count > 1 {
+            self.category = match forward {
+                true => (self.category + 1) % count,
+                false => (self.category + count - 1) % count,
+            };
+            self.sound.play(Sound::MenuMove);
+        }
+    }
+
+    fn categories(&self) -> Vec<ScoreCategory> {
+        self.state
+            .selected()
+            .and_then(|index| self.board_names.get(index))
+            .map(|name| self.scoreboard.get_categories(name))
+            .unwrap_or_default()
+    }
+
+    /// The shown category and its scores, best first.
+    fn selected_board(&self) -> Option<(ScoreCategory, NamedScores)> {
+        let board_name = self.board_names.get(self.state.selected()?)?;
+        let category = *self.categories().get(self.category)?;
+        Some((category, self.scoreboard.get_named(board_name, category)?))
     }
 
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Scoreboard")
+        Paragraph::new(t("scoreboard.title"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
     fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, q/Q to back.")
+        Paragraph::new(t("scoreboard.footer"))
             .centered()
             .render(area, buf);
     }
 
     fn render_list_of_name(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
-            .title(Line::raw(" Board Names ").centered())
+            .title(Line::raw(t("scoreboard.list")).centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
@@ -124,16 +186,62 @@ impl ScoreboardTui {
         StatefulWidget::render(list, area, buf, &mut self.state);
     }
 
+    /// The hardcore streak of the selected board, shown above its hardcore scores.
+    fn streak_lines(&self) -> Vec<Line<'static>> {
+        let Some(board_name) = self
+            .state
+            .selected()
+            .and_then(|index| self.board_names.get(index))
+        else {
+            return Vec::new();
+        };
+        let streak = self.scoreboard.get_streak(board_name);
+        vec![
+            Line::from(tf(
+                "scoreboard.streak",
+                &[
+                    ("current", &streak.get_current()),
+                    ("best", &streak.get_best()),
+                ],
+            ))
+            .bold(),
+            Line::from(tf("scoreboard.streak_rule", &[("score", &STREAK_SCORE)])).italic(),
+            Line::default(),
+        ]
+    }
+
     fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
-        let selected_scoreboard = self
-            .selected_board()
-            .iter()
-            .enumerate()
-            .map(|(index, score)| format!("{}: {}", index, score))
-            .join("\n");
+        let (title, selected_scoreboard) = match self.selected_board() {
+            Some((category, scores)) => {
+                let mut lines = match category.get_mode() {
+                    Mode::Hardcore => self.streak_lines(),
+                    _ => Vec::new(),
+                };
+                let mut marked = false;
+                lines.extend(scores.iter().enumerate().map(|(index, (score, name))| {
+                    let mut entry = format!("{}: {}", index, category.format_score(*score));
+                    if let Some(name) = name {
+                        entry += &format!(" {name}");
+                    }
+                    if !marked && self.new_record == Some((category, *score)) {
+                        marked = true;
+                        return Line::from(format!("★ {entry} ★")).bold().fg(Color::Yellow);
+                    }
+                    Line::from(entry)
+                }));
+                let title = format!(
+                    " {} ({}/{}) ",
+                    category.get_name(),
+                    self.category + 1,
+                    self.categories().len()
+                );
+                (title, Text::from(lines))
+            }
+            None => (t("scoreboard.selected"), Text::default()),
+        };
 
         let block = Block::new()
-            .title(Line::raw(" Selected Board ").centered())
+            .title(Line::raw(title).centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
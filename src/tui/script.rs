@@ -0,0 +1,239 @@
+//! Plays a recorded key sequence into the game instead of the keyboard, for end-to-end
+//! tests. A script is a text file of `<tick> <key>` lines; `seed <n>` makes the food fall
+//! the same way every run, `<tick> end` stops the game (a tick after the last key by
+//! default) and `#` starts a comment. A tick passes each time the game waits for input:
+//! once per step of the snake, and once per frame a menu sits idle, so a script plays the
+//! same way however fast the machine is. When the script is over, the game state at the
+//! end of the last game played is written out as JSON.
+
+use std::{
+    collections::VecDeque,
+    fs, io,
+    sync::{Mutex, OnceLock},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use snake_game::core::Game;
+
+const END_MESSAGE: &str = "the input script is over";
+
+/// The seed of the script being played, set once it starts.
+static SEED: OnceLock<Option<u64>> = OnceLock::new();
+/// The state of the game played last, as JSON.
+static LAST_STATE: Mutex<Option<String>> = Mutex::new(None);
+
+pub struct Script {
+    seed: Option<u64>,
+    /// The keys by the tick they are pressed at, in order.
+    keys: VecDeque<(u32, KeyEvent)>,
+    end: u32,
+    /// The ticks passed so far.
+    tick: u32,
+}
+
+impl Script {
+    pub fn load(path: &str) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut script = Self {
+            seed: None,
+            keys: VecDeque::new(),
+            end: 0,
+            tick: 0,
+        };
+        let mut end = None;
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fail = |what: &str| format!("line {}: {what}", number + 1);
+            let (first, rest) = line.split_once(' ').ok_or_else(|| fail("no key"))?;
+            let rest = rest.trim();
+            if first == "seed" {
+                script.seed = Some(rest.parse().map_err(|_| fail("bad seed"))?);
+                continue;
+            }
+            let tick: u32 = first.parse().map_err(|_| fail("bad tick"))?;
+            if script.keys.back().is_some_and(|(last, _)| tick < *last) {
+                return Err(fail("ticks must not go back"));
+            }
+            if rest == "end" {
+                end = Some(tick);
+                break;
+            }
+            let key = parse_key(rest).ok_or_else(|| fail("unknown key"))?;
+            script.keys.push_back((tick, key));
+        }
+        let last = script.keys.back().map_or(0, |(tick, _)| *tick);
+        script.end = end.unwrap_or(last + 1).max(last);
+        Ok(script)
+    }
+
+    /// Makes games start with the seed of the script.
+    pub(super) fn start(&self) {
+        SEED.get_or_init(|| self.seed);
+    }
+
+    /// The key pressed at the current tick, if any. When there is none and `wait` is set,
+    /// the caller would wait for input, so a tick passes first. An error ends the game
+    /// once the script is over.
+    pub(super) fn next_key(&mut self, wait: bool) -> io::Result<Option<KeyEvent>> {
+        if let Some(key) = self.take_due() {
+            return Ok(Some(key));
+        }
+        if self.tick >= self.end {
+            return Err(io::Error::other(END_MESSAGE));
+        }
+        if !wait {
+            return Ok(None);
+        }
+        self.tick += 1;
+        Ok(self.take_due())
+    }
+
+    fn take_due(&mut self) -> Option<KeyEvent> {
+        let (tick, _) = self.keys.front()?;
+        if *tick > self.tick {
+            return None;
+        }
+        self.keys.pop_front().map(|(_, key)| key)
+    }
+}
+
+/// A character, or a key by name such as `enter`, `esc`, `up` or `f2`, after any `ctrl+`.
+fn parse_key(text: &str) -> Option<KeyEvent> {
+    let (modifiers, name) = match text.strip_prefix("ctrl+") {
+        Some(name) => (KeyModifiers::CONTROL, name),
+        None => (KeyModifiers::NONE, text),
+    };
+    let code = match name {
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ if name.len() > 1 && name.starts_with('f') => KeyCode::F(name[1..].parse().ok()?),
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// The seed games are started with while a script plays, `None` otherwise.
+pub fn seed() -> Option<u64> {
+    SEED.get().copied().flatten()
+}
+
+/// Keeps the state of `game` to write out when the script is over.
+pub fn record(game: &Game) {
+    if SEED.get().is_none() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(&game.snapshot()) {
+        *LAST_STATE.lock().unwrap() = Some(json);
+    }
+}
+
+/// Whether `error` is the end of the script rather than a failure.
+pub fn is_end(error: &io::Error) -> bool {
+    error.to_string() == END_MESSAGE
+}
+
+/// The state of the last game played, `null` when there was none.
+pub fn take_state() -> String {
+    LAST_STATE
+        .lock()
+        .unwrap()
+        .take()
+        .unwrap_or_else(|| "null".to_string())
+}
+
+#[cfg(test)]
+mod test_script {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{is_end, parse_key, Script};
+
+    #[test]
+    fn parses_keys_by_name() {
+        let key = |code| Some(KeyEvent::new(code, KeyModifiers::NONE));
+        assert_eq!(parse_key("enter"), key(KeyCode::Enter));
+        assert_eq!(parse_key("space"), key(KeyCode::Char(' ')));
+        assert_eq!(parse_key("f12"), key(KeyCode::F(12)));
+        assert_eq!(parse_key("q"), key(KeyCode::Char('q')));
+        assert_eq!(
+            parse_key("ctrl+c"),
+            Some(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(parse_key("fx"), None);
+        assert_eq!(parse_key("qq"), None);
+        assert_eq!(parse_key(""), None);
+    }
+
+    #[test]
+    fn parses_scripts() {
+        let script = Script::parse("# a comment\nseed 7\n4 enter\n\n8 up  # turn\n").unwrap();
+        assert_eq!(script.seed, Some(7));
+        assert_eq!(
+            script
+                .keys
+                .iter()
+                .map(|(tick, _)| *tick)
+                .collect::<Vec<_>>(),
+            [4, 8]
+        );
+        assert_eq!(script.end, 9);
+
+        let script = Script::parse("2 a\n5 end\n9 b").unwrap();
+        assert_eq!(script.keys.len(), 1);
+        assert_eq!(script.end, 5);
+
+        assert_eq!(
+            Script::parse("3 a\n2 b").err().unwrap(),
+            "line 2: ticks must not go back"
+        );
+        assert_eq!(Script::parse("x a").err().unwrap(), "line 1: bad tick");
+        assert_eq!(
+            Script::parse("1 nope").err().unwrap(),
+            "line 1: unknown key"
+        );
+        assert_eq!(Script::parse("seed x").err().unwrap(), "line 1: bad seed");
+        assert_eq!(Script::parse("4").err().unwrap(), "line 1: no key");
+    }
+
+    #[test]
+    fn keys_come_on_the_ticks_waited_for() {
+        let mut script = Script::parse("0 a\n2 b\n2 c\n").unwrap();
+        let code = |key: Option<KeyEvent>| key.map(|key| key.code);
+        assert_eq!(
+            code(script.next_key(false).unwrap()),
+            Some(KeyCode::Char('a'))
+        );
+        assert_eq!(code(script.next_key(false).unwrap()), None);
+        assert_eq!(code(script.next_key(true).unwrap()), None);
+        assert_eq!(
+            code(script.next_key(true).unwrap()),
+            Some(KeyCode::Char('b'))
+        );
+        assert_eq!(
+            code(script.next_key(false).unwrap()),
+            Some(KeyCode::Char('c'))
+        );
+        assert_eq!(code(script.next_key(true).unwrap()), None);
+        assert!(is_end(&script.next_key(true).unwrap_err()));
+    }
+}
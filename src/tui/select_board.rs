@@ -1,26 +1,76 @@
-use crate::core::{Board, Boards};
+use super::{
+    events, heatmap,
+    i18n::{t, tf},
+    modal::{Modal, ModalResult},
+    sound::{Sound, SoundPlayer},
+    theme::{self, BoardFrame, Palette},
+    toast::{self, Toasts},
+};
+use snake_game::core::{
+    death_heatmap, read_games, steer, Board, Boards, CampaignLevel, CampaignProgress, Cell,
+    CellKind, Difficulty, Game, GameRecord, GameSnapshot, Mode, Objective, RecentBoards,
+    ScoreCategory, Scoreboard, Settings, MAX_DIFFICULTY, MAX_RATING,
+};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 use itertools::Itertools;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style, Stylize},
     symbols::border,
-    text::Line,
+    text::{Line, Span, Text},
     widgets::{
         Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
         Widget,
     },
     DefaultTerminal, Frame,
 };
-use std::io;
+use std::{
+    io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// The time between two steps of the snake wandering the preview.
+const DEMO_TICK: Duration = Duration::from_millis(150);
 
 pub enum SelectBoardTuiResult {
     Board(Board),
     Exit,
     CreateBoard,
+    EditBoard(Board),
+    ImportBoards,
     ScoreBoards,
+    BoardScores(String),
+    /// Where the games on the board ended.
+    DeathHeatmap(Board),
+    Settings,
+}
+
+/// How many of the last played boards are listed above the rest.
+const RECENT_BOARDS: usize = 3;
+
+/// A line of the board list: a group title or the board at an index of `Boards`.
+enum Row {
+    Header(&'static str),
+    Board(usize),
+}
+
+/// The order the boards are listed in.
+#[derive(Clone, Copy, PartialEq)]
+enum Order {
+    Saved,
+    BestRated,
+    Easiest,
+    Hardest,
+}
+
+/// What answering the open modal leads to.
+enum Pending {
+    Alert,
+    Delete(usize),
+    Overwrite(String),
 }
 
 pub struct SelectBoardTui {
@@ -28,9 +78,37 @@ pub struct SelectBoardTui {
     selected: bool,
     create_board: bool,
     show_scoreboards: bool,
+    show_settings: bool,
+    edit_board: bool,
+    show_board_scores: bool,
+    show_death_heatmap: bool,
+    import_boards: bool,
+    /// Colors the cells of the preview where games ended.
+    heatmap_overlay: bool,
+    /// Shows the actions on the highlighted board instead of the usual keys.
+    manage: bool,
+    modal: Option<(Modal, Pending)>,
     boards: Boards,
+    scoreboard: Scoreboard,
+    /// The games of the stats log.
+    games: Vec<GameRecord>,
+    /// The scores shown are the ones earned with the current settings.
+    category: ScoreCategory,
     board_names: Vec<String>,
+    order: Order,
+    /// The difficulty of each board in stars, measured when the list is rebuilt.
+    difficulties: Vec<u8>,
+    /// What each board of a pack's unlock order still needs, `None` once it is unlocked.
+    locks: Vec<Option<String>>,
+    /// Only boards rated this many stars or more are listed, all of them for 0.
+    min_rating: u8,
+    rows: Vec<Row>,
     state: ListState,
+    sound: SoundPlayer,
+    frame: BoardFrame,
+    /// Draws the snake wandering the preview.
+    palette: Palette,
+    demo: Option<DemoSnake>,
 }
 
 impl Default for SelectBoardTui {
@@ -44,22 +122,82 @@ impl SelectBoardTui {
         let boards = Boards::new();
         let board_names = boards.get_names();
 
-        let mut state = ListState::default();
-        state.select_first();
+        let quarantined = Boards::take_quarantined();
+        let modal = (!quarantined.is_empty()).then(|| {
+            let message = tf(
+                "select_board.quarantined",
+                &[
+                    ("boards", &quarantined.join(", ")),
+                    ("path", &Boards::get_quarantine_path()),
+                ],
+            );
+            (
+                Modal::alert(&t("select_board.quarantined_title"), &message),
+                Pending::Alert,
+            )
+        });
 
-        Self {
+        let settings = Settings::new();
+        let category = ScoreCategory::new(
+            Mode::Classic,
+            settings.get_apples(),
+            settings.get_shifting_walls(),
+        )
+        .with_moving_food(settings.is_moving_food_enabled())
+        .with_mice(settings.is_mice_enabled())
+        .with_roaming_snakes(settings.is_roaming_snakes_enabled());
+
+        let mut select_board = Self {
             exit: false,
             selected: false,
             create_board: false,
             show_scoreboards: false,
-            state,
+            show_settings: false,
+            edit_board: false,
+            show_board_scores: false,
+            show_death_heatmap: false,
+            import_boards: false,
+            heatmap_overlay: false,
+            manage: false,
+            modal,
+            state: ListState::default(),
             boards,
+            scoreboard: Scoreboard::new(),
+            games: read_games().unwrap_or_default(),
+            category,
             board_names,
-        }
+            order: Order::Saved,
+            difficulties: Vec::new(),
+            locks: Vec::new(),
+            min_rating: 0,
+            rows: Vec::new(),
+            sound: SoundPlayer::new(&settings),
+            frame: BoardFrame::new(&settings),
+            palette: Palette::new(&settings),
+            demo: None,
+        };
+        select_board.reload();
+        select_board
+    }
+
+    /// Shows the scores of `mode` instead of the classic ones.
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.category = self.category.with_mode(mode);
+        self
     }
 
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<SelectBoardTuiResult> {
-        while !(self.exit || self.selected || self.create_board || self.show_scoreboards) {
+        while !(self.exit
+            || self.selected
+            || self.create_board
+            || self.show_scoreboards
+            || self.show_settings
+            || self.edit_board
+            || self.show_board_scores
+            || self.show_death_heatmap
+            || self.import_boards)
+        {
+            self.step_demo();
             terminal.draw(|frame| self.draw(frame))?;
 
             self.handle_events()?;
@@ -71,6 +209,16 @@ impl SelectBoardTui {
             SelectBoardTuiResult::CreateBoard
         } else if self.show_scoreboards {
             SelectBoardTuiResult::ScoreBoards
+        } else if self.show_settings {
+            SelectBoardTuiResult::Settings
+        } else if self.edit_board {
+            SelectBoardTuiResult::EditBoard(self.selected_board())
+        } else if self.import_boards {
+            SelectBoardTuiResult::ImportBoards
+        } else if self.show_board_scores {
+            SelectBoardTuiResult::BoardScores(self.selected_board().get_name().to_string())
+        } else if self.show_death_heatmap {
+            SelectBoardTuiResult::DeathHeatmap(self.selected_board())
         } else {
             SelectBoardTuiResult::Board(self.selected_board())
         };
@@ -80,67 +228,434 @@ impl SelectBoardTui {
 
     fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
     }
 
     fn key_event(&mut self, key_event: KeyEvent) {
+        if self.manage {
+            return self.key_event_manage(key_event);
+        }
+
         match key_event.code {
-            KeyCode::Enter => self.selected = true,
-            KeyCode::Char('q') => self.exit = true,
+            KeyCode::Enter => self.choose(),
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.exit = true,
             KeyCode::Char('c') => self.create_board = true,
             KeyCode::Char('s') => self.show_scoreboards = true,
+            KeyCode::Char('o') => self.show_settings = true,
+            KeyCode::Char('m') => self.manage = true,
+            KeyCode::Char('r') => self.toggle_rating_order(),
+            KeyCode::Char('d') => self.next_difficulty_order(),
+            KeyCode::Char('f') => self.next_rating_filter(),
+            KeyCode::Char('h') => self.heatmap_overlay = !self.heatmap_overlay,
+            KeyCode::Char(c @ '0'..='5') => self.rate(c as u8 - b'0'),
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
             _ => {}
         }
     }
 
+    fn selected_index(&self) -> Option<usize> {
+        match self.rows.get(self.state.selected()?)? {
+            Row::Board(index) => Some(*index),
+            Row::Header(_) => None,
+        }
+    }
+
+    /// Picks the selected board, unless it is locked.
+    fn choose(&mut self) {
+        let lock = self
+            .selected_index()
+            .and_then(|index| self.locks.get(index).cloned().flatten());
+        match lock {
+            Some(requirement) => {
+                let modal = Modal::alert(&t("select_board.locked_title"), &requirement);
+                self.modal = Some((modal, Pending::Alert));
+            }
+            None => self.selected = true,
+        }
+    }
+
+    /// Gives the selected board `stars`, 0 taking its rating away.
+    fn rate(&mut self, stars: u8) {
+        let Some(index) = self.selected_index() else {
+            return;
+        };
+        match self.boards.set_rating(index, (stars > 0).then_some(stars)) {
+            Ok(()) => {
+                self.sound.play(Sound::MenuMove);
+                self.reload_keeping(index);
+            }
+            Err(e) => self.alert(&e.to_string()),
+        }
+    }
+
+    fn toggle_rating_order(&mut self) {
+        self.order = match self.order {
+            Order::BestRated => Order::Saved,
+            _ => Order::BestRated,
+        };
+        self.reorder();
+    }
+
+    /// Lists the easiest boards first, then the hardest, then back in their saved order.
+    fn next_difficulty_order(&mut self) {
+        self.order = match self.order {
+            Order::Easiest => Order::Hardest,
+            Order::Hardest => Order::Saved,
+            _ => Order::Easiest,
+        };
+        self.reorder();
+    }
+
+    fn reorder(&mut self) {
+        match self.selected_index() {
+            Some(index) => self.reload_keeping(index),
+            None => self.reload(),
+        }
+    }
+
+    /// Raises the least rating listed a star at a time, skipping ratings no board has, and
+    /// back to listing every board after the most stars.
+    fn next_rating_filter(&mut self) {
+        let selected = self.selected_index();
+        self.min_rating = (self.min_rating + 1..=MAX_RATING)
+            .find(|stars| !self.boards.get_rated(*stars, false).is_empty())
+            .unwrap_or(0);
+        match selected {
+            Some(index) => self.reload_keeping(index),
+            None => self.reload(),
+        }
+    }
+
+    /// Rebuilds the list and selects the board at `index` again when it is still listed.
+    fn reload_keeping(&mut self, index: usize) {
+        self.reload();
+        let row = self
+            .rows
+            .iter()
+            .rposition(|row| matches!(row, Row::Board(i) if *i == index));
+        if row.is_some() {
+            self.state.select(row);
+        }
+    }
+
+    fn key_event_manage(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('e') => self.edit_board = true,
+            KeyCode::Char('d') => self.duplicate(),
+            KeyCode::Char('x') => self.export("json"),
+            KeyCode::Char('p') => self.export("png"),
+            KeyCode::Char('i') => self.import_boards = true,
+            KeyCode::Delete | KeyCode::Backspace => self.ask_delete(),
+            KeyCode::Char('v') => self.show_board_scores = true,
+            KeyCode::Char('h') => self.show_death_heatmap = true,
+            KeyCode::Char('m') | KeyCode::Esc => self.manage = false,
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
+            _ => {}
+        }
+    }
+
+    fn duplicate(&mut self) {
+        let Some(Row::Board(index)) = self.state.selected().map(|row| &self.rows[row]) else {
+            return;
+        };
+
+        match self.boards.duplicate(*index) {
+            Ok(name) => {
+                self.reload();
+                toast::push(tf("select_board.duplicated", &[("name", &name)]));
+            }
+            Err(e) => self.alert(&e.to_string()),
+        }
+    }
+
+    fn ask_delete(&mut self) {
+        let Some(Row::Board(index)) = self.state.selected().map(|row| &self.rows[row]) else {
+            return;
+        };
+        if self.board_names.len() == 1 {
+            return self.alert(&t("select_board.last_board"));
+        }
+
+        let question = tf(
+            "select_board.delete_question",
+            &[("name", &self.board_names[*index])],
+        );
+        self.modal = Some((
+            Modal::confirm(&t("select_board.delete_title"), &question),
+            Pending::Delete(*index),
+        ));
+    }
+
+    fn delete(&mut self, index: usize) {
+        if let Some(board) = self.boards.remove(index) {
+            self.reload();
+            toast::push(tf("select_board.deleted", &[("name", &board.get_name())]));
+        }
+    }
+
+    /// Rebuilds the list after boards were added, removed or rated, keeping a board
+    /// selected. A rating filter no board passes any more is dropped.
+    fn reload(&mut self) {
+        self.board_names = self.boards.get_names();
+        self.difficulties = (0..self.board_names.len())
+            .filter_map(|index| self.boards.get(index).map(Difficulty::get_stars))
+            .collect();
+        self.locks = self.locks();
+        self.rows = self.rows();
+        if !self.rows.iter().any(|row| matches!(row, Row::Board(_))) {
+            self.min_rating = 0;
+            self.rows = self.rows();
+        }
+        if !matches!(
+            self.state.selected().and_then(|row| self.rows.get(row)),
+            Some(Row::Board(_))
+        ) {
+            self.state.select(
+                self.rows
+                    .iter()
+                    .position(|row| matches!(row, Row::Board(_))),
+            );
+        }
+    }
+
+    fn alert(&mut self, message: &str) {
+        self.modal = Some((
+            Modal::alert(&t("select_board.error"), message),
+            Pending::Alert,
+        ));
+    }
+
+    fn modal_answered(&mut self, pending: Pending, result: ModalResult) {
+        match (pending, result) {
+            (Pending::Delete(index), ModalResult::Confirmed) => self.delete(index),
+            (Pending::Overwrite(path), ModalResult::Confirmed) => self.save_export(&path),
+            _ => {}
+        }
+    }
+
+    /// Saves the selected board next to the game as JSON, or as a picture for `png`.
+    fn export(&mut self, extension: &str) {
+        let board = self.selected_board();
+        let name: String = board
+            .get_name()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        let path = format!("board-{name}.{extension}");
+
+        if Path::new(&path).exists() {
+            let question = tf("select_board.overwrite_question", &[("path", &path)]);
+            self.modal = Some((
+                Modal::confirm(&t("select_board.export_title"), &question),
+                Pending::Overwrite(path),
+            ));
+        } else {
+            self.save_export(&path);
+        }
+    }
+
+    fn save_export(&mut self, path: &str) {
+        let board = self.selected_board();
+        let saved = match path.ends_with(".png") {
+            true => board.save_png(path),
+            false => board.save(path),
+        };
+        match saved {
+            Ok(_) => toast::push(tf("select_board.exported", &[("path", &path)])),
+            Err(e) => self.alert(&e.to_string()),
+        }
+    }
+
     fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.key_event(key_event)
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                match &mut self.modal {
+                    Some((modal, _)) => {
+                        if let Some(result) = modal.key_event(key_event) {
+                            let (_, pending) = self.modal.take().unwrap();
+                            self.modal_answered(pending, result);
+                        }
+                    }
+                    None => self.key_event(key_event),
+                }
             }
             _ => {}
         };
         Ok(())
     }
 
+    /// For each board, what unlocks it when it is a locked level of a pack.
+    fn locks(&self) -> Vec<Option<String>> {
+        let progress = CampaignProgress::new();
+        let levels: Vec<Option<&CampaignLevel>> = (0..self.board_names.len())
+            .map(|index| self.boards.get(index).and_then(Board::get_campaign))
+            .collect();
+        levels
+            .iter()
+            .map(|level| {
+                let level = level.filter(|level| !progress.is_unlocked(level))?;
+                let before = levels
+                    .iter()
+                    .position(|other| other.is_some_and(|other| level.is_after(other)));
+                Some(match before {
+                    Some(index) => tf(
+                        "select_board.locked",
+                        &[
+                            ("score", &levels[index].unwrap().get_clear_score()),
+                            ("board", &self.board_names[index]),
+                        ],
+                    ),
+                    None => tf(
+                        "select_board.locked_level",
+                        &[
+                            ("score", &level.get_clear_score()),
+                            ("level", &(level.get_level() - 1)),
+                            ("pack", &level.get_pack()),
+                        ],
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// The recently played boards first when there are any, then every board. Sorted, or
+    /// filtered by rating, only the boards that make it are listed.
+    fn rows(&self) -> Vec<Row> {
+        let mut indices = self
+            .boards
+            .get_rated(self.min_rating, self.order == Order::BestRated);
+        match self.order {
+            Order::Easiest => self.boards.sort_by_difficulty(&mut indices, false),
+            Order::Hardest => self.boards.sort_by_difficulty(&mut indices, true),
+            Order::Saved | Order::BestRated => {}
+        }
+        let all = indices.into_iter().map(Row::Board);
+        if self.order != Order::Saved || self.min_rating > 0 {
+            return all.collect();
+        }
+
+        let recent: Vec<usize> = RecentBoards::new()
+            .get_recent(RECENT_BOARDS)
+            .iter()
+            .filter_map(|name| self.board_names.iter().position(|board| board == name))
+            .collect();
+        if recent.is_empty() {
+            return all.collect();
+        }
+
+        let mut rows = vec![Row::Header("select_board.recent")];
+        rows.extend(recent.into_iter().map(Row::Board));
+        rows.push(Row::Header("select_board.all"));
+        rows.extend(all);
+        rows
+    }
+
+    /// Moves the selection to the next board row in `rows`, skipping the group titles.
+    fn select_board_row(&mut self, rows: impl Iterator<Item = usize>) {
+        let next = rows
+            .into_iter()
+            .find(|row| matches!(self.rows[*row], Row::Board(_)));
+        if next.is_some() {
+            self.state.select(next);
+            self.sound.play(Sound::MenuMove);
+        }
+    }
+
     fn select_next(&mut self) {
-        self.state.select_next();
+        let selected = self.state.selected().unwrap_or(0);
+        self.select_board_row(selected + 1..self.rows.len());
     }
 
     fn select_previous(&mut self) {
-        self.state.select_previous();
+        let selected = self.state.selected().unwrap_or(0);
+        self.select_board_row((0..selected).rev());
+    }
+
+    /// Moves the snake in the preview on once a `DEMO_TICK` has passed, starting it over on
+    /// another board when the selection changed.
+    fn step_demo(&mut self) {
+        if self.state.selected().is_none() || self.rows.is_empty() {
+            self.demo = None;
+            return;
+        }
+        let board = self.selected_board();
+        match &mut self.demo {
+            Some(demo) if demo.board.get_name() == board.get_name() => demo.step(),
+            _ => self.demo = Some(DemoSnake::new(board)),
+        }
     }
 
     fn selected_board(&self) -> Board {
-        let index = self.state.selected().unwrap();
-        let border = self.boards.get(index).unwrap().clone();
-        border
+        let index = match self.rows[self.state.selected().unwrap()] {
+            Row::Board(index) => index,
+            Row::Header(_) => unreachable!("group titles are never selected"),
+        };
+        self.boards.get(index).unwrap().clone()
     }
 
     fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Select Board")
+        Paragraph::new(t("select_board.title"))
             .bold()
             .centered()
             .render(area, buf);
     }
 
-    fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, c/C to go create board, s/S to go show scoreboards, ⮡ to go play selected board, q/Q to quit game.")
-            .centered()
-            .render(area, buf);
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let keys = if self.manage {
+            t("select_board.manage_footer")
+        } else {
+            t("select_board.footer")
+        };
+        Paragraph::new(keys).centered().render(area, buf);
     }
 
     fn render_list_of_name(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut title = if self.manage {
+            t("select_board.manage_list")
+        } else {
+            t("select_board.list")
+        };
+        if self.min_rating > 0 {
+            title += &tf("select_board.rated", &[("stars", &self.min_rating)]);
+        }
+        match self.order {
+            Order::Saved => {}
+            Order::BestRated => title += &t("select_board.best_rated_first"),
+            Order::Easiest => title += &t("select_board.easiest_first"),
+            Order::Hardest => title += &t("select_board.hardest_first"),
+        }
+        let by_difficulty = matches!(self.order, Order::Easiest | Order::Hardest);
         let block = Block::new()
-            .title(Line::raw(" Board Names ").centered())
+            .title(Line::raw(title).centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
         let items: Vec<_> = self
-            .board_names
+            .rows
             .iter()
-            .map(|todo_item| ListItem::from(todo_item.to_string()))
+            .map(|row| match row {
+                Row::Header(key) => ListItem::from(Line::from(t(key)).italic().dim()),
+                Row::Board(index) => {
+                    let name = &self.board_names[*index];
+                    let mut spans = vec![Span::raw(name.to_string())];
+                    if by_difficulty {
+                        let difficulty = self.difficulties.get(*index).copied().unwrap_or(0);
+                        spans.push(Span::raw(format!(" {}", difficulty_stars(difficulty))).red());
+                    }
+                    if let Some(rating) = self.boards.get(*index).and_then(Board::get_rating) {
+                        spans.push(Span::raw(format!(" {}", stars(rating))).yellow());
+                    }
+                    match self.locks.get(*index).cloned().flatten() {
+                        Some(requirement) => {
+                            spans.push(Span::raw(format!(" ({requirement})")).italic());
+                            ListItem::from(Line::from(spans).dim())
+                        }
+                        None => ListItem::from(Line::from(spans)),
+                    }
+                }
+            })
             .collect();
 
         let list = List::new(items)
@@ -152,23 +667,110 @@ impl SelectBoardTui {
         StatefulWidget::render(list, area, buf, &mut self.state);
     }
 
-    fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
-        let selected_board = self
-            .selected_board()
-            .get_table()
+    fn render_stats(&self, area: Rect, buf: &mut Buffer) {
+        let board = self.selected_board();
+        let name = board.get_name();
+        let category = match board.get_rules().get_apples() {
+            Some(apples) => self.category.with_apples(apples),
+            None => self.category,
+        }
+        .with_difficulty(Some(Difficulty::estimate(&board)));
+        let best = match self.scoreboard.get_best(name, category) {
+            Some(best) => category.format_score(best),
+            None => "-".to_string(),
+        };
+
+        let deaths = self
+            .games
             .iter()
-            .map(|row| row.join(""))
-            .join("\n");
+            .filter(|game| game.get_board() == name)
+            .filter_map(|game| game.get_death())
+            .counts();
+        let most_deaths = match deaths.iter().max_by_key(|(_, count)| **count) {
+            Some((cause, count)) => tf(
+                "select_board.most_deaths",
+                &[
+                    ("cause", &cause.get_name()),
+                    ("count", count),
+                    ("games", &deaths.values().sum::<usize>()),
+                ],
+            ),
+            None => "-".to_string(),
+        };
+
+        let lines = vec![
+            Line::from(tf("select_board.size", &[("size", &board.get_size())])),
+            Line::from(tf(
+                "select_board.walls",
+                &[
+                    ("walls", &board.get_walls().len()),
+                    (
+                        "density",
+                        &format!("{:.1}", board.get_wall_density() * 100.0),
+                    ),
+                ],
+            )),
+            Line::from(vec![
+                Span::raw(t("select_board.difficulty")),
+                Span::raw(difficulty_stars(Difficulty::get_stars(&board))).red(),
+                Span::raw(format!(" ({})", Difficulty::estimate(&board).get_name())),
+            ]),
+            Line::from(tf("select_board.best", &[("best", &best)])),
+            Line::from(tf(
+                "select_board.times_played",
+                &[("times", &self.scoreboard.get_times_played(name))],
+            )),
+            Line::from(tf("select_board.deaths", &[("deaths", &most_deaths)])),
+            Line::from(match board.get_rating() {
+                Some(rating) => tf("select_board.rating", &[("stars", &stars(rating))]),
+                None => t("select_board.unrated"),
+            }),
+        ];
 
         let block = Block::new()
-            .title(Line::raw(" Selected Board ").centered())
+            .title(Line::raw(t("select_board.stats")).centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
-        Paragraph::new(selected_board)
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
+        let board = self.selected_board();
+        let mut block = Block::new()
+            .title(Line::raw(t("select_board.selected")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        let mut rules = board.get_rules().describe();
+        rules.extend(board.get_objective().map(Objective::describe));
+        if !rules.is_empty() {
+            block = block.title_bottom(
+                Line::raw(tf("select_board.rules", &[("rules", &rules.join(", "))])).centered(),
+            );
+        }
+
+        if self.heatmap_overlay {
+            block = block.title_top(Line::raw(t("select_board.heatmap")).right_aligned());
+        }
+
+        let inner = block.inner(area);
+
+        let table_len = board.get_size() + 2;
+        let fits = table_len <= inner.width && table_len <= inner.height;
+        let text = match &self.demo {
+            Some(demo) if fits && demo.board.get_name() == board.get_name() => {
+                self.palette.text(&demo.cells)
+            }
+            _ => preview(&board, inner.width, inner.height, &self.frame),
+        };
+        Paragraph::new(text)
             .block(block)
             .alignment(Alignment::Center)
             .render(area, buf);
+        if self.heatmap_overlay {
+            let deaths = death_heatmap(&self.games, board.get_name());
+            heatmap::overlay(&board, &deaths, inner, buf);
+        }
     }
 }
 
@@ -186,8 +788,126 @@ impl Widget for &mut SelectBoardTui {
                 .areas(main_area);
 
         SelectBoardTui::render_header(header_area, buf);
-        SelectBoardTui::render_footer(footer_area, buf);
+        self.render_footer(footer_area, buf);
         self.render_list_of_name(list_area, buf);
-        self.render_selected_item(item_area, buf);
+        let [preview_area, stats_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(9)]).areas(item_area);
+        self.render_selected_item(preview_area, buf);
+        self.render_stats(stats_area, buf);
+        if let Some((modal, _)) = &self.modal {
+            modal.render(area, buf);
+        }
     }
 }
+
+/// A rating as filled and empty stars out of `MAX_RATING`.
+fn stars(rating: u8) -> String {
+    "★".repeat(rating as usize) + &"☆".repeat((MAX_RATING - rating) as usize)
+}
+
+fn difficulty_stars(difficulty: u8) -> String {
+    let difficulty = difficulty.min(MAX_DIFFICULTY);
+    "★".repeat(difficulty as usize) + &"☆".repeat((MAX_DIFFICULTY - difficulty) as usize)
+}
+
+/// A snake steered by the autopilot around the board in the preview, to show how it plays.
+/// It is kept as a snapshot, which owns its board, and started over when it dies.
+struct DemoSnake {
+    board: Board,
+    snapshot: GameSnapshot,
+    /// The cells of the snapshot, drawn until the next step.
+    cells: Vec<Vec<Cell>>,
+    stepped: Instant,
+}
+
+impl DemoSnake {
+    fn new(board: Board) -> Self {
+        let (snapshot, cells) = Self::start(&board);
+        Self {
+            board,
+            snapshot,
+            cells,
+            stepped: Instant::now(),
+        }
+    }
+
+    fn start(board: &Board) -> (GameSnapshot, Vec<Vec<Cell>>) {
+        let mut game = Game::with_seed(board, 3, rand::random());
+        if let Some(apples) = board.get_rules().get_apples() {
+            game.set_food_count(apples as usize);
+        }
+        (game.snapshot(), game.get_cells())
+    }
+
+    fn step(&mut self) {
+        if self.stepped.elapsed() < DEMO_TICK {
+            return;
+        }
+        self.stepped = Instant::now();
+        let mut game = self.snapshot.resume();
+        if let Some(direction) = steer(&game) {
+            game.rotation(direction);
+        }
+        (self.snapshot, self.cells) = match game.walk() {
+            true => (game.snapshot(), game.get_cells()),
+            false => Self::start(&self.board),
+        };
+    }
+}
+
+/// The board as it is drawn in a game, framed by `frame`, or downscaled when it does not fit
+/// `width`x`height`.
+pub fn preview(board: &Board, width: u16, height: u16, frame: &BoardFrame) -> Text<'static> {
+    let table_len = board.get_size() + 2;
+    if table_len > width || table_len > height {
+        return Text::from(downscale(&board.get_cells(), width, height));
+    }
+    let cells = board.get_cells();
+    theme::table(&cells, frame)
+        .into_iter()
+        .zip(&cells)
+        .map(|(row, cells)| {
+            row.into_iter()
+                .zip(cells)
+                .map(|(glyph, cell)| match cell.get_kind() {
+                    CellKind::Border => Span::styled(glyph, frame.style()),
+                    _ => Span::raw(glyph),
+                })
+                .collect::<Line>()
+        })
+        .collect()
+}
+
+/// Draws a board too big for `width`x`height` with half blocks, each half standing for a
+/// square of cells that is shown filled when any of them is not empty.
+fn downscale(cells: &[Vec<Cell>], width: u16, height: u16) -> String {
+    let len = cells.len();
+    let (width, height) = (width.max(1) as usize, height.max(1) as usize);
+    let scale = (1..=len)
+        .find(|scale| len.div_ceil(*scale) <= width && len.div_ceil(scale * 2) <= height)
+        .unwrap_or(len);
+
+    let filled = |row: usize, column: usize| {
+        cells
+            .iter()
+            .skip(row * scale)
+            .take(scale)
+            .flat_map(|line| line.iter().skip(column * scale).take(scale))
+            .any(|cell| cell.get_kind() != CellKind::Empty)
+    };
+
+    (0..len.div_ceil(scale * 2))
+        .map(|row| {
+            (0..len.div_ceil(scale))
+                .map(
+                    |column| match (filled(row * 2, column), filled(row * 2 + 1, column)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                )
+                .collect::<String>()
+        })
+        .join("\n")
+}
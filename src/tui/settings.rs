@@ -0,0 +1,530 @@
+use std::io;
+
+use super::{
+    events,
+    i18n::{self, t, tf},
+    kitty,
+    sound::{Sound, SoundPlayer},
+    text_input::TextInput,
+    theme,
+    toast::Toasts,
+};
+#[cfg(feature = "gamepad")]
+use crate::tui::gamepad;
+#[cfg(feature = "presence")]
+use crate::tui::presence;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget,
+    },
+    DefaultTerminal, Frame,
+};
+#[cfg(feature = "gamepad")]
+use snake_game::core::GamepadAction;
+use snake_game::core::{Appearance, Messages, Settings, MAX_SNAKE_NAME_LEN};
+
+#[derive(Clone, Copy)]
+enum Item {
+    Language,
+    Profile,
+    SnakeName,
+    ScreenShake,
+    Trail,
+    Volume,
+    Mute,
+    Bell,
+    Theme,
+    Appearance,
+    HeadMarker,
+    Background,
+    Border,
+    BorderColor,
+    PixelBoard,
+    RecordCasts,
+    Apples,
+    ShiftingWalls,
+    MovingFood,
+    Mice,
+    AdaptiveSpeed,
+    RoamingSnakes,
+    Spotlight,
+    TargetLength,
+    KeptTopScores,
+    KeptRecentGames,
+    #[cfg(feature = "presence")]
+    DiscordPresence,
+    #[cfg(feature = "gamepad")]
+    Gamepad(GamepadAction),
+}
+
+fn items() -> Vec<Item> {
+    #[allow(unused_mut)]
+    let mut items = vec![
+        Item::Language,
+        Item::Profile,
+        Item::SnakeName,
+        Item::ScreenShake,
+        Item::Trail,
+        Item::Volume,
+        Item::Mute,
+        Item::Bell,
+        Item::Theme,
+        Item::Appearance,
+        Item::HeadMarker,
+        Item::Background,
+        Item::Border,
+        Item::BorderColor,
+        Item::PixelBoard,
+        Item::RecordCasts,
+        Item::Apples,
+        Item::ShiftingWalls,
+        Item::MovingFood,
+        Item::Mice,
+        Item::AdaptiveSpeed,
+        Item::RoamingSnakes,
+        Item::Spotlight,
+        Item::TargetLength,
+        Item::KeptTopScores,
+        Item::KeptRecentGames,
+    ];
+
+    #[cfg(feature = "presence")]
+    items.push(Item::DiscordPresence);
+    #[cfg(feature = "gamepad")]
+    items.extend(GamepadAction::ALL.map(Item::Gamepad));
+
+    items
+}
+
+pub struct SettingsTui {
+    settings: Settings,
+    sound: SoundPlayer,
+    exit: bool,
+    state: ListState,
+    /// Set while the snake name is being typed.
+    naming: Option<TextInput>,
+    #[cfg(feature = "gamepad")]
+    capturing: Option<GamepadAction>,
+}
+
+impl Default for SettingsTui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsTui {
+    pub fn new() -> Self {
+        let mut state = ListState::default();
+        state.select_first();
+
+        let settings = Settings::new();
+
+        Self {
+            sound: SoundPlayer::new(&settings),
+            settings,
+            exit: false,
+            state,
+            naming: None,
+            #[cfg(feature = "gamepad")]
+            capturing: None,
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_events()?;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
+            KeyCode::Char('h') | KeyCode::Left => self.change_selected(false),
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter | KeyCode::Char(' ') => {
+                self.change_selected(true)
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        #[cfg(feature = "gamepad")]
+        if let Some(action) = self.capturing {
+            return self.capture_button(action);
+        }
+
+        match events::next(events::MENU_FRAME)? {
+            Some(Event::Key(key_event))
+                if key_event.kind == KeyEventKind::Press && self.naming.is_some() =>
+            {
+                self.naming_key(key_event)
+            }
+            Some(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                self.key_event(key_event)
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn naming_key(&mut self, key_event: KeyEvent) {
+        let Some(input) = &mut self.naming else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc => self.naming = None,
+            KeyCode::Enter => {
+                self.settings.set_snake_name(&input.get_value());
+                self.naming = None;
+            }
+            _ => {
+                input.key_event(key_event);
+            }
+        }
+    }
+
+    fn selected_item(&self) -> Option<Item> {
+        self.state
+            .selected()
+            .and_then(|index| items().get(index).copied())
+    }
+
+    /// Waits for a raw controller button to bind, with esc cancelling.
+    #[cfg(feature = "gamepad")]
+    fn capture_button(&mut self, action: GamepadAction) -> io::Result<()> {
+        if let Some(button) = gamepad::take_captured() {
+            self.settings.set_gamepad_button(action, button);
+            gamepad::set_mapping(self.settings.get_gamepad_mapping());
+            self.capturing = None;
+        } else if events::poll(std::time::Duration::from_millis(50))? {
+            if let Some(Event::Key(key_event)) = events::read()? {
+                if key_event.code == KeyCode::Esc {
+                    gamepad::cancel_capture();
+                    self.capturing = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn select_next(&mut self) {
+        self.state.select_next();
+        self.sound.play(Sound::MenuMove);
+    }
+
+    fn select_previous(&mut self) {
+        self.state.select_previous();
+        self.sound.play(Sound::MenuMove);
+    }
+
+    /// Saves the settings and moves to the next or previous profile, the default one included.
+    fn switch_profile(&mut self, increase: bool) {
+        let mut profiles: Vec<Option<String>> = vec![None];
+        profiles.extend(Settings::list_profiles().into_iter().map(Some));
+        let current = Settings::get_profile();
+        if !profiles.contains(&current) {
+            profiles.push(current.clone());
+        }
+
+        let index = profiles.iter().position(|p| *p == current).unwrap_or(0);
+        let next = match increase {
+            true => (index + 1) % profiles.len(),
+            false => (index + profiles.len() - 1) % profiles.len(),
+        };
+        if Settings::use_profile(profiles[next].as_deref()).is_ok() {
+            self.settings = Settings::new();
+            i18n::load(self.settings.get_language());
+            #[cfg(feature = "gamepad")]
+            gamepad::set_mapping(self.settings.get_gamepad_mapping());
+        }
+    }
+
+    /// Moves to the next or previous language there is a catalog for.
+    fn switch_language(&mut self, increase: bool) {
+        let languages = Messages::list_languages();
+        let index = languages
+            .iter()
+            .position(|language| language == self.settings.get_language())
+            .unwrap_or(0);
+        let next = match increase {
+            true => (index + 1) % languages.len(),
+            false => (index + languages.len() - 1) % languages.len(),
+        };
+        self.settings.set_language(languages[next].clone());
+        i18n::load(self.settings.get_language());
+    }
+
+    fn change_selected(&mut self, increase: bool) {
+        match self.selected_item() {
+            Some(Item::Language) => self.switch_language(increase),
+            Some(Item::Profile) => self.switch_profile(increase),
+            Some(Item::SnakeName) => {
+                let name = self.settings.get_snake_name();
+                self.naming = Some(TextInput::new(MAX_SNAKE_NAME_LEN).with_value(name));
+            }
+            Some(Item::ScreenShake) => self.settings.toggle_screen_shake(),
+            Some(Item::Trail) => self.settings.toggle_trail(),
+            Some(Item::Volume) if increase => self.settings.increase_volume(),
+            Some(Item::Volume) => self.settings.decrease_volume(),
+            Some(Item::Mute) => self.settings.toggle_mute(),
+            Some(Item::Bell) => self.settings.toggle_bell(),
+            Some(Item::Theme) => self.settings.next_theme(),
+            Some(Item::Appearance) => self.settings.next_appearance(),
+            Some(Item::HeadMarker) => self.settings.toggle_head_marker(),
+            Some(Item::Background) => self.settings.next_background(),
+            Some(Item::Border) => self.settings.next_border_set(),
+            Some(Item::BorderColor) => self.settings.next_border_color(),
+            Some(Item::PixelBoard) => self.settings.toggle_pixel_board(),
+            Some(Item::RecordCasts) => self.settings.toggle_record_casts(),
+            Some(Item::Apples) if increase => self.settings.increase_apples(),
+            Some(Item::Apples) => self.settings.decrease_apples(),
+            Some(Item::ShiftingWalls) if increase => self.settings.increase_shifting_walls(),
+            Some(Item::ShiftingWalls) => self.settings.decrease_shifting_walls(),
+            Some(Item::MovingFood) => self.settings.toggle_moving_food(),
+            Some(Item::Mice) => self.settings.toggle_mice(),
+            Some(Item::AdaptiveSpeed) => self.settings.toggle_adaptive_speed(),
+            Some(Item::RoamingSnakes) => self.settings.toggle_roaming_snakes(),
+            Some(Item::Spotlight) => self.settings.toggle_spotlight(),
+            Some(Item::TargetLength) if increase => self.settings.increase_target_length(),
+            Some(Item::TargetLength) => self.settings.decrease_target_length(),
+            Some(Item::KeptTopScores) if increase => self.settings.increase_kept_top_scores(),
+            Some(Item::KeptTopScores) => self.settings.decrease_kept_top_scores(),
+            Some(Item::KeptRecentGames) if increase => self.settings.increase_kept_recent_games(),
+            Some(Item::KeptRecentGames) => self.settings.decrease_kept_recent_games(),
+            #[cfg(feature = "presence")]
+            Some(Item::DiscordPresence) => self.settings.toggle_discord_presence(),
+            #[cfg(feature = "gamepad")]
+            Some(Item::Gamepad(action)) => {
+                gamepad::start_capture();
+                self.capturing = Some(action);
+            }
+            None => {}
+        }
+        self.refresh_sound();
+    }
+
+    fn refresh_sound(&mut self) {
+        self.sound = SoundPlayer::new(&self.settings);
+        self.sound.play(Sound::MenuMove);
+    }
+
+    fn label(&self, item: Item) -> String {
+        match item {
+            Item::Language => tf("settings.language", &[("language", &t("language.name"))]),
+            Item::Profile => tf(
+                "settings.profile",
+                &[(
+                    "profile",
+                    &Settings::get_profile().unwrap_or_else(|| t("settings.default_profile")),
+                )],
+            ),
+            Item::SnakeName => match self.settings.get_snake_name() {
+                "" => t("settings.snake_unnamed"),
+                name => tf("settings.snake_name", &[("name", &name)]),
+            },
+            Item::ScreenShake => on_off(
+                "settings.screen_shake",
+                self.settings.is_screen_shake_enabled(),
+            ),
+            Item::Trail => on_off("settings.trail", self.settings.is_trail_enabled()),
+            Item::Volume => tf(
+                "settings.volume",
+                &[("volume", &self.settings.get_volume())],
+            ),
+            Item::Mute => on_off("settings.mute", self.settings.is_muted()),
+            Item::Bell => on_off("settings.bell", self.settings.is_bell_enabled()),
+            Item::Theme => tf(
+                "settings.theme",
+                &[("theme", &self.settings.get_theme().get_name())],
+            ),
+            Item::HeadMarker => on_off(
+                "settings.head_marker",
+                self.settings.is_head_marker_enabled(),
+            ),
+            Item::Appearance => match self.settings.get_appearance() {
+                Appearance::Auto => tf(
+                    "settings.appearance_auto",
+                    &[(
+                        "appearance",
+                        &Appearance::Auto.resolve(theme::detected()).get_name(),
+                    )],
+                ),
+                appearance => tf(
+                    "settings.appearance",
+                    &[("appearance", &appearance.get_name())],
+                ),
+            },
+            Item::Background => tf(
+                "settings.background",
+                &[
+                    ("theme", &self.settings.get_theme().get_name()),
+                    ("background", &self.settings.get_background().get_name()),
+                ],
+            ),
+            Item::Border => tf(
+                "settings.border",
+                &[
+                    ("theme", &self.settings.get_theme().get_name()),
+                    ("border", &self.settings.get_border().get_set().get_name()),
+                ],
+            ),
+            Item::BorderColor => match self.settings.get_border().get_color() {
+                Some(color) => tf("settings.border_color", &[("color", &color.get_name())]),
+                None => t("settings.border_color_theme"),
+            },
+            Item::PixelBoard => on_off(
+                match kitty::is_supported() {
+                    true => "settings.pixel_board",
+                    false => "settings.pixel_board_unsupported",
+                },
+                self.settings.is_pixel_board_enabled(),
+            ),
+            Item::RecordCasts => {
+                on_off("settings.record_casts", self.settings.is_recording_casts())
+            }
+            Item::Apples => tf(
+                "settings.apples",
+                &[("apples", &self.settings.get_apples())],
+            ),
+            Item::ShiftingWalls => match self.settings.get_shifting_walls() {
+                0 => t("settings.shifting_walls_off"),
+                1 => t("settings.shifting_walls_every_apple"),
+                every => tf("settings.shifting_walls", &[("every", &every)]),
+            },
+            Item::MovingFood => on_off(
+                "settings.moving_food",
+                self.settings.is_moving_food_enabled(),
+            ),
+            Item::Mice => on_off("settings.mice", self.settings.is_mice_enabled()),
+            Item::AdaptiveSpeed => on_off(
+                "settings.adaptive_speed",
+                self.settings.is_adaptive_speed_enabled(),
+            ),
+            Item::RoamingSnakes => on_off(
+                "settings.roaming_snakes",
+                self.settings.is_roaming_snakes_enabled(),
+            ),
+            Item::Spotlight => on_off("settings.spotlight", self.settings.is_spotlight_enabled()),
+            Item::TargetLength => tf(
+                "settings.target_length",
+                &[("length", &self.settings.get_target_length())],
+            ),
+            Item::KeptTopScores => match self.settings.get_kept_top_scores() {
+                0 => t("settings.kept_top_scores_all"),
+                kept => tf("settings.kept_top_scores", &[("kept", &kept)]),
+            },
+            Item::KeptRecentGames => match self.settings.get_kept_recent_games() {
+                0 => t("settings.kept_recent_games_all"),
+                kept => tf("settings.kept_recent_games", &[("kept", &kept)]),
+            },
+            #[cfg(feature = "presence")]
+            Item::DiscordPresence => on_off(
+                match presence::is_available() {
+                    true => "settings.discord_presence",
+                    false => "settings.discord_presence_unavailable",
+                },
+                self.settings.is_discord_presence_enabled(),
+            ),
+            #[cfg(feature = "gamepad")]
+            Item::Gamepad(action) if self.capturing == Some(action) => tf(
+                "settings.gamepad_capture",
+                &[("action", &action.get_name())],
+            ),
+            #[cfg(feature = "gamepad")]
+            Item::Gamepad(action) => tf(
+                "settings.gamepad",
+                &[
+                    ("action", &action.get_name()),
+                    (
+                        "button",
+                        &self.settings.get_gamepad_mapping().get_button(action),
+                    ),
+                ],
+            ),
+        }
+    }
+
+    fn render_header(area: Rect, buf: &mut Buffer) {
+        Paragraph::new(t("settings.title"))
+            .bold()
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let keys = match self.naming {
+            Some(_) => t("common.naming_footer"),
+            None => t("settings.footer"),
+        };
+        Paragraph::new(keys).centered().render(area, buf);
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(t("settings.list")).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let items: Vec<_> = items()
+            .into_iter()
+            .map(|item| match (item, &self.naming) {
+                (Item::SnakeName, Some(input)) => {
+                    let mut line = input.line();
+                    line.spans
+                        .insert(0, Span::raw(t("settings.snake_name_prompt")));
+                    ListItem::from(line)
+                }
+                _ => ListItem::from(self.label(item)),
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+}
+
+/// The label of `key` with its `{value}` filled in as on or off.
+fn on_off(key: &str, value: bool) -> String {
+    let value = match value {
+        true => t("common.on"),
+        false => t("common.off"),
+    };
+    tf(key, &[("value", &value)])
+}
+
+impl Widget for &mut SettingsTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        SettingsTui::render_header(header_area, buf);
+        self.render_footer(footer_area, buf);
+        self.render_list(main_area, buf);
+    }
+}
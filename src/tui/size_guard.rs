@@ -0,0 +1,85 @@
+use std::io;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    text::Line,
+    widgets::{Paragraph, Widget, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    toast::Toasts,
+};
+
+/// The smallest terminal the menus are laid out for.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 16;
+
+/// Shown instead of a screen that does not fit the terminal, until it is resized.
+pub struct SizeGuardTui {
+    width: u16,
+    height: u16,
+}
+
+impl SizeGuardTui {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+
+    pub fn fits(&self, area: Rect) -> bool {
+        area.width >= self.width && area.height >= self.height
+    }
+
+    /// Waits until the terminal is big enough; `false` when the player quit instead.
+    pub fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<bool> {
+        loop {
+            let size = terminal.size()?;
+            if self.fits(Rect::new(0, 0, size.width, size.height)) {
+                return Ok(true);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Some(Event::Key(key_event)) = events::read()? {
+                if key_event.kind == KeyEventKind::Press
+                    && matches!(key_event.code, KeyCode::Char('q') | KeyCode::Char('Q'))
+                {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        frame.render_widget(Toasts, frame.area());
+    }
+}
+
+impl Widget for &SizeGuardTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::from(tf(
+                "size_guard.too_small",
+                &[
+                    ("width", &self.width),
+                    ("height", &self.height),
+                    ("have_width", &area.width),
+                    ("have_height", &area.height),
+                ],
+            )),
+            Line::from(t("size_guard.footer")),
+        ];
+
+        let [middle] = Layout::vertical([Constraint::Length(lines.len() as u16 + 1)])
+            .flex(Flex::Center)
+            .areas(area);
+        Paragraph::new(lines)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .render(middle, buf);
+    }
+}
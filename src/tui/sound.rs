@@ -0,0 +1,247 @@
+use std::io::{self, Write};
+
+use snake_game::core::Settings;
+
+#[derive(Clone, Copy)]
+pub enum Sound {
+    Eat,
+    Death,
+    NewRecord,
+    /// A mouse caught, worth more than an apple.
+    PowerUp,
+    /// A gate opening after its stage's apples were eaten.
+    Gate,
+    MenuMove,
+}
+
+/// Plays short effect sounds and, when enabled, rings the terminal bell for key moments.
+///
+/// Real audio is only produced when built with the `audio` feature.
+pub struct SoundPlayer {
+    bell: bool,
+    #[cfg(feature = "audio")]
+    volume: Option<f32>,
+}
+
+impl SoundPlayer {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            bell: settings.is_bell_enabled(),
+            #[cfg(feature = "audio")]
+            volume: (!settings.is_muted() && settings.get_volume() > 0)
+                .then(|| settings.get_volume() as f32 / 100.0),
+        }
+    }
+
+    /// A player that never makes a sound.
+    pub fn silent() -> Self {
+        Self {
+            bell: false,
+            #[cfg(feature = "audio")]
+            volume: None,
+        }
+    }
+
+    pub fn play(&self, sound: Sound) {
+        if self.bell && !matches!(sound, Sound::MenuMove) {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+
+        #[cfg(feature = "audio")]
+        if let Some(volume) = self.volume {
+            backend::play(&Self::tones(sound), volume);
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+impl SoundPlayer {
+    /// `(start frequency, end frequency, milliseconds)` segments of a sound.
+    fn tones(sound: Sound) -> Vec<(f32, f32, u32)> {
+        match sound {
+            Sound::Eat => vec![(660.0, 990.0, 70)],
+            Sound::Death => vec![(440.0, 110.0, 350)],
+            Sound::NewRecord => vec![(523.0, 523.0, 90), (659.0, 659.0, 90), (784.0, 1046.0, 160)],
+            Sound::PowerUp => vec![
+                (440.0, 880.0, 60),
+                (880.0, 1320.0, 60),
+                (1320.0, 1760.0, 90),
+            ],
+            Sound::Gate => vec![(196.0, 196.0, 60), (262.0, 392.0, 180)],
+            Sound::MenuMove => vec![(520.0, 520.0, 25)],
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+mod backend {
+    use std::{
+        collections::VecDeque,
+        f32::consts::TAU,
+        io::Write,
+        process::{Child, ChildStdin, Command, Stdio},
+        sync::{
+            mpsc::{self, Receiver, Sender},
+            OnceLock,
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    const SAMPLE_RATE: u32 = 22_050;
+    /// Samples written at a time, 20 ms of sound.
+    const CHUNK: usize = 441;
+    /// How far the stream is written ahead of what is playing, which is also about how long
+    /// a new sound waits before it is mixed in.
+    const LEAD: Duration = Duration::from_millis(40);
+    /// A player still running this long after it started is taken to have found a device.
+    const STARTUP: Duration = Duration::from_millis(100);
+    /// Players that take raw 16-bit mono PCM on stdin: ALSA, PulseAudio/PipeWire, then SoX,
+    /// which also runs on macOS and Windows.
+    const PLAYERS: [(&str, &[&str]); 3] = [
+        (
+            "aplay",
+            &["-q", "-t", "raw", "-f", "S16_LE", "-r", "22050", "-c", "1"],
+        ),
+        (
+            "paplay",
+            &["--raw", "--format=s16le", "--rate=22050", "--channels=1"],
+        ),
+        (
+            "play",
+            &[
+                "-q",
+                "-t",
+                "raw",
+                "-e",
+                "signed-integer",
+                "-b",
+                "16",
+                "-r",
+                "22050",
+                "-c",
+                "1",
+                "-",
+            ],
+        ),
+    ];
+
+    static OUTPUT: OnceLock<Sender<Vec<i16>>> = OnceLock::new();
+
+    /// Hands the sound to the output stream so the game never waits.
+    pub fn play(tones: &[(f32, f32, u32)], volume: f32) {
+        let output = OUTPUT.get_or_init(|| {
+            let (sender, sounds) = mpsc::channel();
+            thread::spawn(move || stream_sounds(sounds));
+            sender
+        });
+        let _ = output.send(samples(tones, volume));
+    }
+
+    /// The one player process that lives as long as the game.
+    struct Stream {
+        child: Child,
+        stdin: ChildStdin,
+    }
+
+    impl Drop for Stream {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    /// Starts the next player in `players` that keeps running, skipping the ones that are not
+    /// installed or exit at once, e.g. for want of a sound device or server.
+    fn open<'a>(
+        players: &mut impl Iterator<Item = &'a (&'a str, &'a [&'a str])>,
+    ) -> Option<Stream> {
+        players.find_map(|(program, args)| {
+            let mut child = Command::new(program)
+                .args(*args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let stdin = child.stdin.take()?;
+            let mut stream = Stream { child, stdin };
+
+            thread::sleep(STARTUP);
+            matches!(stream.child.try_wait(), Ok(None)).then_some(stream)
+        })
+    }
+
+    /// Mixes sounds that overlap instead of queueing them and writes them as they play, no
+    /// more than `LEAD` ahead, so later sounds join in; nothing is written while quiet. When a
+    /// player stops, the next one takes over, and once none is left the game stays silent.
+    fn stream_sounds(sounds: Receiver<Vec<i16>>) {
+        let mut players = PLAYERS.iter();
+        let mut stream = None;
+        let mut pending = VecDeque::new();
+        let mut written_until = Instant::now();
+        loop {
+            if pending.is_empty() {
+                match sounds.recv() {
+                    Ok(sound) => mix(&mut pending, sound),
+                    Err(_) => return,
+                }
+                written_until = written_until.max(Instant::now());
+            }
+            while let Ok(sound) = sounds.try_recv() {
+                mix(&mut pending, sound);
+            }
+
+            if stream.is_none() {
+                stream = open(&mut players);
+                if stream.is_none() {
+                    return;
+                }
+            }
+            let chunk: Vec<u8> = pending
+                .drain(..CHUNK.min(pending.len()))
+                .flat_map(i16::to_le_bytes)
+                .collect();
+            if let Some(Stream { stdin, .. }) = &mut stream {
+                if stdin.write_all(&chunk).is_err() {
+                    stream = None;
+                    continue;
+                }
+            }
+
+            written_until += Duration::from_secs(chunk.len() as u64 / 2) / SAMPLE_RATE;
+            if let Some(ahead) = written_until.checked_duration_since(Instant::now() + LEAD) {
+                thread::sleep(ahead);
+            }
+        }
+    }
+
+    fn mix(pending: &mut VecDeque<i16>, sound: Vec<i16>) {
+        for (i, sample) in sound.into_iter().enumerate() {
+            match pending.get_mut(i) {
+                Some(mixed) => *mixed = mixed.saturating_add(sample),
+                None => pending.push_back(sample),
+            }
+        }
+    }
+
+    fn samples(tones: &[(f32, f32, u32)], volume: f32) -> Vec<i16> {
+        let mut phase = 0.0;
+        let mut samples = Vec::new();
+
+        for &(from, to, millis) in tones {
+            let count = SAMPLE_RATE * millis / 1000;
+            for i in 0..count {
+                let progress = i as f32 / count as f32;
+                let frequency = from + (to - from) * progress;
+                let fade = (1.0 - progress).min(progress * 20.0).min(1.0);
+
+                phase = (phase + TAU * frequency / SAMPLE_RATE as f32) % TAU;
+                samples.push((phase.sin() * fade * volume * i16::MAX as f32 * 0.5) as i16);
+            }
+        }
+
+        samples
+    }
+}
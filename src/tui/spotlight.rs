@@ -0,0 +1,57 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Text},
+};
+
+use snake_game::core::{Cell, CellKind};
+
+/// Cells this far from the head, or closer, are lit.
+const LIGHT_RADIUS: i32 = 5;
+/// Cells this close to an apple catch its glow.
+const GLOW_RADIUS: i32 = 1;
+const DARK: &str = " ";
+
+/// Darkens a rendered board but for a circle around the snake's head, letting each apple
+/// glow faintly through the dark. `text` holds a span per cell of `cells`.
+pub fn apply(cells: &[Vec<Cell>], text: Text<'static>) -> Text<'static> {
+    let Some(head) = find(cells, CellKind::SnakeHead) else {
+        return text;
+    };
+    let food: Vec<(i32, i32)> = positions(cells)
+        .filter(|(x, y)| cells[*x as usize][*y as usize].get_kind() == CellKind::Food)
+        .collect();
+
+    let glow = Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM);
+    let mut text = text;
+    for (x, line) in text.lines.iter_mut().enumerate() {
+        for (y, span) in line.spans.iter_mut().enumerate() {
+            let (x, y) = (x as i32, y as i32);
+            let kind = cells[x as usize][y as usize].get_kind();
+            if kind == CellKind::Border || within(head, (x, y), LIGHT_RADIUS) {
+                continue;
+            }
+            *span = if food.iter().any(|apple| within(*apple, (x, y), GLOW_RADIUS)) {
+                Span::styled(span.content.clone(), glow)
+            } else {
+                Span::raw(DARK)
+            };
+        }
+    }
+    text
+}
+
+fn positions(cells: &[Vec<Cell>]) -> impl Iterator<Item = (i32, i32)> + '_ {
+    cells
+        .iter()
+        .enumerate()
+        .flat_map(|(x, row)| (0..row.len()).map(move |y| (x as i32, y as i32)))
+}
+
+fn find(cells: &[Vec<Cell>], kind: CellKind) -> Option<(i32, i32)> {
+    positions(cells).find(|(x, y)| cells[*x as usize][*y as usize].get_kind() == kind)
+}
+
+fn within(from: (i32, i32), to: (i32, i32), radius: i32) -> bool {
+    let (dx, dy) = (from.0 - to.0, from.1 - to.1);
+    dx * dx + dy * dy <= radius * radius
+}
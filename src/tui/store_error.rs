@@ -0,0 +1,176 @@
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Paragraph, Widget, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use super::{
+    events,
+    i18n::{t, tf},
+    modal::{Modal, ModalResult},
+    toast::{self, Toasts},
+};
+use snake_game::core::{list_backups, Backup, StoreError};
+
+/// Shows the files that could not be read or saved, one at a time, with a retry.
+pub struct StoreErrorTui {
+    errors: Vec<StoreError>,
+    /// The backups offered for the file that could not be read, while the player picks one.
+    restore: Option<(Modal, Vec<Backup>)>,
+    quit: bool,
+}
+
+impl StoreErrorTui {
+    pub fn new(errors: Vec<StoreError>) -> Self {
+        Self {
+            errors,
+            restore: None,
+            quit: false,
+        }
+    }
+
+    /// Returns `false` when the player chose to quit the game.
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<bool> {
+        while !self.errors.is_empty() && !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Some(Event::Key(key_event)) = events::read()? {
+                if key_event.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if let Some((modal, backups)) = &mut self.restore {
+                    match modal.key_event(key_event) {
+                        Some(ModalResult::Chosen(index)) => {
+                            let backup = backups[index].clone();
+                            self.restore = None;
+                            self.restore_backup(&backup);
+                        }
+                        Some(_) => self.restore = None,
+                        None => {}
+                    }
+                    continue;
+                }
+                match key_event.code {
+                    KeyCode::Char('r') | KeyCode::Char('R') => self.retry(),
+                    KeyCode::Char('b') | KeyCode::Char('B') if !self.errors[0].is_write() => {
+                        self.ask_backup()
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S') => {
+                        self.errors.remove(0);
+                    }
+                    KeyCode::Char('q') | KeyCode::Char('Q') => self.quit = true,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(!self.quit)
+    }
+
+    fn retry(&mut self) {
+        match self.errors[0].retry() {
+            Ok(_) => {
+                self.errors.remove(0);
+            }
+            Err(e) => self.errors[0] = e,
+        }
+    }
+
+    fn ask_backup(&mut self) {
+        let path = self.errors[0].get_path().to_string();
+        let backups = list_backups(&path);
+        if backups.is_empty() {
+            return toast::push(tf("store_error.no_backups", &[("path", &path)]));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let options = backups
+            .iter()
+            .map(|backup| {
+                let saved = age(now.saturating_sub(backup.get_at()));
+                tf("store_error.backup", &[("saved", &saved)])
+            })
+            .collect();
+        let modal = Modal::choice(
+            &t("store_error.restore_title"),
+            &tf("store_error.restore_question", &[("path", &path)]),
+            options,
+        );
+        self.restore = Some((modal, backups));
+    }
+
+    fn restore_backup(&mut self, backup: &Backup) {
+        match backup.restore() {
+            Ok(()) => {
+                toast::push(tf("store_error.restored", &[("path", &backup.get_path())]));
+                self.retry();
+            }
+            Err(e) => toast::push(e.to_string()),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+        if let Some((modal, _)) = &self.restore {
+            frame.render_widget(modal, frame.area());
+        }
+        frame.render_widget(Toasts, frame.area());
+    }
+}
+
+/// How long ago something happened, `seconds` back.
+pub(super) fn age(seconds: u64) -> String {
+    match seconds {
+        0..60 => t("common.just_now"),
+        60..3600 => tf("common.minutes_ago", &[("minutes", &(seconds / 60))]),
+        3600..86400 => tf("common.hours_ago", &[("hours", &(seconds / 3600))]),
+        _ => tf("common.days_ago", &[("days", &(seconds / 86400))]),
+    }
+}
+
+impl Widget for &StoreErrorTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let error = &self.errors[0];
+        let title = Line::from(match self.errors.len() {
+            1 => t("store_error.title"),
+            left => tf("store_error.title_left", &[("left", &left)]),
+        });
+        let skip = t(match error.is_write() {
+            true => "store_error.skip_write",
+            false => "store_error.skip_read",
+        });
+        let instructions = Line::from(t(match error.is_write() {
+            true => "store_error.write_footer",
+            false => "store_error.read_footer",
+        }));
+
+        let text = Text::from(vec![
+            Line::from(error.get_message()).red(),
+            Line::default(),
+            Line::from(skip),
+        ]);
+
+        Paragraph::new(text)
+            .wrap(Wrap { trim: true })
+            .centered()
+            .block(
+                Block::new()
+                    .border_type(BorderType::Rounded)
+                    .title(title.centered())
+                    .title_bottom(instructions.centered()),
+            )
+            .render(area, buf);
+    }
+}
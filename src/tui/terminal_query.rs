@@ -0,0 +1,61 @@
+//! Asks the terminal about itself with escape sequences and reads its answer from stdin.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+/// How long the terminal gets to answer.
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Sends `request` followed by a device attributes query, which every terminal answers, and
+/// returns everything read up to that answer, never past it. Must run in raw mode, before
+/// anything else reads input. Reads on this thread and only while input is waiting, so
+/// nothing is left reading stdin once it returns.
+pub fn ask(request: &[u8]) -> Option<String> {
+    // Terminals elsewhere answer through console events rather than stdin.
+    if !cfg!(unix) || !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return None;
+    }
+    let mut stdout = io::stdout();
+    stdout.write_all(request).ok()?;
+    stdout.write_all(b"\x1b[c").ok()?;
+    stdout.flush().ok()?;
+
+    let deadline = Instant::now() + TIMEOUT;
+    let mut reply = Vec::new();
+    // One byte at a time, so nothing typed after the answer is taken from the game.
+    while let Some(byte) = read_byte(deadline.saturating_duration_since(Instant::now())) {
+        reply.push(byte);
+        // The device attributes answer is `ESC [ ? ... c`.
+        if byte == b'c' && reply.windows(3).any(|w| w == b"\x1b[?") {
+            return Some(String::from_utf8_lossy(&reply).to_string());
+        }
+    }
+    None
+}
+
+/// A byte from stdin, straight from the file descriptor so no buffer keeps what follows it,
+/// or `None` when none comes within `timeout`.
+#[cfg(unix)]
+fn read_byte(timeout: Duration) -> Option<u8> {
+    let mut poll = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+    // SAFETY: `poll` points to one valid pollfd for the length of the call.
+    if millis == 0 || unsafe { libc::poll(&mut poll, 1, millis) } <= 0 {
+        return None;
+    }
+    let mut byte = 0u8;
+    // SAFETY: reads at most one byte into `byte`, which outlives the call.
+    let read = unsafe { libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) };
+    (read == 1).then_some(byte)
+}
+
+#[cfg(not(unix))]
+fn read_byte(_timeout: Duration) -> Option<u8> {
+    None
+}
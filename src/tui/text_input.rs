@@ -0,0 +1,127 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// A single-line text field with a cursor, used wherever a screen asks for a name, path or number.
+pub struct TextInput {
+    value: Vec<char>,
+    /// Index into `value` of the character the cursor sits before.
+    cursor: usize,
+    max_len: usize,
+    /// Characters the field accepts; anything else is ignored as it is typed.
+    filter: fn(char) -> bool,
+    /// Checks the whole value once it is submitted.
+    validator: fn(&str) -> Result<(), String>,
+}
+
+impl TextInput {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            value: Vec::new(),
+            cursor: 0,
+            max_len,
+            filter: |c| !c.is_control(),
+            validator: |_| Ok(()),
+        }
+    }
+
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn with_filter(mut self, filter: fn(char) -> bool) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_validator(mut self, validator: fn(&str) -> Result<(), String>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    pub fn get_value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value
+            .chars()
+            .filter(|c| (self.filter)(*c))
+            .take(self.max_len)
+            .collect();
+        self.cursor = self.value.len();
+    }
+
+    /// Inserts `text` at the cursor, as if it were typed.
+    pub fn paste(&mut self, text: &str) {
+        for c in text.chars().filter(|c| (self.filter)(*c)) {
+            if self.value.len() >= self.max_len {
+                break;
+            }
+            self.value.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    /// The trimmed value when it passes the validator.
+    pub fn validate(&self) -> Result<String, String> {
+        let value = self.get_value().trim().to_string();
+        (self.validator)(&value).map(|_| value)
+    }
+
+    /// Edits the value, returning whether the key was used; Enter and Esc are left to the screen.
+    pub fn key_event(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char(c) if (self.filter)(c) => {
+                if self.value.len() < self.max_len {
+                    self.value.insert(self.cursor, c);
+                    self.cursor += 1;
+                }
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.value.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.value.len() => {
+                self.value.remove(self.cursor);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// The value with the cursor drawn as a reversed cell.
+    pub fn line(&self) -> Line<'static> {
+        let cursor = Style::new().add_modifier(Modifier::REVERSED);
+        let before: String = self.value[..self.cursor].iter().collect();
+        let under = self
+            .value
+            .get(self.cursor)
+            .map_or(" ".to_string(), char::to_string);
+        let after: String = self
+            .value
+            .get(self.cursor + 1..)
+            .unwrap_or(&[])
+            .iter()
+            .collect();
+
+        Line::from(vec![
+            Span::raw(before),
+            Span::styled(under, cursor),
+            Span::raw(after),
+        ])
+    }
+}
@@ -0,0 +1,299 @@
+use std::sync::OnceLock;
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span, Text},
+};
+
+use super::terminal_query;
+use snake_game::core::{
+    Appearance, Background, BorderSet, Cell, CellKind, Settings, Theme, WallColor,
+};
+
+const HEAD_MARKER: &str = "◉";
+/// Drawn instead of a wall glyph the terminal would not fit in one column.
+const FALLBACK_WALL: &str = "█";
+const DOT: &str = "⋅";
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+static DETECTED: OnceLock<Option<Appearance>> = OnceLock::new();
+
+/// Turns core cells into styled text according to the player's theme settings.
+pub struct Palette {
+    theme: Theme,
+    head_marker: bool,
+    background: Background,
+    /// Dark or light, never `Auto`.
+    appearance: Appearance,
+    /// The muted colors of zen mode, used whatever the theme.
+    soft: bool,
+    frame: BoardFrame,
+}
+
+impl Palette {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            theme: settings.get_theme(),
+            head_marker: settings.is_head_marker_enabled(),
+            background: settings.get_background(),
+            appearance: settings.get_appearance().resolve(detected()),
+            soft: false,
+            frame: BoardFrame::new(settings),
+        }
+    }
+
+    pub fn soft(mut self) -> Self {
+        self.soft = true;
+        self
+    }
+
+    pub fn style(&self, kind: CellKind) -> Style {
+        if self.soft {
+            return Self::soft_style(kind);
+        }
+        match self.theme {
+            Theme::Classic => match kind {
+                CellKind::RivalSnake | CellKind::RivalHead => Style::new().fg(Color::Magenta),
+                CellKind::Shifting => Style::new().fg(self.pick(Color::Yellow, Color::Red)),
+                CellKind::Checkpoint => Style::new().fg(self.pick(Color::Cyan, Color::Blue)),
+                CellKind::Gate => Style::new().fg(self.pick(Color::LightMagenta, Color::Magenta)),
+                _ => Style::new(),
+            },
+            Theme::HighContrast => {
+                let (ink, paper) = (self.ink(), self.paper());
+                let bold = Modifier::BOLD;
+                let bright = |dark, light| {
+                    Style::new()
+                        .fg(self.pick(dark, light))
+                        .bg(paper)
+                        .add_modifier(bold)
+                };
+                let inverted = |dark, light| {
+                    Style::new()
+                        .fg(paper)
+                        .bg(self.pick(dark, light))
+                        .add_modifier(bold)
+                };
+                match kind {
+                    CellKind::Empty => Style::new().bg(paper),
+                    CellKind::Border => Style::new().fg(ink).bg(paper),
+                    CellKind::Wall => Style::new().fg(ink).bg(ink),
+                    CellKind::Food => bright(Color::LightGreen, Color::Green),
+                    CellKind::Snake => bright(Color::LightYellow, Color::Blue),
+                    CellKind::RivalSnake => bright(Color::LightCyan, Color::Magenta),
+                    CellKind::RivalHead => inverted(Color::LightCyan, Color::Magenta),
+                    CellKind::Shifting => bright(Color::LightRed, Color::Red),
+                    CellKind::SnakeHead => inverted(Color::LightYellow, Color::Blue),
+                    CellKind::Checkpoint => bright(Color::LightBlue, Color::Cyan),
+                    CellKind::Gate => bright(Color::LightMagenta, Color::Magenta),
+                }
+            }
+        }
+    }
+
+    pub fn is_light(&self) -> bool {
+        self.appearance == Appearance::Light
+    }
+
+    /// `dark` on a dark background, `light` on a light one.
+    fn pick(&self, dark: Color, light: Color) -> Color {
+        match self.appearance {
+            Appearance::Light => light,
+            _ => dark,
+        }
+    }
+
+    /// The background of the high contrast theme.
+    fn paper(&self) -> Color {
+        self.pick(Color::Black, Color::White)
+    }
+
+    fn ink(&self) -> Color {
+        self.pick(Color::White, Color::Black)
+    }
+
+    fn soft_style(kind: CellKind) -> Style {
+        match kind {
+            CellKind::Border | CellKind::Wall => Style::new().fg(Color::Rgb(120, 130, 150)),
+            CellKind::Food => Style::new().fg(Color::Rgb(230, 180, 190)),
+            CellKind::Snake | CellKind::SnakeHead => Style::new().fg(Color::Rgb(160, 210, 180)),
+            CellKind::RivalSnake | CellKind::RivalHead => {
+                Style::new().fg(Color::Rgb(190, 170, 220))
+            }
+            CellKind::Shifting => Style::new().fg(Color::Rgb(230, 210, 160)),
+            CellKind::Checkpoint => Style::new().fg(Color::Rgb(170, 200, 230)),
+            CellKind::Gate => Style::new().fg(Color::Rgb(210, 170, 210)),
+            CellKind::Empty => Style::new(),
+        }
+    }
+
+    pub fn text(&self, cells: &[Vec<Cell>]) -> Text<'static> {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(x, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(y, cell)| match cell.get_kind() {
+                        CellKind::Empty => self.background(x, y),
+                        CellKind::Border => Span::styled(
+                            self.frame.glyph(cell).to_string(),
+                            self.cell_style(cell).patch(self.frame.style()),
+                        ),
+                        _ => Span::styled(self.glyph(cell), self.cell_style(cell)),
+                    })
+                    .collect::<Line>()
+            })
+            .collect()
+    }
+
+    /// An empty cell at row `x` and column `y`, patterned by the theme's background.
+    fn background(&self, x: usize, y: usize) -> Span<'static> {
+        let style = self.style(CellKind::Empty);
+        let shade = match self.theme {
+            Theme::Classic => self.pick(Color::Indexed(236), Color::Indexed(254)),
+            Theme::HighContrast => self.pick(Color::Indexed(234), Color::Indexed(252)),
+        };
+        match self.background {
+            Background::Checkerboard if (x + y).is_multiple_of(2) => {
+                Span::styled(" ", style.bg(shade))
+            }
+            Background::Dots if x % 2 == 1 && y % 2 == 1 => {
+                Span::styled(DOT, style.fg(self.pick(Color::DarkGray, Color::Gray)))
+            }
+            _ => Span::styled(" ", style),
+        }
+    }
+
+    /// The kind's style, in the color the board gave the cell if any.
+    fn cell_style(&self, cell: &Cell) -> Style {
+        let style = self.style(cell.get_kind());
+        match cell.get_tint() {
+            Some(tint) if !self.soft => {
+                let style = style.fg(tint_color(tint));
+                match self.theme {
+                    Theme::Classic => style,
+                    Theme::HighContrast => style.bg(self.paper()),
+                }
+            }
+            _ => style,
+        }
+    }
+
+    fn glyph(&self, cell: &Cell) -> String {
+        match cell.get_kind() {
+            CellKind::SnakeHead if self.head_marker => HEAD_MARKER.to_string(),
+            _ => glyph(cell).to_string(),
+        }
+    }
+}
+
+/// The lines and color the current theme draws the board's frame with.
+#[derive(Clone, Copy)]
+pub struct BoardFrame {
+    set: border::Set,
+    color: Option<WallColor>,
+}
+
+impl BoardFrame {
+    pub fn new(settings: &Settings) -> Self {
+        let border = settings.get_border();
+        let set = match border.get_set() {
+            BorderSet::Plain => border::PLAIN,
+            BorderSet::Rounded => border::ROUNDED,
+            BorderSet::Double => border::DOUBLE,
+            BorderSet::Thick => border::THICK,
+            BorderSet::Ascii => ASCII_BORDER,
+        };
+        Self {
+            set,
+            color: border.get_color(),
+        }
+    }
+
+    /// The glyph of a border cell in the frame's lines; other cells keep theirs.
+    pub fn glyph<'a>(&self, cell: &'a Cell) -> &'a str {
+        if cell.get_kind() != CellKind::Border {
+            return glyph(cell);
+        }
+        match cell.get_glyph() {
+            "┌" => self.set.top_left,
+            "┐" => self.set.top_right,
+            "└" => self.set.bottom_left,
+            "┘" => self.set.bottom_right,
+            "─" => self.set.horizontal_top,
+            "│" => self.set.vertical_left,
+            other => other,
+        }
+    }
+
+    /// Empty when the frame keeps the theme's color.
+    pub fn style(&self) -> Style {
+        match self.color {
+            Some(color) => Style::new().fg(tint_color(color)),
+            None => Style::new(),
+        }
+    }
+}
+
+/// The cell's glyph, or a plain block for a wall glyph wider than one column.
+pub fn glyph(cell: &Cell) -> &str {
+    match cell.get_kind() {
+        CellKind::Wall if Span::raw(cell.get_glyph()).width() != 1 => FALLBACK_WALL,
+        _ => cell.get_glyph(),
+    }
+}
+
+/// The cells' glyphs, safe to lay out as plain text, the border drawn in `frame`'s lines.
+pub fn table(cells: &[Vec<Cell>], frame: &BoardFrame) -> Vec<Vec<String>> {
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| frame.glyph(cell).to_string())
+                .collect()
+        })
+        .collect()
+}
+
+pub fn tint_color(tint: WallColor) -> Color {
+    match tint {
+        WallColor::Red => Color::Red,
+        WallColor::Green => Color::Green,
+        WallColor::Blue => Color::Blue,
+        WallColor::Yellow => Color::Yellow,
+        WallColor::Magenta => Color::Magenta,
+        WallColor::Cyan => Color::Cyan,
+        WallColor::White => Color::White,
+        WallColor::Gray => Color::Gray,
+    }
+}
+
+/// The terminal's background as found by `detect`, if it could be told.
+pub fn detected() -> Option<Appearance> {
+    DETECTED.get().copied().flatten()
+}
+
+/// Finds out whether the terminal has a dark or light background, from `COLORFGBG` or by
+/// asking the terminal with the OSC 11 query.
+pub fn detect() {
+    DETECTED.get_or_init(|| {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| Appearance::from_colorfgbg(&value))
+            .or_else(|| {
+                terminal_query::ask(b"\x1b]11;?\x1b\\")
+                    .and_then(|reply| Appearance::from_osc_reply(&reply))
+            })
+    });
+}
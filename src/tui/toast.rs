@@ -0,0 +1,68 @@
+//! Short notices any screen can raise, drawn in the top-right corner over whatever is shown.
+
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::border,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+const SHOWN_FOR: Duration = Duration::from_secs(3);
+const MAX_SHOWN: usize = 3;
+const TOAST_HEIGHT: u16 = 3;
+
+static TOASTS: Mutex<VecDeque<(String, Instant)>> = Mutex::new(VecDeque::new());
+
+pub fn push(message: impl Into<String>) {
+    let mut toasts = TOASTS.lock().unwrap();
+    if toasts.len() == MAX_SHOWN {
+        toasts.pop_front();
+    }
+    toasts.push_back((message.into(), Instant::now() + SHOWN_FOR));
+}
+
+/// When the oldest toast shown goes away, so an idle screen knows to draw again.
+pub fn next_expiry() -> Option<Instant> {
+    let mut toasts = TOASTS.lock().unwrap();
+    let now = Instant::now();
+    toasts.retain(|(_, until)| *until > now);
+    toasts.iter().map(|(_, until)| *until).min()
+}
+
+/// Draws the toasts that have not expired yet, the newest at the top.
+pub struct Toasts;
+
+impl Widget for Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut toasts = TOASTS.lock().unwrap();
+        let now = Instant::now();
+        toasts.retain(|(_, until)| *until > now);
+
+        for (index, (message, _)) in toasts.iter().rev().enumerate() {
+            let width = (message.chars().count() as u16 + 4).min(area.width);
+            let y = area.y + index as u16 * TOAST_HEIGHT;
+            if y + TOAST_HEIGHT > area.bottom() {
+                break;
+            }
+            let toast = Rect::new(area.right() - width, y, width, TOAST_HEIGHT);
+
+            Clear.render(toast, buf);
+            Paragraph::new(message.as_str())
+                .style(Style::new().fg(Color::Black).bg(Color::Yellow))
+                .centered()
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_set(border::ROUNDED),
+                )
+                .render(toast, buf);
+        }
+    }
+}
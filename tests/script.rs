@@ -0,0 +1,57 @@
+//! Plays the scripts in `tests/scripts` through the game and checks the state it ends in.
+//! The game needs a terminal, so it is run under `script` from util-linux, which these
+//! tests fail without.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+use serde_json::Value;
+
+/// Plays `name` from `tests/scripts` in a data directory of its own, returning the state
+/// of the last game played.
+fn play(name: &str) -> Value {
+    let dir = env::temp_dir().join(format!("snake-script-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/settings.json"), r#"{"tutorial_done":true}"#).unwrap();
+
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "scripts", name]
+        .iter()
+        .collect();
+    let command = format!(
+        "stty cols 140 rows 45; '{}' --script '{}'",
+        env!("CARGO_BIN_EXE_snake_game"),
+        path.display()
+    );
+    let output = Command::new("script")
+        .args(["-qec", &command, "/dev/null"])
+        .current_dir(&dir)
+        .output()
+        .expect("`script` from util-linux is needed to run the game in a terminal");
+    let _ = fs::remove_dir_all(&dir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .rev()
+        .find_map(|line| line.find('{').map(|start| &line[start..]))
+        .unwrap_or_else(|| panic!("no state in the output of {name}: {stdout}"));
+    serde_json::from_str(line.trim_end()).unwrap()
+}
+
+fn cells(state: &Value) -> Vec<(u64, u64)> {
+    state["snake"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|cell| (cell["x"].as_u64().unwrap(), cell["y"].as_u64().unwrap()))
+        .collect()
+}
+
+#[test]
+fn turns_play_out_the_same_every_run() {
+    let state = play("turns.txt");
+    assert_eq!(state["tick"], 29);
+    assert_eq!(state["direction"], "Up");
+    assert_eq!(cells(&state), [(7, 7), (8, 7), (9, 7)]);
+    assert_eq!(state["over"], false);
+}